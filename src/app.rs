@@ -0,0 +1,834 @@
+use crate::bridge::{
+    action_errors, kelvin_to_mireds, ActionResponse, AlertMode, BridgeClient, BridgeStatus,
+    BrightnessChange, Group, LatencyStats, Light, LightStateUpdate, Scene,
+};
+use crate::config::{load_config, save_config, Config};
+use crate::error::{HueStatusError, Result};
+use crate::scenes::{
+    ColorDefinition, SceneCreationResult, SceneExecutionResult, SceneManager, SceneValidationResult,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Relative brightness drop used for the confirmation flash
+const CONFIRM_DIP_STEP: i16 = -150;
+
+/// Transition time for each half of the confirmation flash, in deciseconds
+const CONFIRM_TRANSITION: u16 = 2;
+
+/// How long the lights stay dimmed during the confirmation flash
+const CONFIRM_HOLD: Duration = Duration::from_millis(300);
+
+/// Outcome of a relative brightness change on the status group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrightnessAdjustment {
+    /// Group brightness before the change
+    pub previous: u8,
+    /// Brightness that was set
+    pub brightness: u8,
+    /// Per-light failures reported by the bridge
+    pub failures: Vec<String>,
+}
+
+/// High-level entry point for embedding huestatus in other applications
+#[derive(Debug, Clone)]
+pub struct Huestatus {
+    config: Config,
+    client: BridgeClient,
+    scene_manager: SceneManager,
+    config_path: Option<PathBuf>,
+    failure_alert: AlertMode,
+    verbose: bool,
+}
+
+impl Huestatus {
+    /// Create a new instance from a loaded configuration
+    pub fn new(config: Config) -> Result<Self> {
+        let settings = &config.settings;
+        let verbose = settings.verbose_logging && !settings.quiet_mode;
+
+        let mut client = BridgeClient::with_config(
+            config.bridge.ip.clone(),
+            settings.timeout_seconds,
+            settings.retry_attempts,
+            settings.retry_delay_seconds,
+            verbose,
+        )?
+        .with_username(config.bridge.application_key.clone())
+        .with_transition_time(settings.transition_time);
+
+        if let Some(user_agent) = &settings.user_agent {
+            client = client.with_user_agent(user_agent.clone())?;
+        }
+        if settings.insecure {
+            client = client.with_insecure(true)?;
+        }
+
+        let scene_manager = SceneManager::new(client.clone()).with_verbose(verbose);
+
+        Ok(Self {
+            config,
+            client,
+            scene_manager,
+            config_path: None,
+            failure_alert: AlertMode::None,
+            verbose,
+        })
+    }
+
+    /// Load the default configuration file with environment overrides applied
+    pub fn load() -> Result<Self> {
+        let mut config = Config::load()?;
+        config.apply_env_overrides()?;
+        config.validate()?;
+        Self::new(config)
+    }
+
+    /// Config file to write refreshed scene IDs back to (enables `auto_refresh_scenes`)
+    pub fn with_config_path(mut self, config_path: Option<PathBuf>) -> Self {
+        self.config_path = config_path;
+        self
+    }
+
+    /// Compare light states before and after each recall
+    pub fn with_change_verification(mut self, verify_changes: bool) -> Self {
+        self.scene_manager = self.scene_manager.with_change_verification(verify_changes);
+        self
+    }
+
+    /// Skip recalling a status scene the lights already show
+    pub fn with_only_on_change(mut self, only_on_change: bool) -> Self {
+        self.scene_manager = self.scene_manager.with_only_on_change(only_on_change);
+        self
+    }
+
+    /// Measure how long the lights take to apply each recalled scene
+    pub fn with_apply_measurement(mut self, measure_apply: bool) -> Self {
+        self.scene_manager = self.scene_manager.with_apply_measurement(measure_apply);
+        self
+    }
+
+    /// Blink or breathe the status lights after each failure recall
+    pub fn with_failure_alert(mut self, alert: AlertMode) -> Self {
+        self.failure_alert = alert;
+        self
+    }
+
+    /// Let scene refreshes overwrite status scenes that were edited outside huestatus
+    pub fn with_overwrite_changes(mut self, overwrite_changes: bool) -> Self {
+        self.scene_manager = self.scene_manager.with_overwrite_changes(overwrite_changes);
+        self
+    }
+
+    /// Fail scene refreshes when a status scene name is taken by a scene huestatus didn't create
+    pub fn with_strict_scene_names(mut self, strict_scene_names: bool) -> Self {
+        self.scene_manager = self
+            .scene_manager
+            .with_strict_scene_names(strict_scene_names);
+        self
+    }
+
+    /// Get the active configuration
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Get the underlying bridge client
+    pub fn client(&self) -> &BridgeClient {
+        &self.client
+    }
+
+    /// Get the underlying scene manager
+    pub fn scene_manager(&self) -> &SceneManager {
+        &self.scene_manager
+    }
+
+    /// Show the success status
+    pub async fn show_success(&self) -> Result<SceneExecutionResult> {
+        self.show_status("success").await
+    }
+
+    /// Show the failure status
+    pub async fn show_failure(&self) -> Result<SceneExecutionResult> {
+        self.show_status("failure").await
+    }
+
+    /// Show the running (in-progress) status
+    pub async fn show_running(&self) -> Result<SceneExecutionResult> {
+        self.show_status("running").await
+    }
+
+    /// Show a status by type ("success", "failure" or "running")
+    ///
+    /// Like the other commands, each call starts with a fresh retry budget.
+    pub async fn show_status(&self, status_type: &str) -> Result<SceneExecutionResult> {
+        self.client.retry_budget().reset();
+
+        if self.verbose {
+            eprintln!("🔍 Executing {status_type} status...");
+            eprintln!("📍 Bridge: {}", self.config.bridge.ip);
+        }
+
+        let mut result = match self
+            .scene_manager
+            .execute_status_scene(status_type, &self.config)
+            .await
+        {
+            Err(HueStatusError::EmptyScene { scene_name }) if self.can_refresh_scenes() => {
+                if !self.config.settings.quiet_mode {
+                    eprintln!("🔄 Scene '{scene_name}' has no lights, refreshing status scenes...");
+                }
+
+                let (config, creation) = self.refresh_and_store_scenes().await?;
+                if !self.config.settings.quiet_mode {
+                    for warning in &creation.warnings {
+                        eprintln!("⚠️ {warning}");
+                    }
+                }
+
+                self.scene_manager
+                    .execute_status_scene(status_type, &config)
+                    .await?
+            }
+            result => result?,
+        };
+
+        // Nothing changed on the lights, so there is nothing to confirm or alert
+        if result.already_showing {
+            return Ok(result);
+        }
+
+        if self.config.settings.confirm_flash && result.success {
+            self.confirm_flash(&result.scene_id).await?;
+        }
+
+        // The failure scene is already showing; a failed alert is only worth a warning
+        if status_type == "failure" && self.failure_alert != AlertMode::None && result.success {
+            match self.send_alert(self.failure_alert).await {
+                Ok(warnings) => result.warnings.extend(warnings),
+                Err(e) => result.warnings.push(format!(
+                    "Could not send the '{}' alert: {e}",
+                    self.failure_alert.as_str()
+                )),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Start an alert effect on the status lights that support it
+    ///
+    /// The whole group gets one request when every light supports alerts;
+    /// otherwise each capable light is alerted on its own.
+    async fn send_alert(&self, alert: AlertMode) -> Result<Vec<String>> {
+        let group_id = &self.config.settings.recall_group_id;
+        let lights = self.client.get_suitable_lights_in_group(group_id).await?;
+        let update = LightStateUpdate::alert(alert);
+
+        if self.verbose {
+            eprintln!("🚨 Sending '{}' alert...", alert.as_str());
+        }
+
+        if lights.iter().all(|(_, light)| light.supports_alert()) {
+            let response = self.client.set_group_action(group_id, &update).await?;
+            return Ok(action_errors(&response));
+        }
+
+        let mut warnings = Vec::new();
+        let mut updates = Vec::new();
+        for (id, light) in &lights {
+            if light.supports_alert() {
+                updates.push((id.clone(), update.clone()));
+            } else {
+                warnings.push(format!(
+                    "Light '{}' ({id}) doesn't support alerts",
+                    light.name
+                ));
+            }
+        }
+        let (_, light_warnings) = self.set_lights(updates).await?;
+        warnings.extend(light_warnings);
+        Ok(warnings)
+    }
+
+    /// Check whether empty status scenes may be recreated automatically
+    fn can_refresh_scenes(&self) -> bool {
+        self.config.settings.auto_refresh_scenes && self.config_path.is_some()
+    }
+
+    /// Rebuild the status scenes from the current lights and store their IDs in the config file
+    ///
+    /// Scenes that still exist keep their IDs; requires `with_config_path`.
+    pub async fn refresh_scenes(&self) -> Result<SceneCreationResult> {
+        let (_, creation) = self.refresh_and_store_scenes().await?;
+        Ok(creation)
+    }
+
+    /// Refresh the status scenes, returning the updated config alongside the result
+    async fn refresh_and_store_scenes(&self) -> Result<(Config, SceneCreationResult)> {
+        let config_path = self
+            .config_path
+            .as_ref()
+            .ok_or(HueStatusError::ConfigNotFound)?;
+
+        let mut config = self.config.clone();
+        let creation = self
+            .scene_manager
+            .refresh_status_scenes(&mut config)
+            .await?;
+
+        // Only the scenes change on disk; command-line and environment overrides stay out
+        let mut stored = load_config(config_path)?;
+        stored.scenes = config.scenes.clone();
+        save_config(&stored, config_path)?;
+
+        Ok((config, creation))
+    }
+
+    /// Dip the status group's brightness, then recall the scene again to restore it
+    async fn confirm_flash(&self, scene_id: &str) -> Result<()> {
+        if self.verbose {
+            eprintln!("💡 Flashing lights to confirm...");
+        }
+
+        let group_id = &self.config.settings.recall_group_id;
+        let dip = LightStateUpdate::brightness_step(CONFIRM_DIP_STEP)
+            .with_transition_time(Some(CONFIRM_TRANSITION));
+        self.client.set_group_action(group_id, &dip).await?;
+
+        tokio::time::sleep(CONFIRM_HOLD).await;
+
+        // Re-recalling restores the exact scene brightness, which bri_inc can't
+        // guarantee once it has been clamped at the minimum
+        self.client
+            .clone()
+            .with_transition_time(Some(CONFIRM_TRANSITION))
+            .execute_scene_on_group(group_id, scene_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Recall an arbitrary scene by ID on the configured group, bypassing the status scenes
+    pub async fn recall_scene(&self, scene_id: &str) -> Result<SceneExecutionResult> {
+        self.client.retry_budget().reset();
+
+        let scene = self.client.get_scene(scene_id).await?;
+        let group_id = &self.config.settings.recall_group_id;
+
+        if self.verbose {
+            eprintln!(
+                "🎬 Recalling scene '{}' ({scene_id}) on group {group_id}...",
+                scene.name
+            );
+        }
+
+        let start_time = std::time::Instant::now();
+        let retries_before = self.client.retry_count();
+        let response = self
+            .client
+            .execute_scene_on_group(group_id, scene_id)
+            .await?;
+
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::SceneExecutionFailed {
+                reason: no_success_reason(&format!("scene '{scene_id}'"), &response),
+            });
+        }
+
+        Ok(SceneExecutionResult {
+            scene_id: scene_id.to_string(),
+            scene_name: scene.name,
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            success: true,
+            state_change: None,
+            apply_latency_ms: None,
+            already_showing: false,
+            warnings: action_errors(&response),
+            retry_count: self.client.retry_count() - retries_before,
+        })
+    }
+
+    /// Show a color on the configured group directly, without a stored scene
+    ///
+    /// The result has an empty `scene_id` and the color name as `scene_name`.
+    pub async fn show_color(&self, color: &ColorDefinition) -> Result<SceneExecutionResult> {
+        self.client.retry_budget().reset();
+
+        if self.verbose {
+            eprintln!("🎨 Showing color {}...", color.summary());
+        }
+
+        let start_time = std::time::Instant::now();
+        let retries_before = self.client.retry_count();
+        let update = color
+            .to_light_state_update()
+            .with_transition_time(self.config.settings.transition_time);
+        let response = self
+            .client
+            .set_group_action(&self.config.settings.recall_group_id, &update)
+            .await?;
+
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::SceneExecutionFailed {
+                reason: no_success_reason(&format!("color '{}'", color.name), &response),
+            });
+        }
+
+        Ok(SceneExecutionResult {
+            scene_id: String::new(),
+            scene_name: color.name.clone(),
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            success: true,
+            state_change: None,
+            apply_latency_ms: None,
+            already_showing: false,
+            warnings: action_errors(&response),
+            retry_count: self.client.retry_count() - retries_before,
+        })
+    }
+
+    /// Show a color on exactly the given lights, bypassing the stored scene and group
+    pub async fn show_color_on_lights(
+        &self,
+        color: &ColorDefinition,
+        light_ids: &[String],
+    ) -> Result<SceneExecutionResult> {
+        self.client.retry_budget().reset();
+
+        if self.verbose {
+            eprintln!(
+                "🎨 Showing color {} on light(s) {}...",
+                color.summary(),
+                light_ids.join(", ")
+            );
+        }
+
+        let start_time = std::time::Instant::now();
+        let retries_before = self.client.retry_count();
+
+        let lights = self.client.get_lights().await?;
+        let unknown = unknown_light_ids(light_ids, &lights);
+        if !unknown.is_empty() {
+            let mut known: Vec<_> = lights.keys().map(String::as_str).collect();
+            known.sort_by_key(|id| (id.len(), *id));
+            return Err(HueStatusError::InvalidConfig {
+                reason: format!(
+                    "unknown light id(s) {} (the bridge has {})",
+                    unknown.join(", "),
+                    known.join(", ")
+                ),
+            });
+        }
+
+        let update = color
+            .to_light_state_update()
+            .with_transition_time(self.config.settings.transition_time);
+        let updates = light_ids
+            .iter()
+            .map(|light_id| (light_id.clone(), update.clone()))
+            .collect();
+        let (response, warnings) = self.set_lights(updates).await?;
+
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::SceneExecutionFailed {
+                reason: no_success_reason(&format!("color '{}'", color.name), &response),
+            });
+        }
+
+        Ok(SceneExecutionResult {
+            scene_id: String::new(),
+            scene_name: color.name.clone(),
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            success: true,
+            state_change: None,
+            apply_latency_ms: None,
+            already_showing: false,
+            warnings,
+            retry_count: self.client.retry_count() - retries_before,
+        })
+    }
+
+    /// Show a white color temperature on the status group, or on the given lights
+    ///
+    /// The temperature is clamped to each light's range; lights without
+    /// color temperature support are skipped with a warning.
+    pub async fn show_color_temperature(
+        &self,
+        kelvin: u16,
+        brightness: u8,
+        light_ids: Option<&[String]>,
+    ) -> Result<SceneExecutionResult> {
+        self.client.retry_budget().reset();
+
+        if self.verbose {
+            eprintln!("🎨 Showing color temperature {kelvin}K...");
+        }
+
+        let start_time = std::time::Instant::now();
+        let retries_before = self.client.retry_count();
+
+        let lights = self.client.get_lights().await?;
+        let light_ids = match light_ids {
+            Some(light_ids) => {
+                let unknown = unknown_light_ids(light_ids, &lights);
+                if !unknown.is_empty() {
+                    return Err(HueStatusError::InvalidConfig {
+                        reason: format!("unknown light id(s) {}", unknown.join(", ")),
+                    });
+                }
+                light_ids.to_vec()
+            }
+            None if self.config.settings.recall_group_id == "0" => lights.keys().cloned().collect(),
+            None => {
+                self.client
+                    .get_group(&self.config.settings.recall_group_id)
+                    .await?
+                    .lights
+            }
+        };
+
+        let ct = kelvin_to_mireds(kelvin);
+        let mut updates = Vec::new();
+        let mut warnings = Vec::new();
+        for light_id in &light_ids {
+            let Some(range) = lights
+                .get(light_id)
+                .and_then(Light::color_temperature_range)
+            else {
+                warnings.push(format!(
+                    "Light {light_id} doesn't support color temperature, skipped"
+                ));
+                continue;
+            };
+            let update = LightStateUpdate {
+                on: Some(true),
+                bri: Some(brightness),
+                ct: Some(range.clamp(ct)),
+                ..LightStateUpdate::default()
+            }
+            .with_transition_time(self.config.settings.transition_time);
+            updates.push((light_id.clone(), update));
+        }
+
+        let (response, light_warnings) = self.set_lights(updates).await?;
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::SceneExecutionFailed {
+                reason: format!("No light showed color temperature {kelvin}K"),
+            });
+        }
+
+        warnings.extend(light_warnings);
+        Ok(SceneExecutionResult {
+            scene_id: String::new(),
+            scene_name: format!("{kelvin}K"),
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            success: true,
+            state_change: None,
+            apply_latency_ms: None,
+            already_showing: false,
+            warnings,
+            retry_count: self.client.retry_count() - retries_before,
+        })
+    }
+
+    /// Apply per-light state changes so the lights change together
+    ///
+    /// Identical changes that cover exactly the lights of one group are sent as
+    /// a single group action; otherwise the lights are set concurrently.
+    /// Returns the responses and per-light warnings.
+    async fn set_lights(
+        &self,
+        updates: Vec<(String, LightStateUpdate)>,
+    ) -> Result<(Vec<ActionResponse>, Vec<String>)> {
+        let light_ids: Vec<_> = updates.iter().map(|(id, _)| id.clone()).collect();
+        let shared_update = updates
+            .first()
+            .map(|(_, update)| update)
+            .filter(|first| updates.iter().all(|(_, update)| update == *first));
+        if let Some(update) = shared_update {
+            let groups = self.client.get_groups().await.unwrap_or_default();
+            if let Some(group_id) = group_with_lights(&groups, &light_ids) {
+                if self.verbose {
+                    eprintln!("💡 Setting group {group_id}, which holds exactly these lights");
+                }
+                let response = self.client.set_group_action(group_id, update).await?;
+                let warnings = action_errors(&response);
+                return Ok((response, warnings));
+            }
+        }
+
+        self.client.set_light_states(updates).await.into_result()
+    }
+
+    /// Turn off the lights in the status group, returning any per-light failures
+    pub async fn off(&self) -> Result<Vec<String>> {
+        self.client.retry_budget().reset();
+
+        if self.verbose {
+            eprintln!("🌑 Turning off lights...");
+        }
+
+        let update =
+            LightStateUpdate::off().with_transition_time(self.config.settings.transition_time);
+        let response = self
+            .client
+            .set_group_action(&self.config.settings.recall_group_id, &update)
+            .await?;
+
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::ApiError {
+                message: no_success_reason("the off command", &response),
+            });
+        }
+
+        Ok(action_errors(&response))
+    }
+
+    /// Nudge the status group's brightness relative to its current level
+    pub async fn adjust_brightness(
+        &self,
+        change: BrightnessChange,
+    ) -> Result<BrightnessAdjustment> {
+        self.client.retry_budget().reset();
+
+        let group_id = &self.config.settings.recall_group_id;
+        let group = self.client.get_group(group_id).await?;
+        let previous = group.action.bri.ok_or_else(|| HueStatusError::ApiError {
+            message: format!("Group {group_id} reports no brightness"),
+        })?;
+        let brightness = change.apply(previous);
+
+        if self.verbose {
+            eprintln!(
+                "🔆 Changing brightness of group {group_id} from {previous} to {brightness}..."
+            );
+        }
+
+        let update = LightStateUpdate {
+            bri: Some(brightness),
+            ..LightStateUpdate::default()
+        }
+        .with_transition_time(self.config.settings.transition_time);
+        let response = self.client.set_group_action(group_id, &update).await?;
+
+        if !response.iter().any(ActionResponse::is_success) {
+            return Err(HueStatusError::ApiError {
+                message: no_success_reason("the brightness change", &response),
+            });
+        }
+
+        Ok(BrightnessAdjustment {
+            previous,
+            brightness,
+            failures: action_errors(&response),
+        })
+    }
+
+    /// Re-check the bridge if it was last verified longer ago than `max_age`
+    ///
+    /// Tests the connection and the application key, then stores the new
+    /// verification time in the config file set with `with_config_path`.
+    /// Fails if the bridge is unreachable or the key was revoked.
+    pub async fn ensure_verified_within(&self, max_age: chrono::Duration) -> Result<()> {
+        if self.config.is_bridge_verified_within(max_age) {
+            return Ok(());
+        }
+
+        if self.verbose {
+            eprintln!("🔐 Bridge verification is older than the allowed age, re-checking...");
+        }
+
+        self.client.test_connection().await?;
+        // Only the key the bridge issued can list the lights
+        self.client.get_lights().await?;
+
+        if let Some(config_path) = &self.config_path {
+            let mut stored = load_config(config_path)?;
+            stored.update_last_verified();
+            save_config(&stored, config_path)?;
+        }
+
+        if self.verbose {
+            eprintln!("✅ Bridge re-verified");
+        }
+        Ok(())
+    }
+
+    /// Check the bridge connection and validate the status scenes
+    pub async fn validate(&self) -> Result<Vec<SceneValidationResult>> {
+        self.client.test_connection().await?;
+
+        if self.verbose {
+            eprintln!("✅ Bridge connection successful");
+        }
+
+        self.scene_manager
+            .validate_status_scenes(&self.config)
+            .await
+    }
+
+    /// List the bridge groups and rooms, ordered by ID
+    pub async fn groups(&self) -> Result<Vec<(String, Group)>> {
+        let mut groups: Vec<_> = self.client.get_groups().await?.into_iter().collect();
+        groups.sort_by_key(|(id, _)| id.parse::<u32>().unwrap_or(u32::MAX));
+        Ok(groups)
+    }
+
+    /// List the bridge scenes with their lightstates, ordered by name
+    ///
+    /// The scene list omits lightstates, so each scene is fetched individually;
+    /// a scene that fails to load is listed without them.
+    pub async fn scenes(&self) -> Result<Vec<(String, Scene)>> {
+        let mut scenes = Vec::new();
+        for (id, summary) in self.client.get_scenes().await? {
+            let scene = self.client.get_scene(&id).await.unwrap_or(summary);
+            scenes.push((id, scene));
+        }
+        scenes.sort_by(|(a_id, a), (b_id, b)| a.name.cmp(&b.name).then_with(|| a_id.cmp(b_id)));
+        Ok(scenes)
+    }
+
+    /// Get a summary of the bridge state
+    pub async fn status(&self) -> Result<BridgeStatus> {
+        self.client.get_bridge_status().await
+    }
+
+    /// Measure round-trip latency to the bridge
+    pub async fn bench(&self, iterations: usize) -> LatencyStats {
+        self.client.measure_latency(iterations).await
+    }
+}
+
+/// Error reason for a request no light accepted, with the bridge's per-light errors
+fn no_success_reason(subject: &str, response: &[ActionResponse]) -> String {
+    let errors = action_errors(response);
+    if errors.is_empty() {
+        format!("Bridge returned no response for {subject}")
+    } else {
+        format!("Bridge rejected {subject}: {}", errors.join(", "))
+    }
+}
+
+/// Group whose lights are exactly `light_ids` (in any order), if there is one
+fn group_with_lights<'a>(
+    groups: &'a HashMap<String, Group>,
+    light_ids: &[String],
+) -> Option<&'a str> {
+    let mut wanted: Vec<_> = light_ids.iter().collect();
+    wanted.sort();
+    wanted.dedup();
+
+    groups
+        .iter()
+        .filter(|(_, group)| {
+            let mut lights: Vec<_> = group.lights.iter().collect();
+            lights.sort();
+            lights == wanted
+        })
+        .map(|(id, _)| id.as_str())
+        .min_by_key(|id| (id.len(), *id))
+}
+
+/// Requested light IDs the bridge doesn't know
+fn unknown_light_ids<'a>(requested: &'a [String], lights: &HashMap<String, Light>) -> Vec<&'a str> {
+    requested
+        .iter()
+        .filter(|id| !lights.contains_key(id.as_str()))
+        .map(String::as_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> Config {
+        Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("success-id".to_string(), "success".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "failure".to_string(), true),
+        )
+    }
+
+    #[test]
+    fn test_huestatus_new() {
+        let mut config = test_config();
+        config.settings.verbose_logging = true;
+
+        let app = Huestatus::new(config).unwrap();
+        assert_eq!(app.config().bridge.ip, "192.168.1.100");
+        assert!(app.verbose);
+    }
+
+    #[test]
+    fn test_group_with_lights() {
+        let group = |lights: &[&str]| -> Group {
+            serde_json::from_value(serde_json::json!({
+                "name": "Room",
+                "lights": lights,
+                "type": "Room",
+                "state": { "all_on": false, "any_on": false },
+                "recycle": false,
+                "action": { "on": false },
+            }))
+            .unwrap()
+        };
+        let groups = HashMap::from([
+            ("1".to_string(), group(&["1", "2"])),
+            ("2".to_string(), group(&["3"])),
+        ]);
+
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        assert_eq!(group_with_lights(&groups, &ids(&["2", "1"])), Some("1"));
+        assert_eq!(group_with_lights(&groups, &ids(&["1"])), None);
+        assert_eq!(group_with_lights(&groups, &ids(&["1", "2", "3"])), None);
+    }
+
+    #[test]
+    fn test_unknown_light_ids() {
+        let light: Light = serde_json::from_value(serde_json::json!({
+            "state": {"on": true, "reachable": true},
+            "type": "Extended color light",
+            "name": "Desk",
+            "modelid": "LCT015",
+            "swversion": "1.0"
+        }))
+        .unwrap();
+        let lights = HashMap::from([("1".to_string(), light.clone()), ("2".to_string(), light)]);
+
+        let requested = vec!["2".to_string(), "7".to_string(), "1".to_string()];
+        assert_eq!(unknown_light_ids(&requested, &lights), vec!["7"]);
+        assert!(unknown_light_ids(&requested[..1], &lights).is_empty());
+    }
+
+    #[test]
+    fn test_huestatus_quiet_overrides_verbose() {
+        let mut config = test_config();
+        config.settings.verbose_logging = true;
+        config.settings.quiet_mode = true;
+
+        let app = Huestatus::new(config).unwrap();
+        assert!(!app.verbose);
+    }
+
+    #[test]
+    fn test_no_success_reason() {
+        assert_eq!(
+            no_success_reason("the off command", &[]),
+            "Bridge returned no response for the off command"
+        );
+
+        let response: Vec<ActionResponse> = serde_json::from_value(serde_json::json!([
+            {"error": {"type": 201, "address": "/lights/1/state/bri", "description": "parameter, bri, is not modifiable. Device is set to off."}},
+            {"error": {"type": 3, "address": "/lights/9", "description": "resource, /lights/9, not available"}}
+        ]))
+        .unwrap();
+        assert_eq!(
+            no_success_reason("color 'success'", &response),
+            "Bridge rejected color 'success': /lights/1/state/bri: parameter, bri, is not modifiable. Device is set to off., /lights/9: resource, /lights/9, not available"
+        );
+    }
+}
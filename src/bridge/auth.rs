@@ -1,17 +1,28 @@
-use crate::bridge::{BridgeClient, HueError};
-use crate::error::{HueStatusError, Result};
+use crate::bridge::{build_http_client, default_user_agent, BridgeClient, HueError};
+use crate::error::{cancelled, HueStatusError, Result};
+use console::Term;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
 use std::time::Duration;
-use tokio::time::{interval, sleep, timeout, Instant};
+use tokio::time::{interval, sleep, timeout, Instant, Interval};
+use tokio_util::sync::CancellationToken;
+
+/// Timeout for each individual HTTP request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Width of the link button countdown bar, in cells
+const COUNTDOWN_BAR_WIDTH: usize = 30;
 
 /// Authentication manager for Hue Bridge
 #[derive(Debug, Clone)]
 pub struct BridgeAuth {
     client: Client,
     bridge_ip: String,
+    user_agent: String,
     timeout: Duration,
     verbose: bool,
+    cancellation: Option<CancellationToken>,
 }
 
 /// Authentication request payload
@@ -40,6 +51,69 @@ pub struct AuthResult {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Countdown shown while polling for the link button press
+///
+/// Draws a bar that updates in place on an interactive stderr. Without a
+/// terminal, with colors disabled, or in verbose mode (whose request logs
+/// would break the line) it falls back to the plain verbose text.
+struct AuthCountdown {
+    term: Term,
+    timeout: Duration,
+    animated: bool,
+    verbose: bool,
+}
+
+impl AuthCountdown {
+    fn new(timeout: Duration, verbose: bool) -> Self {
+        let term = Term::stderr();
+        let animated = !verbose && term.is_term() && console::colors_enabled_stderr();
+        Self {
+            term,
+            timeout,
+            animated,
+            verbose,
+        }
+    }
+
+    /// Show the time left after another unsuccessful poll
+    fn update(&self, elapsed: Duration) {
+        if self.animated {
+            let _ = self.term.clear_line();
+            let _ = self.term.write_str(&format!(
+                "⏳ Waiting for the link button {}",
+                countdown_bar(elapsed, self.timeout, COUNTDOWN_BAR_WIDTH)
+            ));
+        } else if self.verbose {
+            let remaining = self.timeout.as_secs().saturating_sub(elapsed.as_secs());
+            eprintln!("⏳ Waiting for button press... ({remaining} seconds remaining)");
+        }
+    }
+
+    /// Remove the bar once polling ends
+    fn finish(&self) {
+        if self.animated {
+            let _ = self.term.clear_line();
+        }
+    }
+}
+
+/// Render `[████░░░░] 12s left` for the time left of `total`
+fn countdown_bar(elapsed: Duration, total: Duration, width: usize) -> String {
+    let remaining = total.saturating_sub(elapsed);
+    let filled = if total.is_zero() {
+        0
+    } else {
+        ((remaining.as_secs_f64() / total.as_secs_f64()) * width as f64).round() as usize
+    };
+    let filled = filled.min(width);
+    format!(
+        "[{}{}] {}s left",
+        console::style("█".repeat(filled)).cyan(),
+        "░".repeat(width - filled),
+        remaining.as_secs()
+    )
+}
+
 /// Authentication status during the process
 #[derive(Debug, Clone, PartialEq)]
 pub enum AuthStatus {
@@ -53,20 +127,31 @@ pub enum AuthStatus {
 impl BridgeAuth {
     /// Create a new authentication manager
     pub fn new(bridge_ip: String) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent("huestatus/1.0")
-            .build()
-            .map_err(|e| HueStatusError::NetworkError { source: e })?;
+        let user_agent = default_user_agent();
+        let client = build_http_client(REQUEST_TIMEOUT, &user_agent, false)?;
 
         Ok(Self {
             client,
             bridge_ip,
+            user_agent,
             timeout: Duration::from_secs(30),
             verbose: false,
+            cancellation: None,
         })
     }
 
+    /// Override the user agent sent with every request
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Result<Self> {
+        self.user_agent = user_agent.into();
+        self.rebuild_http_client()
+    }
+
+    /// Rebuild the HTTP client after a connection setting changed
+    fn rebuild_http_client(mut self) -> Result<Self> {
+        self.client = build_http_client(REQUEST_TIMEOUT, &self.user_agent, false)?;
+        Ok(self)
+    }
+
     /// Set authentication timeout
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
@@ -79,6 +164,22 @@ impl BridgeAuth {
         self
     }
 
+    /// Stop waiting for the link button when the token is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Wait for the next poll, failing with `Cancelled` if the token fires first
+    async fn next_poll(&self, poll_interval: &mut Interval) -> Result<()> {
+        tokio::select! {
+            _ = poll_interval.tick() => Ok(()),
+            _ = cancelled(self.cancellation.as_ref()) => Err(HueStatusError::Cancelled {
+                operation: "Authentication".to_string(),
+            }),
+        }
+    }
+
     /// Authenticate with the bridge using link button
     pub async fn authenticate(&self, app_name: &str, instance_name: &str) -> Result<AuthResult> {
         let device_type = format!("{app_name}#{instance_name}");
@@ -92,10 +193,12 @@ impl BridgeAuth {
 
         let start_time = Instant::now();
         let mut poll_interval = interval(Duration::from_secs(1));
+        let countdown = AuthCountdown::new(self.timeout, self.verbose);
 
         loop {
             // Check if we've exceeded the timeout
             if start_time.elapsed() > self.timeout {
+                countdown.finish();
                 if self.verbose {
                     eprintln!(
                         "⏰ Authentication timed out after {} seconds",
@@ -108,11 +211,15 @@ impl BridgeAuth {
             }
 
             // Wait for next poll interval
-            poll_interval.tick().await;
+            if let Err(e) = self.next_poll(&mut poll_interval).await {
+                countdown.finish();
+                return Err(e);
+            }
 
             // Try to authenticate
             match self.try_authenticate(&device_type).await {
                 Ok(username) => {
+                    countdown.finish();
                     if self.verbose {
                         eprintln!("✅ Authentication successful! Username: {username}");
                     }
@@ -125,18 +232,11 @@ impl BridgeAuth {
                 }
                 Err(HueStatusError::LinkButtonNotPressed) => {
                     // Continue polling
-                    if self.verbose {
-                        let remaining = self
-                            .timeout
-                            .as_secs()
-                            .saturating_sub(start_time.elapsed().as_secs());
-                        eprintln!(
-                            "⏳ Waiting for button press... ({remaining} seconds remaining)"
-                        );
-                    }
+                    countdown.update(start_time.elapsed());
                     continue;
                 }
                 Err(e) => {
+                    countdown.finish();
                     if self.verbose {
                         eprintln!("❌ Authentication error: {e}");
                     }
@@ -230,10 +330,12 @@ impl BridgeAuth {
 
         let start_time = Instant::now();
         let mut poll_interval = interval(Duration::from_secs(1));
+        let countdown = AuthCountdown::new(self.timeout, self.verbose);
 
         loop {
             // Check if we've exceeded the timeout
             if start_time.elapsed() > self.timeout {
+                countdown.finish();
                 callback(AuthStatus::Timeout);
                 return Err(HueStatusError::TimeoutError {
                     operation: "Authentication".to_string(),
@@ -241,11 +343,16 @@ impl BridgeAuth {
             }
 
             // Wait for next poll interval
-            poll_interval.tick().await;
+            if let Err(e) = self.next_poll(&mut poll_interval).await {
+                countdown.finish();
+                callback(AuthStatus::Error(e.to_string()));
+                return Err(e);
+            }
 
             // Try to authenticate
             match self.try_authenticate(&device_type).await {
                 Ok(username) => {
+                    countdown.finish();
                     callback(AuthStatus::Success(username.clone()));
 
                     return Ok(AuthResult {
@@ -256,9 +363,11 @@ impl BridgeAuth {
                 }
                 Err(HueStatusError::LinkButtonNotPressed) => {
                     // Continue polling - no callback needed as status hasn't changed
+                    countdown.update(start_time.elapsed());
                     continue;
                 }
                 Err(e) => {
+                    countdown.finish();
                     callback(AuthStatus::Error(e.to_string()));
                     return Err(e);
                 }
@@ -321,12 +430,15 @@ impl BridgeAuth {
         }
     }
 
-    /// Create authenticated bridge client
+    /// Create authenticated bridge client, reusing this one's connection pool
     pub fn create_authenticated_client(&self, username: String) -> Result<BridgeClient> {
-        BridgeClient::new(self.bridge_ip.clone())?
-            .with_username(username)
-            .with_verbose(self.verbose)
-            .pipe(Ok)
+        Ok(BridgeClient::from_http_client(
+            self.bridge_ip.clone(),
+            self.client.clone(),
+            self.user_agent.clone(),
+        )
+        .with_username(username)
+        .with_verbose(self.verbose))
     }
 
     /// Interactive authentication with user prompts
@@ -342,13 +454,19 @@ impl BridgeAuth {
         println!("2. Wait for the button to start blinking");
         println!("3. Press Enter to continue");
         println!();
-        print!("Press the bridge button now and then press Enter...");
 
-        // Wait for user to press Enter
-        let mut input = String::new();
-        std::io::stdin()
-            .read_line(&mut input)
-            .map_err(|e| HueStatusError::IoError { source: e })?;
+        // Without a terminal nobody can press Enter, so go straight to polling
+        if std::io::stdin().is_terminal() {
+            print!("Press the bridge button now and then press Enter...");
+            std::io::Write::flush(&mut std::io::stdout()).ok();
+
+            let mut input = String::new();
+            std::io::stdin()
+                .read_line(&mut input)
+                .map_err(|e| HueStatusError::IoError { source: e })?;
+        } else {
+            println!("Stdin is not a terminal; waiting for the button press...");
+        }
 
         println!("🔍 Attempting authentication...");
 
@@ -357,19 +475,10 @@ impl BridgeAuth {
 
         let result = self
             .authenticate_with_callback(app_name, instance_name, |status| match status {
-                AuthStatus::WaitingForButton => {
-                    print!("⏳ Waiting for button press...");
-                    std::io::Write::flush(&mut std::io::stdout()).ok();
-                }
-                AuthStatus::Success(_) => {
-                    println!("\n✅ Authentication successful!");
-                }
-                AuthStatus::Timeout => {
-                    println!("\n⏰ Authentication timed out");
-                }
-                AuthStatus::Error(err) => {
-                    println!("\n❌ Authentication failed: {err}");
-                }
+                AuthStatus::WaitingForButton => println!("⏳ Waiting for button press..."),
+                AuthStatus::Success(_) => println!("✅ Authentication successful!"),
+                AuthStatus::Timeout => println!("⏰ Authentication timed out"),
+                AuthStatus::Error(err) => println!("❌ Authentication failed: {err}"),
                 _ => {}
             })
             .await;
@@ -479,17 +588,6 @@ impl std::fmt::Display for AuthStatus {
     }
 }
 
-// Helper trait for ergonomic method chaining
-trait Pipe<T> {
-    fn pipe<U, F: FnOnce(T) -> U>(self, f: F) -> U;
-}
-
-impl<T> Pipe<T> for T {
-    fn pipe<U, F: FnOnce(T) -> U>(self, f: F) -> U {
-        f(self)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -500,6 +598,33 @@ mod tests {
         assert!(auth.is_ok());
     }
 
+    #[test]
+    fn test_authenticated_client_keeps_settings() {
+        let auth = BridgeAuth::new("192.168.1.100".to_string())
+            .unwrap()
+            .with_user_agent("corp-proxy-allowed/2.0")
+            .unwrap();
+        let client = auth
+            .create_authenticated_client("test-user".to_string())
+            .unwrap();
+        assert_eq!(client.user_agent(), "corp-proxy-allowed/2.0");
+    }
+
+    #[test]
+    fn test_countdown_bar() {
+        let bar = |elapsed| {
+            console::strip_ansi_codes(&countdown_bar(
+                Duration::from_secs(elapsed),
+                Duration::from_secs(30),
+                10,
+            ))
+            .into_owned()
+        };
+        assert_eq!(bar(0), "[██████████] 30s left");
+        assert_eq!(bar(12), "[██████░░░░] 18s left");
+        assert_eq!(bar(45), "[░░░░░░░░░░] 0s left");
+    }
+
     #[test]
     fn test_auth_request_serialization() {
         let request = AuthRequest {
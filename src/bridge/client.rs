@@ -1,45 +1,116 @@
 use crate::bridge::{
-    BridgeCapabilities, BridgeConfiguration, CreateSceneRequest, Group, Light, Scene,
-    SceneActionRequest,
+    build_http_client, default_user_agent, find_scene_by_name, BridgeCapabilities,
+    BridgeConfiguration, CreateSceneRequest, Group, HueErrorDetails, Light, LightStateUpdate,
+    Scene, SceneActionRequest, UpdateSceneRequest,
 };
 use crate::error::{HueStatusError, Result};
-use reqwest::{Client, ClientBuilder};
+use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
 use tokio::time::{sleep, timeout};
 
+/// First pause between connection attempts while waiting for the bridge
+const WAIT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest pause between connection attempts while waiting for the bridge
+const WAIT_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Most per-light requests in flight at once; the bridge rate limits beyond this
+pub const MAX_CONCURRENT_LIGHT_REQUESTS: usize = 5;
+
 /// HTTP client for interacting with Hue Bridge API
 #[derive(Debug, Clone)]
 pub struct BridgeClient {
     client: Client,
     bridge_ip: String,
+    /// Root of the API, `http://<bridge ip>/api` unless overridden
+    api_url: String,
+    user_agent: String,
+    accept_invalid_certs: bool,
     username: Option<String>,
     timeout: Duration,
     retry_attempts: usize,
     retry_delay: Duration,
     verbose: bool,
+    transition_time: Option<u16>,
+    /// Retries performed so far, shared between clones
+    retries: Arc<AtomicUsize>,
+    /// Retries still allowed for the current command, shared between clones
+    retry_budget: RetryBudget,
+}
+
+/// Retries left for a command, shared by every layer that retries
+///
+/// `BridgeClient` retries individual requests and `SceneExecutor` retries whole
+/// scene recalls on top of that. Both draw from this budget, so one command makes
+/// at most `retry_attempts - 1` retries in total instead of one set per layer.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    limit: usize,
+    remaining: Arc<AtomicUsize>,
+}
+
+impl RetryBudget {
+    /// Create a budget allowing `attempts - 1` retries in total
+    pub fn for_attempts(attempts: usize) -> Self {
+        let limit = attempts.saturating_sub(1);
+        Self {
+            limit,
+            remaining: Arc::new(AtomicUsize::new(limit)),
+        }
+    }
+
+    /// Use up one retry, returning false once the budget is exhausted
+    pub fn try_consume(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Retries still available
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::Relaxed)
+    }
+
+    /// Restore the full budget, e.g. at the start of the next command
+    pub fn reset(&self) {
+        self.remaining.store(self.limit, Ordering::Relaxed);
+    }
 }
 
 impl BridgeClient {
     /// Create a new bridge client
     pub fn new(bridge_ip: String) -> Result<Self> {
-        let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(10))
-            .user_agent("huestatus/1.0")
-            .build()
-            .map_err(|e| HueStatusError::NetworkError { source: e })?;
+        let user_agent = default_user_agent();
+        let client = build_http_client(Duration::from_secs(10), &user_agent, false)?;
+        Ok(Self::from_http_client(bridge_ip, client, user_agent))
+    }
 
-        Ok(Self {
+    /// Create a client on an existing HTTP client, sharing its connection pool
+    ///
+    /// The HTTP client must use the default 10 second timeout and the given
+    /// user agent, and verify certificates.
+    pub(crate) fn from_http_client(bridge_ip: String, client: Client, user_agent: String) -> Self {
+        Self {
             client,
+            api_url: format!("http://{bridge_ip}/api"),
             bridge_ip,
+            user_agent,
+            accept_invalid_certs: false,
             username: None,
             timeout: Duration::from_secs(10),
             retry_attempts: 3,
             retry_delay: Duration::from_secs(1),
             verbose: false,
-        })
+            transition_time: None,
+            retries: Arc::default(),
+            retry_budget: RetryBudget::for_attempts(3),
+        }
     }
 
     /// Create a new bridge client with custom configuration
@@ -50,23 +121,32 @@ impl BridgeClient {
         retry_delay_seconds: u64,
         verbose: bool,
     ) -> Result<Self> {
-        let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(timeout_seconds))
-            .user_agent("huestatus/1.0")
-            .build()
-            .map_err(|e| HueStatusError::NetworkError { source: e })?;
+        let user_agent = default_user_agent();
+        let client = build_http_client(Duration::from_secs(timeout_seconds), &user_agent, false)?;
 
         Ok(Self {
             client,
+            api_url: format!("http://{bridge_ip}/api"),
             bridge_ip,
+            user_agent,
+            accept_invalid_certs: false,
             username: None,
             timeout: Duration::from_secs(timeout_seconds),
             retry_attempts,
             retry_delay: Duration::from_secs(retry_delay_seconds),
             verbose,
+            transition_time: None,
+            retries: Arc::default(),
+            retry_budget: RetryBudget::for_attempts(retry_attempts),
         })
     }
 
+    /// Send requests to `base_url` instead of `http://<bridge ip>`, e.g. a mock server in tests
+    pub fn with_base_url(mut self, base_url: &str) -> Result<Self> {
+        self.api_url = format!("{}/api", base_url.trim_end_matches('/'));
+        self.rebuild_http_client()
+    }
+
     /// Set the username for authenticated requests
     pub fn with_username(mut self, username: String) -> Self {
         self.username = Some(username);
@@ -79,9 +159,62 @@ impl BridgeClient {
         self
     }
 
+    /// Override the user agent sent with every request
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Result<Self> {
+        self.user_agent = user_agent.into();
+        self.rebuild_http_client()
+    }
+
+    /// Disable TLS certificate verification (only for local bridges with self-signed certs)
+    ///
+    /// Only takes effect for an `https://` API URL; plain HTTP has no certificate to check.
+    pub fn with_insecure(mut self, accept_invalid_certs: bool) -> Result<Self> {
+        if self.accept_invalid_certs == accept_invalid_certs {
+            return Ok(self);
+        }
+        self.accept_invalid_certs = accept_invalid_certs;
+        self.rebuild_http_client()
+    }
+
+    /// Rebuild the HTTP client after a connection setting changed
+    fn rebuild_http_client(mut self) -> Result<Self> {
+        self.client = build_http_client(
+            self.timeout,
+            &self.user_agent,
+            self.skips_certificate_verification(),
+        )?;
+        Ok(self)
+    }
+
+    /// Whether `--insecure` applies: only an HTTPS bridge has a certificate to skip
+    fn skips_certificate_verification(&self) -> bool {
+        self.accept_invalid_certs && self.api_url.starts_with("https://")
+    }
+
+    /// Get the user agent sent with every request
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Set the transition time (in deciseconds) used when recalling scenes
+    pub fn with_transition_time(mut self, transition_time: Option<u16>) -> Self {
+        self.transition_time = transition_time;
+        self
+    }
+
+    /// Get the number of request retries performed by this client and its clones
+    pub fn retry_count(&self) -> usize {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Get the retry budget shared by this client and its clones
+    pub fn retry_budget(&self) -> &RetryBudget {
+        &self.retry_budget
+    }
+
     /// Get the base URL for API requests
     fn base_url(&self) -> String {
-        format!("http://{}/api", self.bridge_ip)
+        self.api_url.clone()
     }
 
     /// Get the authenticated base URL
@@ -90,7 +223,7 @@ impl BridgeClient {
             .username
             .as_ref()
             .ok_or(HueStatusError::AuthenticationFailed)?;
-        Ok(format!("http://{}/api/{}", self.bridge_ip, username))
+        Ok(format!("{}/{}", self.api_url, username))
     }
 
     /// Make a GET request with retry logic
@@ -117,20 +250,7 @@ impl BridgeClient {
                 eprintln!("📡 Response: {} {}", response.status(), response.url());
             }
 
-            let json: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| HueStatusError::NetworkError { source: e })?;
-
-            // Check if response is an error array
-            if let Ok(errors) = serde_json::from_value::<Vec<crate::bridge::HueError>>(json.clone())
-            {
-                if !errors.is_empty() {
-                    return Err(errors[0].clone().into());
-                }
-            }
-
-            serde_json::from_value(json).map_err(|e| HueStatusError::JsonError { source: e })
+            parse_response(response).await
         })
         .await
     }
@@ -162,20 +282,7 @@ impl BridgeClient {
                 eprintln!("📡 Response: {} {}", response.status(), response.url());
             }
 
-            let json: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| HueStatusError::NetworkError { source: e })?;
-
-            // Check if response is an error array
-            if let Ok(errors) = serde_json::from_value::<Vec<crate::bridge::HueError>>(json.clone())
-            {
-                if !errors.is_empty() {
-                    return Err(errors[0].clone().into());
-                }
-            }
-
-            serde_json::from_value(json).map_err(|e| HueStatusError::JsonError { source: e })
+            parse_response(response).await
         })
         .await
     }
@@ -207,20 +314,7 @@ impl BridgeClient {
                 eprintln!("📡 Response: {} {}", response.status(), response.url());
             }
 
-            let json: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| HueStatusError::NetworkError { source: e })?;
-
-            // Check if response is an error array
-            if let Ok(errors) = serde_json::from_value::<Vec<crate::bridge::HueError>>(json.clone())
-            {
-                if !errors.is_empty() {
-                    return Err(errors[0].clone().into());
-                }
-            }
-
-            serde_json::from_value(json).map_err(|e| HueStatusError::JsonError { source: e })
+            parse_response(response).await
         })
         .await
     }
@@ -249,20 +343,7 @@ impl BridgeClient {
                 eprintln!("📡 Response: {} {}", response.status(), response.url());
             }
 
-            let json: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| HueStatusError::NetworkError { source: e })?;
-
-            // Check if response is an error array
-            if let Ok(errors) = serde_json::from_value::<Vec<crate::bridge::HueError>>(json.clone())
-            {
-                if !errors.is_empty() {
-                    return Err(errors[0].clone().into());
-                }
-            }
-
-            serde_json::from_value(json).map_err(|e| HueStatusError::JsonError { source: e })
+            parse_response(response).await
         })
         .await
     }
@@ -279,17 +360,25 @@ impl BridgeClient {
             match request_fn().await {
                 Ok(result) => return Ok(result),
                 Err(error) => {
-                    last_error = Some(error);
+                    let error = last_error.insert(error);
 
                     if attempt < self.retry_attempts - 1 {
+                        if !self.retry_budget.try_consume() {
+                            if self.verbose {
+                                eprintln!("⏳ Retry budget for this command used up");
+                            }
+                            break;
+                        }
+                        let delay = retry_delay(self.retry_delay, attempt, error);
                         if self.verbose {
                             eprintln!(
                                 "⏳ Retry attempt {} in {} seconds",
                                 attempt + 1,
-                                self.retry_delay.as_secs()
+                                delay.as_secs()
                             );
                         }
-                        sleep(self.retry_delay).await;
+                        self.retries.fetch_add(1, Ordering::Relaxed);
+                        sleep(delay).await;
                     }
                 }
             }
@@ -302,7 +391,7 @@ impl BridgeClient {
 
     /// Test connection to bridge
     pub async fn test_connection(&self) -> Result<()> {
-        let url = format!("http://{}/api/0/config", self.bridge_ip);
+        let url = format!("{}/0/config", self.api_url);
 
         if self.verbose {
             eprintln!("🔍 Testing connection to {}", self.bridge_ip);
@@ -324,6 +413,42 @@ impl BridgeClient {
         Ok(())
     }
 
+    /// Poll the bridge with exponential backoff until it answers or `max_wait` elapses
+    pub async fn wait_until_reachable(&self, max_wait: Duration) -> Result<()> {
+        let deadline = Instant::now() + max_wait;
+        let mut delay = WAIT_INITIAL_DELAY;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let error = match timeout(remaining, self.test_connection()).await {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(e)) => e,
+                Err(_) => HueStatusError::TimeoutError {
+                    operation: "Connection test".to_string(),
+                },
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(HueStatusError::BridgeConnectionFailed {
+                    reason: format!(
+                        "Bridge not reachable after waiting {}s: {error}",
+                        max_wait.as_secs()
+                    ),
+                });
+            }
+
+            if self.verbose {
+                eprintln!(
+                    "⏳ Bridge not reachable yet ({error}), retrying in {}ms",
+                    delay.min(remaining).as_millis()
+                );
+            }
+            sleep(delay.min(remaining)).await;
+            delay = (delay * 2).min(WAIT_MAX_DELAY);
+        }
+    }
+
     /// Get bridge configuration
     pub async fn get_config(&self) -> Result<BridgeConfiguration> {
         self.get("config").await
@@ -354,6 +479,13 @@ impl BridgeClient {
         self.get(&format!("scenes/{scene_id}")).await
     }
 
+    /// Look up a scene ID by its name (case-insensitive, must be unique)
+    pub async fn get_scene_by_name(&self, name: &str) -> Result<(String, Scene)> {
+        let scenes = self.get_scenes().await?;
+        let (id, scene) = find_scene_by_name(&scenes, name)?;
+        Ok((id.to_string(), scene.clone()))
+    }
+
     /// Create a new scene
     pub async fn create_scene(
         &self,
@@ -363,6 +495,16 @@ impl BridgeClient {
         self.post("scenes", scene).await
     }
 
+    /// Replace the lights and light states of an existing scene, keeping its ID
+    pub async fn update_scene(
+        &self,
+        scene_id: &str,
+        scene: &UpdateSceneRequest,
+    ) -> Result<Vec<ActionResponse>> {
+        scene.validate()?;
+        self.put(&format!("scenes/{scene_id}"), scene).await
+    }
+
     /// Delete a scene
     pub async fn delete_scene(&self, scene_id: &str) -> Result<Vec<DeleteResponse>> {
         self.delete(&format!("scenes/{scene_id}")).await
@@ -370,7 +512,8 @@ impl BridgeClient {
 
     /// Execute a scene on all lights (group 0)
     pub async fn execute_scene(&self, scene_id: &str) -> Result<Vec<ActionResponse>> {
-        let action = SceneActionRequest::new(scene_id.to_string());
+        let action = SceneActionRequest::new(scene_id.to_string())
+            .with_transition_time(self.transition_time);
         self.put("groups/0/action", &action).await
     }
 
@@ -380,11 +523,65 @@ impl BridgeClient {
         group_id: &str,
         scene_id: &str,
     ) -> Result<Vec<ActionResponse>> {
-        let action = SceneActionRequest::new(scene_id.to_string());
+        let action = SceneActionRequest::new(scene_id.to_string())
+            .with_transition_time(self.transition_time);
         self.put(&format!("groups/{group_id}/action"), &action)
             .await
     }
 
+    /// Apply a partial state change to every light in a group
+    pub async fn set_group_action(
+        &self,
+        group_id: &str,
+        update: &LightStateUpdate,
+    ) -> Result<Vec<ActionResponse>> {
+        self.put(&format!("groups/{group_id}/action"), update).await
+    }
+
+    /// Apply a partial state change to a single light
+    pub async fn set_light_state(
+        &self,
+        light_id: &str,
+        update: &LightStateUpdate,
+    ) -> Result<Vec<ActionResponse>> {
+        self.put(&format!("lights/{light_id}/state"), update).await
+    }
+
+    /// Apply per-light state changes concurrently, so the lights change together
+    ///
+    /// At most `MAX_CONCURRENT_LIGHT_REQUESTS` requests run at once. Every light
+    /// is attempted; failures are collected instead of stopping the others.
+    pub async fn set_light_states(
+        &self,
+        updates: Vec<(String, LightStateUpdate)>,
+    ) -> LightStatesOutcome {
+        let mut outcome = LightStatesOutcome::default();
+        let mut pending = updates.into_iter();
+        let mut requests = JoinSet::new();
+
+        loop {
+            while requests.len() < MAX_CONCURRENT_LIGHT_REQUESTS {
+                let Some((light_id, update)) = pending.next() else {
+                    break;
+                };
+                let client = self.clone();
+                requests.spawn(async move {
+                    let result = client.set_light_state(&light_id, &update).await;
+                    (light_id, result)
+                });
+            }
+
+            match requests.join_next().await {
+                Some(Ok((_, Ok(responses)))) => outcome.responses.extend(responses),
+                Some(Ok((light_id, Err(e)))) => outcome.failures.push((light_id, e)),
+                Some(Err(e)) => std::panic::resume_unwind(e.into_panic()),
+                None => break,
+            }
+        }
+
+        outcome
+    }
+
     /// Get all groups
     pub async fn get_groups(&self) -> Result<HashMap<String, Group>> {
         self.get("groups").await
@@ -397,15 +594,40 @@ impl BridgeClient {
 
     /// Get reachable lights suitable for status indication
     pub async fn get_suitable_lights(&self) -> Result<Vec<(String, Light)>> {
-        let lights = self.get_lights().await?;
-        let mut suitable_lights = Vec::new();
+        // Move matching lights out of the map instead of cloning them
+        let suitable_lights: Vec<(String, Light)> = self
+            .get_lights()
+            .await?
+            .into_iter()
+            .filter(|(_, light)| light.is_suitable_for_status())
+            .collect();
 
-        for (id, light) in lights {
-            if light.is_suitable_for_status() {
-                suitable_lights.push((id, light));
-            }
+        if suitable_lights.is_empty() {
+            return Err(HueStatusError::NoLightsFound);
         }
 
+        Ok(suitable_lights)
+    }
+
+    /// Get suitable lights that belong to a group ("0" = all lights)
+    ///
+    /// Keeps status scenes scoped to one room on bridges shared between rooms.
+    pub async fn get_suitable_lights_in_group(
+        &self,
+        group_id: &str,
+    ) -> Result<Vec<(String, Light)>> {
+        if group_id == "0" {
+            return self.get_suitable_lights().await;
+        }
+
+        let group = self.get_group(group_id).await?;
+        let suitable_lights: Vec<(String, Light)> = self
+            .get_lights()
+            .await?
+            .into_iter()
+            .filter(|(id, light)| group.lights.contains(id) && light.is_suitable_for_status())
+            .collect();
+
         if suitable_lights.is_empty() {
             return Err(HueStatusError::NoLightsFound);
         }
@@ -459,10 +681,43 @@ impl BridgeClient {
         Ok(())
     }
 
+    /// Time repeated `get_config` round trips to the bridge
+    pub async fn measure_latency(&self, iterations: usize) -> LatencyStats {
+        let mut samples_ms = Vec::with_capacity(iterations);
+        let mut errors = 0;
+
+        for iteration in 1..=iterations {
+            let start = Instant::now();
+            match self.get_config().await {
+                Ok(_) => samples_ms.push(start.elapsed().as_millis() as u64),
+                Err(e) => {
+                    if self.verbose {
+                        eprintln!("🔍 Bench request {iteration} failed: {e}");
+                    }
+                    errors += 1;
+                }
+            }
+        }
+
+        LatencyStats::new(samples_ms, errors)
+    }
+
     /// Get bridge status summary
     pub async fn get_bridge_status(&self) -> Result<BridgeStatus> {
         let config = self.get_config().await?;
-        let capabilities = self.get_capabilities().await?;
+        let mut warnings = Vec::new();
+
+        // Some bridges and emulators don't expose /capabilities
+        let capabilities = match self.get_capabilities().await {
+            Ok(capabilities) => Some(capabilities),
+            Err(e) => {
+                if self.verbose {
+                    eprintln!("🔍 Capabilities unavailable: {e}");
+                }
+                warnings.push(format!("Could not get bridge capabilities: {e}"));
+                None
+            }
+        };
         let lights = self.get_lights().await?;
         let scenes = self.get_scenes().await?;
 
@@ -482,8 +737,9 @@ impl BridgeClient {
             reachable_lights,
             suitable_lights,
             total_scenes: scenes.len(),
-            available_scenes: capabilities.scenes.available,
-            max_scenes: capabilities.scenes.total,
+            available_scenes: capabilities.as_ref().map(|c| c.scenes.available),
+            max_scenes: capabilities.as_ref().map(|c| c.scenes.total),
+            warnings,
         })
     }
 }
@@ -507,9 +763,150 @@ pub struct DeleteResponse {
 }
 
 /// Response for action operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Group actions return one entry per attribute and light, each holding either
+/// a `success` or an `error` object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ActionResponse {
-    pub success: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<HueErrorDetails>,
+}
+
+impl ActionResponse {
+    /// Check if this entry reports success
+    pub fn is_success(&self) -> bool {
+        self.success.is_some() && self.error.is_none()
+    }
+}
+
+/// Combined result of `BridgeClient::set_light_states`
+#[derive(Debug, Default)]
+pub struct LightStatesOutcome {
+    /// Responses of the requests that reached the bridge
+    pub responses: Vec<ActionResponse>,
+    /// Lights whose request failed, with the error
+    pub failures: Vec<(String, HueStatusError)>,
+}
+
+impl LightStatesOutcome {
+    /// Responses if any light was set, otherwise the first failure
+    ///
+    /// The remaining failures become warnings, followed by per-attribute errors.
+    pub fn into_result(mut self) -> Result<(Vec<ActionResponse>, Vec<String>)> {
+        if !self.responses.iter().any(ActionResponse::is_success) && !self.failures.is_empty() {
+            return Err(self.failures.swap_remove(0).1);
+        }
+
+        let mut warnings: Vec<_> = self
+            .failures
+            .iter()
+            .map(|(light_id, e)| format!("Light {light_id}: {e}"))
+            .collect();
+        warnings.extend(crate::bridge::action_errors(&self.responses));
+        Ok((self.responses, warnings))
+    }
+}
+
+/// Maximum number of body characters quoted in HTTP status errors
+const ERROR_BODY_SNIPPET_LEN: usize = 200;
+
+/// Check the HTTP status, then decode the body, surfacing Hue error arrays
+async fn parse_response<R: DeserializeOwned>(response: reqwest::Response) -> Result<R> {
+    let status = response.status();
+    if !status.is_success() {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        let body = response.text().await.unwrap_or_default();
+        return Err(http_status_error(status, &body, retry_after));
+    }
+
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| HueStatusError::NetworkError { source: e })?;
+
+    // Check if response is an error array
+    if let Ok(errors) = serde_json::from_value::<Vec<crate::bridge::HueError>>(json.clone()) {
+        if !errors.is_empty() {
+            return Err(crate::bridge::hue_errors_to_error(errors));
+        }
+    }
+
+    serde_json::from_value(json).map_err(|e| HueStatusError::JsonError { source: e })
+}
+
+/// Longest wait before retrying a rate-limited request
+const MAX_RATE_LIMIT_DELAY: Duration = Duration::from_secs(30);
+
+/// How long to wait before retry number `attempt + 1`
+///
+/// Most errors use the fixed retry delay. When the bridge is rate limiting,
+/// its `Retry-After` is honored, or else the delay doubles with each attempt
+/// (starting from at least one second), up to `MAX_RATE_LIMIT_DELAY`.
+fn retry_delay(base: Duration, attempt: usize, error: &HueStatusError) -> Duration {
+    let HueStatusError::RateLimited {
+        retry_after_seconds,
+        ..
+    } = error
+    else {
+        return base;
+    };
+
+    let delay = match retry_after_seconds {
+        Some(seconds) => Duration::from_secs(*seconds),
+        None => base.max(Duration::from_secs(1)) * 2u32.saturating_pow(attempt as u32 + 1),
+    };
+    delay.min(MAX_RATE_LIMIT_DELAY)
+}
+
+/// Build an error for a non-2xx response, quoting the start of the body
+///
+/// 429 and 503 mean the bridge is overloaded; `retry_after` is the
+/// `Retry-After` header in seconds, if it sent one.
+fn http_status_error(
+    status: reqwest::StatusCode,
+    body: &str,
+    retry_after: Option<u64>,
+) -> HueStatusError {
+    let mut snippet: String = body
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(ERROR_BODY_SNIPPET_LEN)
+        .collect();
+    if snippet.is_empty() {
+        snippet = "empty body".to_string();
+    }
+    let detail = format!("HTTP {status}: {snippet}");
+
+    if matches!(
+        status,
+        reqwest::StatusCode::TOO_MANY_REQUESTS | reqwest::StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        HueStatusError::RateLimited {
+            reason: detail,
+            retry_after_seconds: retry_after,
+        }
+    } else if status.is_server_error() {
+        HueStatusError::BridgeConnectionFailed { reason: detail }
+    } else {
+        HueStatusError::ApiError { message: detail }
+    }
+}
+
+/// Collect the per-light failures from an action response
+pub fn action_errors(responses: &[ActionResponse]) -> Vec<String> {
+    responses
+        .iter()
+        .filter_map(|response| response.error.as_ref())
+        .map(|error| format!("{}: {}", error.address, error.description))
+        .collect()
 }
 
 /// Bridge status summary
@@ -523,8 +920,11 @@ pub struct BridgeStatus {
     pub reachable_lights: usize,
     pub suitable_lights: usize,
     pub total_scenes: usize,
-    pub available_scenes: usize,
-    pub max_scenes: usize,
+    /// Remaining scene slots, if the bridge reports capabilities
+    pub available_scenes: Option<usize>,
+    /// Scene storage limit, if the bridge reports capabilities
+    pub max_scenes: Option<usize>,
+    pub warnings: Vec<String>,
 }
 
 impl BridgeStatus {
@@ -549,7 +949,7 @@ impl BridgeStatus {
             score = score.saturating_sub(10);
         }
 
-        if self.available_scenes < 10 {
+        if matches!(self.available_scenes, Some(available) if available < 10) {
             score = score.saturating_sub(10);
         }
 
@@ -558,6 +958,10 @@ impl BridgeStatus {
 
     /// Get status summary
     pub fn summary(&self) -> String {
+        let max_scenes = self
+            .max_scenes
+            .map_or_else(|| "?".to_string(), |max| max.to_string());
+
         format!(
             "Bridge: {} ({}), API: {}, SW: {}, Lights: {}/{} reachable, Scenes: {}/{}",
             self.bridge_name,
@@ -567,7 +971,81 @@ impl BridgeStatus {
             self.reachable_lights,
             self.total_lights,
             self.total_scenes,
-            self.max_scenes
+            max_scenes
+        )
+    }
+}
+
+/// Round-trip latency measurements against the bridge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyStats {
+    /// Successful round trips in milliseconds, sorted ascending
+    pub samples_ms: Vec<u64>,
+    pub errors: usize,
+}
+
+impl LatencyStats {
+    /// Create statistics from raw samples
+    pub fn new(mut samples_ms: Vec<u64>, errors: usize) -> Self {
+        samples_ms.sort_unstable();
+        Self { samples_ms, errors }
+    }
+
+    /// Total number of requests made
+    pub fn attempts(&self) -> usize {
+        self.samples_ms.len() + self.errors
+    }
+
+    /// Fastest successful round trip
+    pub fn min(&self) -> Option<u64> {
+        self.samples_ms.first().copied()
+    }
+
+    /// Slowest successful round trip
+    pub fn max(&self) -> Option<u64> {
+        self.samples_ms.last().copied()
+    }
+
+    /// Median round trip
+    pub fn median(&self) -> Option<u64> {
+        self.percentile(50)
+    }
+
+    /// 95th percentile round trip
+    pub fn p95(&self) -> Option<u64> {
+        self.percentile(95)
+    }
+
+    /// Nearest-rank percentile of the successful round trips
+    pub fn percentile(&self, percent: usize) -> Option<u64> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let rank = (percent * self.samples_ms.len()).div_ceil(100).max(1);
+        self.samples_ms.get(rank - 1).copied()
+    }
+
+    /// Fraction of requests that failed (0.0 - 1.0)
+    pub fn error_rate(&self) -> f64 {
+        match self.attempts() {
+            0 => 0.0,
+            attempts => self.errors as f64 / attempts as f64,
+        }
+    }
+
+    /// Get latency summary
+    pub fn summary(&self) -> String {
+        let ms = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| format!("{v}ms"));
+
+        format!(
+            "min {}, median {}, p95 {}, max {}, errors {}/{} ({:.1}%)",
+            ms(self.min()),
+            ms(self.median()),
+            ms(self.p95()),
+            ms(self.max()),
+            self.errors,
+            self.attempts(),
+            self.error_rate() * 100.0
         )
     }
 }
@@ -576,6 +1054,199 @@ impl BridgeStatus {
 mod tests {
     use super::*;
 
+    /// Client for a mock bridge, without retries so error cases finish immediately
+    fn mock_client(server: &mockito::Server) -> BridgeClient {
+        BridgeClient::with_config("192.168.1.100".to_string(), 5, 1, 0, false)
+            .unwrap()
+            .with_base_url(&server.url())
+            .unwrap()
+            .with_username("test-user".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_get_lights_from_mock_bridge() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/test-user/lights")
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"1": {"name": "Desk", "type": "Extended color light",
+                   "state": {"on": true, "bri": 200, "hue": 0, "sat": 254, "reachable": true}}}"#,
+            )
+            .create_async()
+            .await;
+
+        let lights = mock_client(&server).get_lights().await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(lights["1"].name, "Desk");
+        assert!(lights["1"].is_reachable());
+    }
+
+    #[tokio::test]
+    async fn test_create_scene_on_mock_bridge() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/test-user/scenes")
+            .with_body(r#"[{"success": {"id": "AbCdEf123"}}]"#)
+            .create_async()
+            .await;
+
+        let scene = CreateSceneRequest::new_success_scene(
+            "huestatus-success".to_string(),
+            vec!["1".to_string()],
+        );
+        let response = mock_client(&server).create_scene(&scene).await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(response[0].success.id, "AbCdEf123");
+    }
+
+    #[tokio::test]
+    async fn test_error_array_from_mock_bridge() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("PUT", "/api/test-user/groups/0/action")
+            .with_body(
+                r#"[{"error": {"type": 3, "address": "/scenes/missing",
+                   "description": "resource, /scenes/missing, not available"}}]"#,
+            )
+            .create_async()
+            .await;
+
+        match mock_client(&server).execute_scene("missing").await {
+            Err(HueStatusError::ResourceNotAvailable { resource }) => {
+                assert!(resource.contains("/scenes/missing"));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_user_from_mock_bridge() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/test-user/scenes")
+            .with_body(
+                r#"[{"error": {"type": 1, "address": "/", "description": "unauthorized user"}}]"#,
+            )
+            .create_async()
+            .await;
+
+        let result = mock_client(&server).get_scenes().await;
+        assert!(matches!(result, Err(HueStatusError::AuthenticationFailed)));
+    }
+
+    #[tokio::test]
+    async fn test_set_light_states_on_mock_bridge() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for light_id in ["1", "2", "3", "4", "5", "6"] {
+            let mock = server
+                .mock(
+                    "PUT",
+                    format!("/api/test-user/lights/{light_id}/state").as_str(),
+                )
+                .with_body(format!(
+                    r#"[{{"success": {{"/lights/{light_id}/state/on": false}}}}]"#
+                ))
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+        server
+            .mock("PUT", "/api/test-user/lights/7/state")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let updates = (1..=7)
+            .map(|id| (id.to_string(), LightStateUpdate::off()))
+            .collect();
+        let outcome = mock_client(&server).set_light_states(updates).await;
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(outcome.responses.len(), 6);
+        assert_eq!(outcome.failures.len(), 1);
+        assert_eq!(outcome.failures[0].0, "7");
+
+        let (responses, warnings) = outcome.into_result().unwrap();
+        assert_eq!(responses.len(), 6);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Light 7: "));
+    }
+
+    #[test]
+    fn test_http_status_error() {
+        let html = "<html>\n  <body>Not   Found</body>\n</html>";
+        match http_status_error(reqwest::StatusCode::NOT_FOUND, html, None) {
+            HueStatusError::ApiError { message } => {
+                assert_eq!(
+                    message,
+                    "HTTP 404 Not Found: <html> <body>Not Found</body> </html>"
+                );
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let long_body = "x".repeat(1000);
+        match http_status_error(reqwest::StatusCode::INTERNAL_SERVER_ERROR, &long_body, None) {
+            HueStatusError::BridgeConnectionFailed { reason } => {
+                assert!(reason.starts_with("HTTP 500 Internal Server Error: xxx"));
+                assert!(reason.len() < 250);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rate_limited_retry_delay() {
+        match http_status_error(reqwest::StatusCode::TOO_MANY_REQUESTS, "", Some(4)) {
+            HueStatusError::RateLimited {
+                retry_after_seconds,
+                ..
+            } => assert_eq!(retry_after_seconds, Some(4)),
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let base = Duration::from_millis(500);
+        let timeout = HueStatusError::TimeoutError {
+            operation: "HTTP request".to_string(),
+        };
+        assert_eq!(retry_delay(base, 2, &timeout), base);
+
+        let limited = |retry_after_seconds| HueStatusError::RateLimited {
+            reason: "busy".to_string(),
+            retry_after_seconds,
+        };
+        assert_eq!(retry_delay(base, 0, &limited(None)), Duration::from_secs(2));
+        assert_eq!(retry_delay(base, 1, &limited(None)), Duration::from_secs(4));
+        assert_eq!(retry_delay(base, 10, &limited(None)), MAX_RATE_LIMIT_DELAY);
+        assert_eq!(
+            retry_delay(base, 0, &limited(Some(7))),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let stats = LatencyStats::new((1..=20).rev().collect(), 5);
+
+        assert_eq!(stats.min(), Some(1));
+        assert_eq!(stats.median(), Some(10));
+        assert_eq!(stats.p95(), Some(19));
+        assert_eq!(stats.max(), Some(20));
+        assert_eq!(stats.attempts(), 25);
+        assert!((stats.error_rate() - 0.2).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.summary(),
+            "min 1ms, median 10ms, p95 19ms, max 20ms, errors 5/25 (20.0%)"
+        );
+
+        let failed = LatencyStats::new(Vec::new(), 3);
+        assert_eq!(failed.median(), None);
+        assert_eq!(failed.error_rate(), 1.0);
+    }
+
     #[test]
     fn test_bridge_client_creation() {
         let client = BridgeClient::new("192.168.1.100".to_string());
@@ -597,6 +1268,89 @@ mod tests {
         assert_eq!(client.username, Some("test-username".to_string()));
     }
 
+    #[test]
+    fn test_bridge_client_user_agent() {
+        let client = BridgeClient::new("192.168.1.100".to_string()).unwrap();
+        assert_eq!(client.user_agent(), format!("huestatus/{}", crate::VERSION));
+
+        let client = client.with_user_agent("corp-proxy-allowed/2.0").unwrap();
+        assert_eq!(client.user_agent(), "corp-proxy-allowed/2.0");
+    }
+
+    #[test]
+    fn test_bridge_client_insecure_keeps_user_agent() {
+        let client = BridgeClient::new("192.168.1.100".to_string())
+            .unwrap()
+            .with_user_agent("corp-proxy-allowed/2.0")
+            .unwrap()
+            .with_insecure(true)
+            .unwrap();
+        assert!(client.accept_invalid_certs);
+        assert_eq!(client.user_agent(), "corp-proxy-allowed/2.0");
+    }
+
+    #[test]
+    fn test_bridge_client_insecure_only_for_https() {
+        let client = BridgeClient::new("192.168.1.100".to_string())
+            .unwrap()
+            .with_insecure(true)
+            .unwrap();
+        assert!(!client.skips_certificate_verification());
+
+        let client = client.with_base_url("https://192.168.1.100").unwrap();
+        assert!(client.skips_certificate_verification());
+    }
+
+    #[test]
+    fn test_retry_count_shared_between_clones() {
+        let client = BridgeClient::new("192.168.1.100".to_string()).unwrap();
+        let clone = client.clone().with_transition_time(Some(0));
+
+        client.retries.fetch_add(2, Ordering::Relaxed);
+        assert_eq!(client.retry_count(), 2);
+        assert_eq!(clone.retry_count(), 2);
+    }
+
+    #[test]
+    fn test_retry_budget() {
+        let client =
+            BridgeClient::with_config("192.168.1.100".to_string(), 10, 3, 1, false).unwrap();
+        let clone = client.clone();
+        let budget = client.retry_budget();
+
+        assert!(budget.try_consume());
+        assert!(clone.retry_budget().try_consume());
+        assert!(!budget.try_consume());
+        assert_eq!(clone.retry_budget().remaining(), 0);
+
+        budget.reset();
+        assert_eq!(clone.retry_budget().remaining(), 2);
+
+        assert!(!RetryBudget::for_attempts(1).try_consume());
+    }
+
+    #[test]
+    fn test_action_response_mixed_results() {
+        let json = serde_json::json!([
+            { "success": { "/groups/0/action/scene": "abc" } },
+            {
+                "error": {
+                    "type": 201,
+                    "address": "/lights/3/state/on",
+                    "description": "parameter, on, is not modifiable. Device is set to off."
+                }
+            }
+        ]);
+
+        let responses: Vec<ActionResponse> = serde_json::from_value(json).unwrap();
+        assert!(responses[0].is_success());
+        assert!(!responses[1].is_success());
+
+        let errors = action_errors(&responses);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/lights/3/state/on"));
+    }
+
     #[test]
     fn test_bridge_status_health() {
         let status = BridgeStatus {
@@ -608,8 +1362,9 @@ mod tests {
             reachable_lights: 5,
             suitable_lights: 5,
             total_scenes: 10,
-            available_scenes: 190,
-            max_scenes: 200,
+            available_scenes: Some(190),
+            max_scenes: Some(200),
+            warnings: Vec::new(),
         };
 
         assert!(status.is_healthy());
@@ -627,8 +1382,9 @@ mod tests {
             reachable_lights: 0,
             suitable_lights: 0,
             total_scenes: 10,
-            available_scenes: 190,
-            max_scenes: 200,
+            available_scenes: Some(190),
+            max_scenes: Some(200),
+            warnings: Vec::new(),
         };
 
         assert!(!status.is_healthy());
@@ -657,8 +1413,9 @@ mod tests {
             reachable_lights: 4,
             suitable_lights: 3,
             total_scenes: 10,
-            available_scenes: 190,
-            max_scenes: 200,
+            available_scenes: Some(190),
+            max_scenes: Some(200),
+            warnings: Vec::new(),
         };
 
         let summary = status.summary();
@@ -667,4 +1424,24 @@ mod tests {
         assert!(summary.contains("4/5"));
         assert!(summary.contains("10/200"));
     }
+
+    #[test]
+    fn test_bridge_status_without_capabilities() {
+        let status = BridgeStatus {
+            bridge_name: "Emulated Bridge".to_string(),
+            bridge_id: "test-id".to_string(),
+            api_version: "1.54.0".to_string(),
+            sw_version: "1954.0".to_string(),
+            total_lights: 2,
+            reachable_lights: 2,
+            suitable_lights: 2,
+            total_scenes: 3,
+            available_scenes: None,
+            max_scenes: None,
+            warnings: vec!["Could not get bridge capabilities".to_string()],
+        };
+
+        assert_eq!(status.health_score(), 100);
+        assert!(status.summary().contains("Scenes: 3/?"));
+    }
 }
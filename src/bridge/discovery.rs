@@ -1,18 +1,54 @@
-use crate::bridge::BridgeInfo;
-use crate::error::{HueStatusError, Result};
+use crate::bridge::{build_http_client, default_user_agent, BridgeInfo};
+use crate::error::{cancelled, check_cancelled, HueStatusError, Result};
+use futures_util::stream::{self, BoxStream, Stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::net::IpAddr;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+use std::net::{IpAddr, Ipv4Addr};
 use std::str::FromStr;
 use std::time::Duration;
+use tokio::task::JoinSet;
 use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+/// Timeout for each individual HTTP request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Philips bridge discovery service
+pub const DEFAULT_DISCOVERY_URL: &str = "https://discovery.meethue.com/";
+
+/// Hue remote API endpoint reporting the bridge of an authorized account
+pub const DEFAULT_REMOTE_DISCOVERY_URL: &str = "https://api.meethue.com/route/api/0/config";
+
+/// Environment variable holding the access token for remote discovery
+pub const REMOTE_TOKEN_ENV_VAR: &str = "HUESTATUS_REMOTE_TOKEN";
+
+/// Host numbers scanned before the rest of a /24 range (where DHCP usually hands out leases)
+const PRIORITY_HOSTS: std::ops::RangeInclusive<u8> = 1..=20;
+
+/// Base delay between the starts of two scan requests
+const SCAN_STAGGER: Duration = Duration::from_millis(2);
+
+/// Upper bound of the random jitter added to each scan request start, in milliseconds
+const SCAN_JITTER_MS: u64 = 3;
 
 /// Bridge discovery methods
 #[derive(Debug, Clone)]
 pub struct BridgeDiscovery {
     client: Client,
+    user_agent: String,
     timeout: Duration,
     verbose: bool,
+    first_match: bool,
+    /// Whether `discover_all` may fall back to probing every host on the subnet
+    network_scan: bool,
+    discovery_url: String,
+    /// OAuth access token for the opt-in remote discovery
+    remote_token: Option<String>,
+    remote_url: String,
+    cancellation: Option<CancellationToken>,
 }
 
 /// Discovery result containing found bridges
@@ -40,6 +76,8 @@ pub enum DiscoveryMethod {
     Mdns,
     Manual,
     NetworkScan,
+    /// Hue remote (cloud) API, only used when an access token is given
+    Remote,
 }
 
 /// Philips discovery service response
@@ -54,19 +92,35 @@ struct PhilipsDiscoveryResponse {
 impl BridgeDiscovery {
     /// Create a new bridge discovery instance
     pub fn new() -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent("huestatus/1.0")
-            .build()
-            .map_err(|e| HueStatusError::NetworkError { source: e })?;
+        let user_agent = default_user_agent();
+        let client = build_http_client(REQUEST_TIMEOUT, &user_agent, false)?;
 
         Ok(Self {
             client,
+            user_agent,
             timeout: Duration::from_secs(10),
             verbose: false,
+            first_match: false,
+            network_scan: false,
+            discovery_url: DEFAULT_DISCOVERY_URL.to_string(),
+            remote_token: None,
+            remote_url: DEFAULT_REMOTE_DISCOVERY_URL.to_string(),
+            cancellation: None,
         })
     }
 
+    /// Override the user agent sent with every request
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Result<Self> {
+        self.user_agent = user_agent.into();
+        self.rebuild_http_client()
+    }
+
+    /// Rebuild the HTTP client after a connection setting changed
+    fn rebuild_http_client(mut self) -> Result<Self> {
+        self.client = build_http_client(REQUEST_TIMEOUT, &self.user_agent, false)?;
+        Ok(self)
+    }
+
     /// Create with custom timeout
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
@@ -79,41 +133,217 @@ impl BridgeDiscovery {
         self
     }
 
-    /// Discover bridges using all available methods
+    /// Stop the network scan as soon as one bridge is confirmed
+    pub fn with_first_match(mut self, first_match: bool) -> Self {
+        self.first_match = first_match;
+        self
+    }
+
+    /// Let `discover_all` fall back to a network scan
+    ///
+    /// The scan sends a request to every host of the local /24 subnets, so it
+    /// is off by default.
+    pub fn with_network_scan(mut self, network_scan: bool) -> Self {
+        self.network_scan = network_scan;
+        self
+    }
+
+    /// Use another discovery service, e.g. a mirror on an air-gapped network
+    pub fn with_discovery_url(mut self, discovery_url: String) -> Self {
+        self.discovery_url = discovery_url;
+        self
+    }
+
+    /// Stop discovery early when the token is cancelled
+    ///
+    /// `discover_all` then fails with `Cancelled`, and `discover_stream` ends.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Enable remote discovery through the Hue cloud with an OAuth access token
+    pub fn with_remote_access_token(mut self, access_token: String) -> Self {
+        self.remote_token = Some(access_token);
+        self
+    }
+
+    /// Use another remote API endpoint for remote discovery
+    pub fn with_remote_url(mut self, remote_url: String) -> Self {
+        self.remote_url = remote_url;
+        self
+    }
+
+    /// Discover bridges via the Philips service and mDNS, then the network scan if enabled
     pub async fn discover_all(&self) -> Result<DiscoveryResult> {
-        // Try Philips discovery service first (most reliable)
-        if let Ok(result) = self.discover_via_philips_service().await {
-            if !result.bridges.is_empty() {
-                return Ok(result);
-            }
+        let mut found = std::pin::pin!(self.discovery_stream());
+        let mut result: Option<DiscoveryResult> = None;
+        while let Some((method, bridge)) = found.next().await {
+            result
+                .get_or_insert_with(|| DiscoveryResult {
+                    bridges: Vec::new(),
+                    method,
+                })
+                .bridges
+                .push(bridge);
         }
 
-        // Try mDNS discovery as fallback
-        if let Ok(result) = self.discover_via_mdns().await {
-            if !result.bridges.is_empty() {
-                return Ok(result);
-            }
+        check_cancelled(self.cancellation.as_ref(), "Bridge discovery")?;
+        result.ok_or(HueStatusError::BridgeNotFound)
+    }
+
+    /// Stream bridges as the discovery methods find them
+    ///
+    /// Methods run in the order of `discover_all`: the Philips service first
+    /// (most reliable), then mDNS, then the network scan if enabled. The
+    /// stream ends after the first method that finds a bridge, and a bridge
+    /// already yielded (by ID or IP) is not yielded again.
+    pub fn discover_stream(&self) -> impl Stream<Item = DiscoveredBridge> + '_ {
+        self.discovery_stream().map(|(_, bridge)| bridge)
+    }
+
+    /// Discovered bridges tagged with the method that found them
+    fn discovery_stream(&self) -> impl Stream<Item = (DiscoveryMethod, DiscoveredBridge)> + '_ {
+        let mut methods = vec![DiscoveryMethod::PhilipsService, DiscoveryMethod::Mdns];
+        if self.network_scan {
+            methods.push(DiscoveryMethod::NetworkScan);
         }
 
-        // Try network scan as last resort
-        if let Ok(result) = self.discover_via_network_scan().await {
-            if !result.bridges.is_empty() {
-                return Ok(result);
+        // Each method's bridges are passed on as they are found; the next
+        // method only starts if the current one found nothing
+        let found = stream::unfold(
+            (methods.into_iter(), None, false),
+            move |(mut methods, mut current, mut found)| async move {
+                loop {
+                    if let Some((method, bridges)) = &mut current {
+                        let method: &DiscoveryMethod = method;
+                        let bridges: &mut BoxStream<'_, DiscoveredBridge> = bridges;
+                        if let Some(bridge) = bridges.next().await {
+                            let item = (method.clone(), bridge);
+                            return Some((item, (methods, current, true)));
+                        }
+                        if found {
+                            return None;
+                        }
+                    }
+                    let method = methods.next()?;
+                    current = Some((method.clone(), self.method_stream(&method)));
+                    found = false;
+                }
+            },
+        );
+
+        let mut seen = SeenBridges::default();
+        found
+            .filter(move |(_, bridge)| std::future::ready(seen.insert(bridge)))
+            .take_until(cancelled(self.cancellation.as_ref()))
+    }
+
+    /// Bridges found by one automatic discovery method, yielded one at a time
+    fn method_stream(&self, method: &DiscoveryMethod) -> BoxStream<'_, DiscoveredBridge> {
+        match method {
+            DiscoveryMethod::PhilipsService => self.philips_service_stream().boxed(),
+            DiscoveryMethod::Mdns => self.mdns_stream().boxed(),
+            DiscoveryMethod::NetworkScan => self.network_scan_stream().boxed(),
+            // Both need input from the user (an IP or an access token)
+            DiscoveryMethod::Manual | DiscoveryMethod::Remote => stream::empty().boxed(),
+        }
+    }
+
+    /// Discover bridges through the Hue remote API (opt-in, never part of `discover_all`)
+    ///
+    /// The remote API reports the address each bridge has on its own network,
+    /// so only bridges that also answer locally are returned.
+    pub async fn discover_via_remote(&self) -> Result<DiscoveryResult> {
+        let Some(token) = &self.remote_token else {
+            return Err(HueStatusError::InvalidConfig {
+                reason: "remote discovery needs an access token".to_string(),
+            });
+        };
+
+        if self.verbose {
+            eprintln!("🔍 Discovering bridges via the Hue remote API...");
+        }
+
+        let request = self.client.get(&self.remote_url).bearer_auth(token);
+        let response = timeout(self.timeout, request.send())
+            .await
+            .map_err(|_| HueStatusError::TimeoutError {
+                operation: "Hue remote API".to_string(),
+            })?
+            .map_err(|e| HueStatusError::DiscoveryServiceUnreachable {
+                reason: e.to_string(),
+            })?;
+
+        if !response.status().is_success() {
+            return Err(HueStatusError::DiscoveryServiceUnreachable {
+                reason: format!("Hue remote API returned HTTP {}", response.status()),
+            });
+        }
+
+        let body: serde_json::Value =
+            response
+                .json()
+                .await
+                .map_err(|e| HueStatusError::DiscoveryServiceUnreachable {
+                    reason: format!("Invalid JSON response: {e}"),
+                })?;
+
+        let mut discovered_bridges = Vec::new();
+        for bridge in parse_remote_discovery(body) {
+            match self
+                .enrich_bridge_info(&bridge.internalipaddress, Some(bridge.id))
+                .await
+            {
+                Ok(enriched) => discovered_bridges.push(enriched),
+                Err(_) if self.verbose => eprintln!(
+                    "🔍 Bridge at {} is known remotely but doesn't answer here",
+                    bridge.internalipaddress
+                ),
+                Err(_) => {}
             }
         }
 
-        Err(HueStatusError::BridgeNotFound)
+        if self.verbose {
+            eprintln!(
+                "📡 Found {} bridge(s) via the Hue remote API",
+                discovered_bridges.len()
+            );
+        }
+
+        Ok(DiscoveryResult {
+            bridges: discovered_bridges,
+            method: DiscoveryMethod::Remote,
+        })
     }
 
     /// Discover bridges using Philips discovery service
     pub async fn discover_via_philips_service(&self) -> Result<DiscoveryResult> {
+        let mut discovered_bridges = Vec::new();
+        for bridge in self.philips_service_entries().await? {
+            discovered_bridges.push(self.enrich_philips_entry(bridge).await);
+        }
+
+        Ok(DiscoveryResult {
+            bridges: discovered_bridges,
+            method: DiscoveryMethod::PhilipsService,
+        })
+    }
+
+    /// Philips service bridges, each yielded as soon as its details are fetched
+    fn philips_service_stream(&self) -> impl Stream<Item = DiscoveredBridge> + '_ {
+        stream::once(self.philips_service_entries())
+            .flat_map(|entries| stream::iter(entries.unwrap_or_default()))
+            .then(move |bridge| self.enrich_philips_entry(bridge))
+    }
+
+    /// Ask the Philips discovery service for the bridges on this network
+    async fn philips_service_entries(&self) -> Result<Vec<PhilipsDiscoveryResponse>> {
         if self.verbose {
             eprintln!("🔍 Discovering bridges via Philips service...");
         }
 
-        let url = "https://discovery.meethue.com/";
-
-        let response = timeout(self.timeout, self.client.get(url).send())
+        let response = timeout(self.timeout, self.client.get(&self.discovery_url).send())
             .await
             .map_err(|_| HueStatusError::TimeoutError {
                 operation: "Philips discovery service".to_string(),
@@ -128,7 +358,7 @@ impl BridgeDiscovery {
             });
         }
 
-        let bridges: Vec<PhilipsDiscoveryResponse> =
+        let body: serde_json::Value =
             response
                 .json()
                 .await
@@ -136,35 +366,35 @@ impl BridgeDiscovery {
                     reason: format!("Invalid JSON response: {e}"),
                 })?;
 
+        if self.verbose && !body.is_array() {
+            eprintln!("🔍 Philips service returned a non-bridge response: {body}");
+        }
+
+        let bridges = parse_philips_discovery(body);
+
         if self.verbose {
             eprintln!("📡 Found {} bridge(s) via Philips service", bridges.len());
         }
 
-        let mut discovered_bridges = Vec::new();
-        for bridge in bridges {
-            // Validate and enrich bridge information
-            if let Ok(enriched) = self
-                .enrich_bridge_info(&bridge.internalipaddress, Some(bridge.id.clone()))
-                .await
-            {
-                discovered_bridges.push(enriched);
-            } else {
-                // Add basic info even if enrichment fails
-                discovered_bridges.push(DiscoveredBridge {
-                    ip: bridge.internalipaddress,
-                    id: Some(bridge.id),
-                    name: None,
-                    model: None,
-                    version: None,
-                    port: bridge.port,
-                });
-            }
-        }
+        Ok(bridges)
+    }
 
-        Ok(DiscoveryResult {
-            bridges: discovered_bridges,
-            method: DiscoveryMethod::PhilipsService,
-        })
+    /// Validate and enrich a Philips service entry, keeping the basic info if the bridge doesn't answer
+    async fn enrich_philips_entry(&self, bridge: PhilipsDiscoveryResponse) -> DiscoveredBridge {
+        match self
+            .enrich_bridge_info(&bridge.internalipaddress, Some(bridge.id.clone()))
+            .await
+        {
+            Ok(enriched) => enriched,
+            Err(_) => DiscoveredBridge {
+                ip: bridge.internalipaddress,
+                id: Some(bridge.id),
+                name: None,
+                model: None,
+                version: None,
+                port: bridge.port,
+            },
+        }
     }
 
     /// Discover bridges using mDNS
@@ -173,28 +403,9 @@ impl BridgeDiscovery {
             eprintln!("🔍 Discovering bridges via mDNS...");
         }
 
-        // Use tokio::task::spawn_blocking for blocking mDNS operations
-        let discovered = tokio::task::spawn_blocking(Self::mdns_discovery_blocking)
-            .await
-            .map_err(|e| HueStatusError::MdnsDiscoveryFailed {
-                reason: format!("Task join error: {e}"),
-            })?;
-
         let mut bridges = Vec::new();
-        for ip in discovered? {
-            if let Ok(enriched) = self.enrich_bridge_info(&ip, None).await {
-                bridges.push(enriched);
-            } else {
-                // Add basic info even if enrichment fails
-                bridges.push(DiscoveredBridge {
-                    ip,
-                    id: None,
-                    name: None,
-                    model: None,
-                    version: None,
-                    port: None,
-                });
-            }
+        for ip in self.mdns_responses().await? {
+            bridges.push(self.enrich_mdns_response(ip).await);
         }
 
         if self.verbose {
@@ -207,6 +418,42 @@ impl BridgeDiscovery {
         })
     }
 
+    /// mDNS bridges, each yielded as soon as its response is enriched
+    fn mdns_stream(&self) -> impl Stream<Item = DiscoveredBridge> + '_ {
+        if self.verbose {
+            eprintln!("🔍 Discovering bridges via mDNS...");
+        }
+
+        stream::once(self.mdns_responses())
+            .flat_map(|ips| stream::iter(ips.unwrap_or_default()))
+            .then(move |ip| self.enrich_mdns_response(ip))
+    }
+
+    /// Addresses that answered the mDNS query
+    async fn mdns_responses(&self) -> Result<Vec<String>> {
+        // Use tokio::task::spawn_blocking for blocking mDNS operations
+        tokio::task::spawn_blocking(Self::mdns_discovery_blocking)
+            .await
+            .map_err(|e| HueStatusError::MdnsDiscoveryFailed {
+                reason: format!("Task join error: {e}"),
+            })?
+    }
+
+    /// Enrich an mDNS response, keeping the bare address if the bridge doesn't answer
+    async fn enrich_mdns_response(&self, ip: String) -> DiscoveredBridge {
+        match self.enrich_bridge_info(&ip, None).await {
+            Ok(enriched) => enriched,
+            Err(_) => DiscoveredBridge {
+                ip,
+                id: None,
+                name: None,
+                model: None,
+                version: None,
+                port: None,
+            },
+        }
+    }
+
     /// Blocking mDNS discovery
     fn mdns_discovery_blocking() -> Result<Vec<String>> {
         // For now, return empty result since mDNS implementation
@@ -221,13 +468,18 @@ impl BridgeDiscovery {
     }
 
     /// Discover bridges via network scan
+    ///
+    /// Probes every host of the local /24 subnets, which can be slow and may be
+    /// flagged by network monitoring.
     pub async fn discover_via_network_scan(&self) -> Result<DiscoveryResult> {
         if self.verbose {
             eprintln!("🔍 Scanning network for bridges...");
         }
 
-        // Get local network ranges to scan
+        // Get local network ranges to scan, skipping our own address and the gateway
         let network_ranges = self.get_local_network_ranges()?;
+        let mut excluded = self.get_local_ip_addresses()?;
+        excluded.extend(default_gateway().map(IpAddr::V4));
         let mut bridges = Vec::new();
 
         for range in network_ranges {
@@ -235,8 +487,12 @@ impl BridgeDiscovery {
                 eprintln!("📡 Scanning network range: {range}");
             }
 
-            let range_bridges = self.scan_network_range(&range).await?;
+            let range_bridges = self.scan_network_range(&range, &excluded).await?;
             bridges.extend(range_bridges);
+
+            if self.first_match && !bridges.is_empty() {
+                break;
+            }
         }
 
         if self.verbose {
@@ -249,6 +505,31 @@ impl BridgeDiscovery {
         })
     }
 
+    /// Network scan results, each yielded as soon as its probe confirms a bridge
+    ///
+    /// Ranges are scanned one after another; with `first_match` the scan
+    /// stops at the first bridge.
+    fn network_scan_stream(&self) -> impl Stream<Item = DiscoveredBridge> + '_ {
+        if self.verbose {
+            eprintln!("🔍 Scanning network for bridges...");
+        }
+
+        let ranges = self.get_local_network_ranges().unwrap_or_default();
+        let mut excluded = self.get_local_ip_addresses().unwrap_or_default();
+        excluded.extend(default_gateway().map(IpAddr::V4));
+
+        let bridges = stream::iter(ranges)
+            .flat_map(move |range| {
+                if self.verbose {
+                    eprintln!("📡 Scanning network range: {range}");
+                }
+                self.scan_network_range_stream(&range, &excluded)
+            })
+            .map(|(_, bridge)| bridge);
+        let limit = if self.first_match { 1 } else { usize::MAX };
+        bridges.take(limit)
+    }
+
     /// Get local network ranges for scanning
     fn get_local_network_ranges(&self) -> Result<Vec<String>> {
         // Get local IP addresses
@@ -301,33 +582,53 @@ impl BridgeDiscovery {
     }
 
     /// Scan a network range for Hue bridges
-    async fn scan_network_range(&self, network: &str) -> Result<Vec<DiscoveredBridge>> {
-        let mut bridges = Vec::new();
-        let mut tasks = Vec::new();
+    async fn scan_network_range(
+        &self,
+        network: &str,
+        excluded: &[IpAddr],
+    ) -> Result<Vec<DiscoveredBridge>> {
+        let found = self.scan_network_range_stream(network, excluded);
+        // Stop early if only one bridge is wanted
+        let limit = if self.first_match { 1 } else { usize::MAX };
+        let mut found: Vec<_> = found.take(limit).collect().await;
+
+        found.sort_by_key(|(position, _)| *position);
+        Ok(found.into_iter().map(|(_, bridge)| bridge).collect())
+    }
 
-        // Scan IPs 1-254 in the network range
-        for i in 1..=254 {
-            let ip = format!("{network}.{i}");
+    /// Probe every address of a range, yielding each bridge (with its scan position) as it answers
+    ///
+    /// Dropping the stream aborts the probes still running.
+    fn scan_network_range_stream(
+        &self,
+        network: &str,
+        excluded: &[IpAddr],
+    ) -> impl Stream<Item = (usize, DiscoveredBridge)> {
+        let mut tasks = JoinSet::new();
+        let jitter = RandomState::new();
+
+        // Stagger request starts with random jitter so the bridge is not hit by a burst
+        for (position, ip) in scan_order(network, excluded).into_iter().enumerate() {
             let client = self.client.clone();
             let timeout = self.timeout;
-            let ip_clone = ip.clone();
+            let start_delay = SCAN_STAGGER * position as u32
+                + Duration::from_millis(jitter.hash_one(&ip) % (SCAN_JITTER_MS + 1));
 
-            let task =
-                tokio::spawn(
-                    async move { Self::test_bridge_at_ip(client, &ip_clone, timeout).await },
-                );
-
-            tasks.push((ip, task));
+            tasks.spawn(async move {
+                tokio::time::sleep(start_delay).await;
+                let bridge = Self::test_bridge_at_ip(client, &ip, timeout).await;
+                (position, bridge)
+            });
         }
 
-        // Wait for all tasks to complete
-        for (_ip, task) in tasks {
-            if let Ok(Ok(Some(bridge))) = task.await {
-                bridges.push(bridge);
+        stream::unfold(tasks, |mut tasks| async move {
+            while let Some(joined) = tasks.join_next().await {
+                if let Ok((position, Ok(Some(bridge)))) = joined {
+                    return Some(((position, bridge), tasks));
+                }
             }
-        }
-
-        Ok(bridges)
+            None
+        })
     }
 
     /// Test if there's a Hue bridge at the given IP
@@ -491,12 +792,13 @@ impl BridgeDiscovery {
 
     /// Get the best bridge from discovery results
     pub fn select_best_bridge(results: &[DiscoveryResult]) -> Option<&DiscoveredBridge> {
-        // Priority order: Philips service > Manual > mDNS > Network scan
+        // Priority order: Philips service > Manual > mDNS > Network scan > Remote
         let method_priority = |method: &DiscoveryMethod| match method {
-            DiscoveryMethod::PhilipsService => 4,
-            DiscoveryMethod::Manual => 3,
-            DiscoveryMethod::Mdns => 2,
-            DiscoveryMethod::NetworkScan => 1,
+            DiscoveryMethod::PhilipsService => 5,
+            DiscoveryMethod::Manual => 4,
+            DiscoveryMethod::Mdns => 3,
+            DiscoveryMethod::NetworkScan => 2,
+            DiscoveryMethod::Remote => 1,
         };
 
         results
@@ -592,11 +894,104 @@ impl DiscoveryResult {
                 DiscoveryMethod::Mdns => "mDNS",
                 DiscoveryMethod::Manual => "manual entry",
                 DiscoveryMethod::NetworkScan => "network scan",
+                DiscoveryMethod::Remote => "Hue remote API",
             }
         )
     }
 }
 
+/// Bridges already yielded by `discover_stream`, by IP and ID
+#[derive(Debug, Default)]
+struct SeenBridges {
+    ips: HashSet<String>,
+    ids: HashSet<String>,
+}
+
+impl SeenBridges {
+    /// Record a bridge, returning whether neither its IP nor its ID was seen before
+    fn insert(&mut self, bridge: &DiscoveredBridge) -> bool {
+        let new_ip = self.ips.insert(bridge.ip.clone());
+        let new_id = bridge
+            .id
+            .as_ref()
+            .is_none_or(|id| self.ids.insert(id.to_ascii_lowercase()));
+        new_ip && new_id
+    }
+}
+
+/// Addresses of a /24 range in scan order: likely DHCP hosts first, excluded addresses skipped
+fn scan_order(network: &str, excluded: &[IpAddr]) -> Vec<String> {
+    let rest = (1..=254u8).filter(|host| !PRIORITY_HOSTS.contains(host));
+
+    PRIORITY_HOSTS
+        .chain(rest)
+        .map(|host| format!("{network}.{host}"))
+        .filter(|ip| {
+            !IpAddr::from_str(ip)
+                .map(|addr| excluded.contains(&addr))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Default IPv4 gateway read from the kernel routing table (Linux only)
+fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [_, "00000000", gateway, ..] => u32::from_str_radix(gateway, 16)
+                .ok()
+                .map(|raw| Ipv4Addr::from(raw.to_le_bytes())),
+            _ => None,
+        }
+    })
+}
+
+/// Extract bridge entries from a Philips discovery service response
+///
+/// The service answers with an object instead of an array when it is rate
+/// limiting or failing; that, like any malformed entry, yields no bridges so
+/// discovery can fall through to the next method.
+fn parse_philips_discovery(body: serde_json::Value) -> Vec<PhilipsDiscoveryResponse> {
+    match body {
+        serde_json::Value::Array(entries) => entries
+            .into_iter()
+            .filter_map(|entry| serde_json::from_value(entry).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse a remote API answer: one bridge configuration, or a list of them
+///
+/// Entries without a bridge ID or local address are skipped.
+fn parse_remote_discovery(body: serde_json::Value) -> Vec<PhilipsDiscoveryResponse> {
+    let entries = match body {
+        serde_json::Value::Array(entries) => entries,
+        entry @ serde_json::Value::Object(_) => vec![entry],
+        _ => Vec::new(),
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let field = |names: &[&str]| {
+                names
+                    .iter()
+                    .find_map(|name| entry.get(*name).and_then(|value| value.as_str()))
+                    .map(str::to_string)
+            };
+            Some(PhilipsDiscoveryResponse {
+                id: field(&["bridgeid", "id"])?,
+                internalipaddress: field(&["ipaddress", "internalipaddress"])?,
+                port: None,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -616,6 +1011,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seen_bridges() {
+        let bridge = |ip: &str, id: Option<&str>| DiscoveredBridge {
+            ip: ip.to_string(),
+            id: id.map(String::from),
+            name: None,
+            model: None,
+            version: None,
+            port: None,
+        };
+        let mut seen = SeenBridges::default();
+
+        assert!(seen.insert(&bridge("192.168.1.2", Some("001788fffe000001"))));
+        assert!(!seen.insert(&bridge("192.168.1.2", None)));
+        assert!(!seen.insert(&bridge("192.168.1.9", Some("001788FFFE000001"))));
+        assert!(seen.insert(&bridge("192.168.1.3", None)));
+    }
+
+    #[test]
+    fn test_scan_order() {
+        let excluded = vec![
+            IpAddr::from_str("192.168.1.1").unwrap(),
+            IpAddr::from_str("192.168.1.42").unwrap(),
+        ];
+        let order = scan_order("192.168.1", &excluded);
+
+        assert_eq!(order.len(), 252);
+        assert_eq!(order[0], "192.168.1.2");
+        assert_eq!(order[18], "192.168.1.20");
+        assert_eq!(order[19], "192.168.1.21");
+        assert!(!order.contains(&"192.168.1.42".to_string()));
+        assert_eq!(order.last().unwrap(), "192.168.1.254");
+    }
+
+    #[test]
+    fn test_parse_philips_discovery() {
+        let body = serde_json::json!([
+            {"id": "001788fffe23456", "internalipaddress": "192.168.1.100", "port": 443},
+            {"unexpected": true}
+        ]);
+        let bridges = parse_philips_discovery(body);
+        assert_eq!(bridges.len(), 1);
+        assert_eq!(bridges[0].internalipaddress, "192.168.1.100");
+        assert_eq!(bridges[0].port, Some(443));
+
+        let rate_limited = serde_json::json!({"error": {"type": 429, "description": "rate limit"}});
+        assert!(parse_philips_discovery(rate_limited).is_empty());
+        assert!(parse_philips_discovery(serde_json::json!([])).is_empty());
+    }
+
+    #[test]
+    fn test_parse_remote_discovery() {
+        let config = serde_json::json!({
+            "name": "Philips hue",
+            "bridgeid": "001788FFFE23456",
+            "ipaddress": "192.168.1.100"
+        });
+        let bridges = parse_remote_discovery(config);
+        assert_eq!(bridges.len(), 1);
+        assert_eq!(bridges[0].id, "001788FFFE23456");
+        assert_eq!(bridges[0].internalipaddress, "192.168.1.100");
+
+        let list = serde_json::json!([
+            {"id": "001788fffe23456", "internalipaddress": "10.0.0.2"},
+            {"name": "no address"}
+        ]);
+        assert_eq!(parse_remote_discovery(list).len(), 1);
+    }
+
+    #[test]
+    fn test_select_best_bridge_prefers_local() {
+        let bridge = |ip: &str| DiscoveredBridge {
+            ip: ip.to_string(),
+            id: None,
+            name: None,
+            model: None,
+            version: None,
+            port: None,
+        };
+        let results = vec![
+            DiscoveryResult {
+                bridges: vec![bridge("10.0.0.2")],
+                method: DiscoveryMethod::Remote,
+            },
+            DiscoveryResult {
+                bridges: vec![bridge("192.168.1.100")],
+                method: DiscoveryMethod::NetworkScan,
+            },
+        ];
+
+        let best = BridgeDiscovery::select_best_bridge(&results).unwrap();
+        assert_eq!(best.ip, "192.168.1.100");
+        let best = BridgeDiscovery::select_best_bridge(&results[..1]).unwrap();
+        assert_eq!(best.ip, "10.0.0.2");
+    }
+
     #[test]
     fn test_discovered_bridge_display_name() {
         let bridge = DiscoveredBridge {
@@ -691,4 +1182,61 @@ mod tests {
         assert!(complete_bridge.is_complete());
         assert!(!incomplete_bridge.is_complete());
     }
+
+    #[tokio::test]
+    async fn test_cancelled_discovery() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let discovery = BridgeDiscovery::new().unwrap().with_cancellation(token);
+
+        assert!(matches!(
+            discovery.discover_all().await,
+            Err(HueStatusError::Cancelled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_discover_stream_yields_bridges_as_found() {
+        let mut first = mockito::Server::new_async().await;
+        let mut second = mockito::Server::new_async().await;
+        let entries = serde_json::json!([
+            { "id": "001788fffe000001", "internalipaddress": first.host_with_port() },
+            { "id": "001788fffe000002", "internalipaddress": second.host_with_port() },
+        ]);
+        first
+            .mock("GET", "/discovery")
+            .with_body(entries.to_string())
+            .create_async()
+            .await;
+        first
+            .mock("GET", "/api/0/config")
+            .with_body(r#"{"name":"Living room"}"#)
+            .create_async()
+            .await;
+        let second_config = second
+            .mock("GET", "/api/0/config")
+            .with_body(r#"{"name":"Bedroom"}"#)
+            .create_async()
+            .await;
+
+        let discovery = BridgeDiscovery::new()
+            .unwrap()
+            .with_discovery_url(format!("{}/discovery", first.url()));
+        let stream = discovery.discover_stream();
+        futures_util::pin_mut!(stream);
+
+        let bridge = stream.next().await.unwrap();
+        assert_eq!(bridge.id.as_deref(), Some("001788fffe000001"));
+        assert_eq!(bridge.name.as_deref(), Some("Living room"));
+        // The first bridge arrives before the second one is even queried
+        assert!(!second_config.matched_async().await);
+
+        let bridge = stream.next().await.unwrap();
+        assert_eq!(bridge.id.as_deref(), Some("001788fffe000002"));
+        assert_eq!(bridge.name.as_deref(), Some("Bedroom"));
+        second_config.assert_async().await;
+
+        // The Philips service found bridges, so mDNS and the scan never run
+        assert!(stream.next().await.is_none());
+    }
 }
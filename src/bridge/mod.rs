@@ -1,6 +1,7 @@
 use crate::error::{HueStatusError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 pub mod auth;
 pub mod client;
@@ -55,7 +56,7 @@ pub struct BridgeCapabilities {
     pub lights: CapabilityLimits,
     pub sensors: CapabilityLimits,
     pub groups: CapabilityLimits,
-    pub scenes: CapabilityLimits,
+    pub scenes: SceneCapabilities,
     pub rules: CapabilityLimits,
     pub schedules: CapabilityLimits,
     pub resourcelinks: CapabilityLimits,
@@ -70,6 +71,16 @@ pub struct CapabilityLimits {
     pub total: usize,
 }
 
+/// Scene capacity, including the light states all scenes share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneCapabilities {
+    pub available: usize,
+    pub total: usize,
+    /// Light states left for scenes (each light in a scene uses one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lightstates: Option<CapabilityLimits>,
+}
+
 /// Streaming capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamingCapabilities {
@@ -85,7 +96,9 @@ pub struct Light {
     pub state: LightState,
     #[serde(rename = "type")]
     pub light_type: String,
+    #[serde(default)]
     pub modelid: String,
+    #[serde(default)]
     pub manufacturername: String,
     pub productname: Option<String>,
     pub capabilities: Option<LightCapabilities>,
@@ -136,6 +149,13 @@ pub struct ColorTemperatureCapability {
     pub max: u16,
 }
 
+impl ColorTemperatureCapability {
+    /// Limit a color temperature (in mireds) to the range the light supports
+    pub fn clamp(&self, ct: u16) -> u16 {
+        ct.clamp(self.min, self.max.max(self.min))
+    }
+}
+
 /// Streaming capability
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamingCapability {
@@ -146,8 +166,11 @@ pub struct StreamingCapability {
 /// Light configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LightConfig {
+    #[serde(default)]
     pub archetype: String,
+    #[serde(default)]
     pub function: String,
+    #[serde(default)]
     pub direction: String,
     pub startup: Option<StartupConfig>,
 }
@@ -176,6 +199,12 @@ pub struct Scene {
     pub lightstates: Option<HashMap<String, LightState>>,
 }
 
+/// Name prefix of the scenes huestatus creates
+pub const HUESTATUS_SCENE_PREFIX: &str = "huestatus-";
+
+/// `appdata.data` marker stored on the scenes huestatus creates
+pub const HUESTATUS_APPDATA: &str = "huestatus";
+
 /// Scene creation request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSceneRequest {
@@ -183,12 +212,94 @@ pub struct CreateSceneRequest {
     pub lights: Vec<String>,
     pub recycle: bool,
     pub lightstates: HashMap<String, LightState>,
+    /// Application data stored with the scene, used to recognize huestatus scenes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appdata: Option<serde_json::Value>,
+}
+
+/// Application data marking a scene as created by huestatus
+pub fn huestatus_appdata() -> serde_json::Value {
+    serde_json::json!({ "version": 1, "data": HUESTATUS_APPDATA })
+}
+
+/// Scene update request (for changing an existing scene in place)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSceneRequest {
+    pub lights: Vec<String>,
+    pub lightstates: HashMap<String, LightState>,
 }
 
 /// Scene action request (for executing scenes)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneActionRequest {
     pub scene: String,
+    /// Transition time in deciseconds (0 = instant); the bridge caps very large values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transitiontime: Option<u16>,
+}
+
+/// Light alert effect sent with a status (`alert` in the Hue API)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertMode {
+    /// No alert
+    #[default]
+    None,
+    /// A single blink
+    Select,
+    /// Breathe for about 15 seconds
+    LSelect,
+}
+
+impl AlertMode {
+    /// Value of the `alert` field
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Select => "select",
+            Self::LSelect => "lselect",
+        }
+    }
+}
+
+impl std::str::FromStr for AlertMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "select" => Ok(Self::Select),
+            "lselect" => Ok(Self::LSelect),
+            _ => Err(format!(
+                "unknown alert '{value}' (use none, select or lselect)"
+            )),
+        }
+    }
+}
+
+/// Partial light or group state change (only set fields are sent)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LightStateUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bri: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bri_inc: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hue: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sat: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xy: Option<[f64; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ct: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert: Option<String>,
+    /// Transition time in deciseconds (0 = instant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transitiontime: Option<u16>,
 }
 
 /// Group information
@@ -227,42 +338,68 @@ pub struct GroupAction {
 }
 
 /// Bridge configuration
+///
+/// Only the identifying fields are required; everything else varies between
+/// firmware versions and falls back to a default when absent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeConfiguration {
     pub name: String,
+    #[serde(default)]
     pub zigbeechannel: u8,
     pub bridgeid: String,
+    #[serde(default)]
     pub mac: String,
+    #[serde(default)]
     pub dhcp: bool,
+    #[serde(default)]
     pub ipaddress: String,
+    #[serde(default)]
     pub netmask: String,
+    #[serde(default)]
     pub gateway: String,
+    #[serde(default)]
     pub proxyaddress: String,
+    #[serde(default)]
     pub proxyport: u16,
-    #[serde(rename = "UTC")]
+    #[serde(default, rename = "UTC")]
     pub utc: String,
+    #[serde(default)]
     pub localtime: String,
+    #[serde(default)]
     pub timezone: String,
     pub modelid: String,
+    #[serde(default)]
     pub datastoreversion: String,
     pub swversion: String,
     pub apiversion: String,
+    #[serde(default)]
     pub swupdate: SwUpdate,
+    #[serde(default)]
     pub swupdate2: SwUpdate2,
+    #[serde(default)]
     pub linkbutton: bool,
+    #[serde(default)]
     pub portalservices: bool,
+    #[serde(default)]
     pub portalconnection: String,
+    #[serde(default)]
     pub portalstate: PortalState,
+    #[serde(default)]
     pub internetservices: InternetServices,
+    #[serde(default)]
     pub factorynew: bool,
     pub replacesbridgeid: Option<String>,
+    #[serde(default)]
     pub backup: Backup,
+    #[serde(default)]
     pub starterkitid: String,
+    #[serde(default)]
     pub whitelist: HashMap<String, WhitelistEntry>,
 }
 
 /// Software update information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SwUpdate {
     pub updatestate: u8,
     pub checkforupdate: bool,
@@ -273,7 +410,8 @@ pub struct SwUpdate {
 }
 
 /// Software update 2 information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SwUpdate2 {
     pub checkforupdate: bool,
     pub lastchange: String,
@@ -283,7 +421,8 @@ pub struct SwUpdate2 {
 }
 
 /// Device types for updates
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DeviceTypes {
     pub bridge: bool,
     pub lights: Vec<String>,
@@ -291,21 +430,24 @@ pub struct DeviceTypes {
 }
 
 /// Bridge update information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BridgeUpdateInfo {
     pub state: String,
     pub lastinstall: String,
 }
 
 /// Auto install configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AutoInstall {
     pub updatetime: String,
     pub on: bool,
 }
 
 /// Portal state
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PortalState {
     pub signedon: bool,
     pub incoming: bool,
@@ -314,7 +456,8 @@ pub struct PortalState {
 }
 
 /// Internet services configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InternetServices {
     pub internet: String,
     pub remoteaccess: String,
@@ -323,14 +466,16 @@ pub struct InternetServices {
 }
 
 /// Backup configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Backup {
     pub status: String,
     pub errorcode: u8,
 }
 
 /// Whitelist entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WhitelistEntry {
     #[serde(rename = "last use date")]
     pub last_use_date: String,
@@ -355,15 +500,7 @@ impl<T> HueResponse<T> {
         match self {
             HueResponse::Success(value) => Ok(value),
             HueResponse::Error(error) => Err(error.into()),
-            HueResponse::ErrorArray(errors) => {
-                if let Some(error) = errors.into_iter().next() {
-                    Err(error.into())
-                } else {
-                    Err(HueStatusError::ApiError {
-                        message: "Unknown API error".to_string(),
-                    })
-                }
-            }
+            HueResponse::ErrorArray(errors) => Err(hue_errors_to_error(errors)),
         }
     }
 
@@ -385,13 +522,228 @@ impl<T> HueResponse<T> {
     }
 }
 
+/// Default user agent sent to the bridge and discovery service
+pub fn default_user_agent() -> String {
+    format!("{}/{}", crate::APP_NAME, crate::VERSION)
+}
+
+/// Build the HTTP client shared by the bridge, auth and discovery clients
+///
+/// `accept_invalid_certs` disables TLS certificate verification entirely; only the
+/// bridge client sets it, for an `https://` bridge with a self-signed certificate.
+/// Discovery and the Hue cloud endpoints always verify certificates.
+pub(crate) fn build_http_client(
+    timeout: Duration,
+    user_agent: &str,
+    accept_invalid_certs: bool,
+) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .user_agent(user_agent)
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .build()
+        .map_err(|e| HueStatusError::NetworkError { source: e })
+}
+
+/// Convert a Hue hue (0-65535) and saturation (0-254) to full-brightness RGB
+pub fn hue_sat_to_rgb(hue: u16, sat: u8) -> [u8; 3] {
+    let h = f64::from(hue) / 65536.0 * 6.0;
+    let s = f64::from(sat.min(254)) / 254.0;
+    let sector = h.floor();
+    let f = h - sector;
+    let (p, q, t) = (1.0 - s, 1.0 - s * f, 1.0 - s * (1.0 - f));
+
+    let (r, g, b) = match sector as u8 {
+        0 => (1.0, t, p),
+        1 => (q, 1.0, p),
+        2 => (p, 1.0, t),
+        3 => (p, q, 1.0),
+        4 => (t, p, 1.0),
+        _ => (1.0, p, q),
+    };
+
+    [r, g, b].map(|channel: f64| (channel * 255.0).round() as u8)
+}
+
+/// Approximate name of a hue/saturation color, e.g. "orange" or "white"
+pub fn hue_sat_color_name(hue: u16, sat: u8) -> &'static str {
+    if sat < 40 {
+        return "white";
+    }
+
+    // Upper bound of each hue range, in degrees
+    let degrees = u32::from(hue) * 360 / 65536;
+    let names = [
+        (15, "red"),
+        (45, "orange"),
+        (70, "yellow"),
+        (160, "green"),
+        (200, "cyan"),
+        (255, "blue"),
+        (290, "purple"),
+        (335, "pink"),
+        (360, "red"),
+    ];
+    names
+        .iter()
+        .find(|(upper, _)| degrees < *upper)
+        .map_or("red", |(_, name)| name)
+}
+
+/// Approximate name of a white color temperature in mireds
+pub fn color_temperature_name(mireds: u16) -> &'static str {
+    match mireds_to_kelvin(mireds) {
+        ..=3500 => "warm white",
+        3501..=5000 => "neutral white",
+        _ => "cool white",
+    }
+}
+
+/// Rough distance between two hue/saturation colors, from 0 (same) to 100
+///
+/// Euclidean distance of the full-brightness RGB values; good enough to tell
+/// whether two status colors can be told apart at a glance.
+pub fn color_distance(a: (u16, u8), b: (u16, u8)) -> f64 {
+    let [ar, ag, ab] = hue_sat_to_rgb(a.0, a.1).map(f64::from);
+    let [br, bg, bb] = hue_sat_to_rgb(b.0, b.1).map(f64::from);
+    let distance = ((ar - br).powi(2) + (ag - bg).powi(2) + (ab - bb).powi(2)).sqrt();
+    distance / (3.0_f64.sqrt() * 255.0) * 100.0
+}
+
+/// Convert a color temperature in Kelvin to the Hue `ct` value in mireds
+pub fn kelvin_to_mireds(kelvin: u16) -> u16 {
+    (1_000_000 / u32::from(kelvin.max(1))).min(u32::from(u16::MAX)) as u16
+}
+
+/// Convert a Hue `ct` value in mireds to a color temperature in Kelvin
+pub fn mireds_to_kelvin(mireds: u16) -> u16 {
+    (1_000_000 / u32::from(mireds.max(1))).min(u32::from(u16::MAX)) as u16
+}
+
+/// Convert a Hue brightness (0-254) to a percentage, rounded to the nearest
+pub fn bri_to_percent(bri: u8) -> u8 {
+    ((u16::from(bri.min(254)) * 100 + 127) / 254) as u8
+}
+
+/// Convert a brightness percentage to a Hue brightness (1-254)
+///
+/// 1-100% maps linearly onto 1-254, so 100% is 254; 0% is 0, meaning off.
+/// Values above 100 are treated as 100%.
+pub fn percent_to_bri(percent: u8) -> u8 {
+    match percent.min(100) {
+        0 => 0,
+        percent => (1 + (u16::from(percent - 1) * 253 + 49) / 99) as u8,
+    }
+}
+
+/// Parse a brightness given as a Hue value (`180`) or a percentage (`70%`)
+pub fn parse_brightness(value: &str) -> std::result::Result<u8, String> {
+    let value = value.trim();
+    match value.strip_suffix('%') {
+        Some(percent) => percent
+            .trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|percent| *percent <= 100)
+            .map(percent_to_bri)
+            .ok_or_else(|| format!("invalid brightness '{value}' (expected 0-100%)")),
+        None => value
+            .parse::<u8>()
+            .ok()
+            .filter(|bri| *bri <= 254)
+            .ok_or_else(|| {
+                format!("invalid brightness '{value}' (expected 0-254 or a percentage like 50%)")
+            }),
+    }
+}
+
+/// Brightness change relative to the current level, e.g. `+10%` or `-20`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrightnessChange {
+    /// Percent of the full range (`+10%`)
+    Percent(i16),
+    /// Hue brightness units (`+25`)
+    Raw(i16),
+}
+
+impl BrightnessChange {
+    /// New brightness after the change, clamped to 1-254 so the lights stay on
+    pub fn apply(self, current: u8) -> u8 {
+        let delta = match self {
+            Self::Percent(percent) => (f64::from(percent) * 2.54).round() as i16,
+            Self::Raw(delta) => delta,
+        };
+        (i16::from(current) + delta).clamp(1, 254) as u8
+    }
+}
+
+impl std::str::FromStr for BrightnessChange {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let value = value.trim();
+        let invalid =
+            || format!("invalid brightness change '{value}' (expected e.g. +10, -20 or +10%)");
+        if !value.starts_with(['+', '-']) {
+            return Err(invalid());
+        }
+        match value.strip_suffix('%') {
+            Some(percent) => percent
+                .trim()
+                .parse::<i16>()
+                .ok()
+                .filter(|percent| percent.abs() <= 100)
+                .map(Self::Percent)
+                .ok_or_else(invalid),
+            None => value
+                .parse::<i16>()
+                .ok()
+                .filter(|delta| delta.abs() <= 254)
+                .map(Self::Raw)
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+/// Find the single scene whose name matches, ignoring case
+pub fn find_scene_by_name<'a>(
+    scenes: &'a HashMap<String, Scene>,
+    name: &str,
+) -> Result<(&'a str, &'a Scene)> {
+    let mut matches: Vec<(&str, &Scene)> = scenes
+        .iter()
+        .filter(|(_, scene)| scene.name.eq_ignore_ascii_case(name))
+        .map(|(id, scene)| (id.as_str(), scene))
+        .collect();
+
+    match matches.len() {
+        0 => Err(HueStatusError::SceneNotFound {
+            scene_name: name.to_string(),
+        }),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            matches.sort_by_key(|(id, _)| *id);
+            let candidates = matches
+                .iter()
+                .map(|(id, scene)| format!("{} ({id})", scene.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            Err(HueStatusError::AmbiguousSceneName {
+                name: name.to_string(),
+                candidates,
+            })
+        }
+    }
+}
+
 impl From<HueError> for HueStatusError {
     fn from(error: HueError) -> Self {
         match error.error.error_type {
             1 => HueStatusError::AuthenticationFailed,
             101 => HueStatusError::LinkButtonNotPressed,
-            3 => HueStatusError::InvalidConfig {
-                reason: format!("Resource not available: {}", error.error.description),
+            3 => HueStatusError::ResourceNotAvailable {
+                resource: error.error.description,
             },
             4 => HueStatusError::InvalidConfig {
                 reason: format!("Method not available: {}", error.error.description),
@@ -412,6 +764,11 @@ impl From<HueError> for HueStatusError {
                 message: "Too many items in list".to_string(),
             },
             12 => portal_connection_required_error(),
+            // The v1 API reports an overloaded bridge as an internal error
+            901 => HueStatusError::RateLimited {
+                reason: error.error.description,
+                retry_after_seconds: None,
+            },
             _ => HueStatusError::ApiError {
                 message: format!(
                     "API error {}: {}",
@@ -422,6 +779,79 @@ impl From<HueError> for HueStatusError {
     }
 }
 
+/// Convert a Hue error array into one error that lists every failure
+///
+/// A single error converts as usual. Of several, the most severe error type
+/// decides the classification (and exit code), with the others added to its
+/// message where it has one; several unclassified errors become one `ApiError`
+/// naming each failing address, so batch failures don't hide all but the first.
+pub fn hue_errors_to_error(mut errors: Vec<HueError>) -> HueStatusError {
+    match errors.len() {
+        0 => HueStatusError::ApiError {
+            message: "Unknown API error".to_string(),
+        },
+        1 => errors.remove(0).into(),
+        count => {
+            let details = |errors: &[HueError]| {
+                errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.error.address, error.error.description))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            };
+
+            // The first of the most severe errors; max_by_key would pick the last
+            let primary = (0..count)
+                .rev()
+                .max_by_key(|&i| hue_error_severity(errors[i].error.error_type))
+                .unwrap_or(0);
+            if hue_error_severity(errors[primary].error.error_type) == 0 {
+                return HueStatusError::ApiError {
+                    message: format!("{count} API errors: {}", details(&errors)),
+                };
+            }
+
+            let primary = errors.remove(primary);
+            let also = format!(" (also: {})", details(&errors));
+            match HueStatusError::from(primary) {
+                HueStatusError::ResourceNotAvailable { resource } => {
+                    HueStatusError::ResourceNotAvailable {
+                        resource: resource + &also,
+                    }
+                }
+                HueStatusError::InvalidConfig { reason } => HueStatusError::InvalidConfig {
+                    reason: reason + &also,
+                },
+                HueStatusError::RateLimited {
+                    reason,
+                    retry_after_seconds,
+                } => HueStatusError::RateLimited {
+                    reason: reason + &also,
+                    retry_after_seconds,
+                },
+                HueStatusError::ApiError { message } => HueStatusError::ApiError {
+                    message: message + &also,
+                },
+                other => other,
+            }
+        }
+    }
+}
+
+/// Rank a Hue error type for `hue_errors_to_error`; 0 for types without their own error
+fn hue_error_severity(error_type: u16) -> u8 {
+    match error_type {
+        // Nothing else can work without a valid key
+        1 => 6,
+        101 => 5,
+        12 => 4,
+        901 => 3,
+        3 => 2,
+        4..=8 => 1,
+        _ => 0,
+    }
+}
+
 impl HueError {
     /// Check if error is related to authentication
     pub fn is_auth_error(&self) -> bool {
@@ -470,12 +900,23 @@ impl Light {
             .is_some()
     }
 
+    /// Get the color gamut type ("A", "B" or "C"), if the light reports one
+    pub fn color_gamut_type(&self) -> Option<&str> {
+        self.capabilities
+            .as_ref()
+            .and_then(|c| c.control.colorgamuttype.as_deref())
+    }
+
     /// Check if light supports color temperature
     pub fn supports_color_temperature(&self) -> bool {
+        self.color_temperature_range().is_some()
+    }
+
+    /// Get the color temperature range the light supports, if any
+    pub fn color_temperature_range(&self) -> Option<&ColorTemperatureCapability> {
         self.capabilities
             .as_ref()
             .and_then(|c| c.control.ct.as_ref())
-            .is_some()
     }
 
     /// Check if light is reachable
@@ -483,6 +924,11 @@ impl Light {
         self.state.reachable.unwrap_or(false)
     }
 
+    /// Check if the light supports alert effects (it reports an `alert` state)
+    pub fn supports_alert(&self) -> bool {
+        self.state.alert.is_some()
+    }
+
     /// Check if light is on
     pub fn is_on(&self) -> bool {
         self.state.on
@@ -513,6 +959,11 @@ impl Light {
         self.state.ct
     }
 
+    /// Describe what the light currently shows, e.g. "on, 80%, orange"
+    pub fn effective_color_summary(&self) -> String {
+        self.state.color_summary()
+    }
+
     /// Check if light is suitable for status indication
     pub fn is_suitable_for_status(&self) -> bool {
         self.is_reachable() && (self.supports_color() || self.supports_color_temperature())
@@ -532,6 +983,18 @@ impl Light {
     }
 }
 
+impl Group {
+    /// Check if group is a room
+    pub fn is_room(&self) -> bool {
+        self.group_type == "Room"
+    }
+
+    /// Check if group contains all of the given lights
+    pub fn contains_lights(&self, light_ids: &[String]) -> bool {
+        light_ids.iter().all(|id| self.lights.contains(id))
+    }
+}
+
 impl Scene {
     /// Check if scene is recycle-able
     pub fn is_recyclable(&self) -> bool {
@@ -562,9 +1025,74 @@ impl Scene {
     pub fn is_suitable_for_status(&self) -> bool {
         !self.is_locked() && self.light_count() > 0
     }
+
+    /// Check if scene carries the huestatus appdata marker
+    ///
+    /// This is the only test of ownership: names can't be trusted, since a
+    /// hand-made scene may use a `huestatus-` name.
+    pub fn has_huestatus_appdata(&self) -> bool {
+        self.appdata
+            .as_ref()
+            .and_then(|appdata| appdata.get("data"))
+            .and_then(|data| data.as_str())
+            == Some(HUESTATUS_APPDATA)
+    }
+
+    /// Average hue and saturation of the colored lightstates, if any
+    ///
+    /// Hue is averaged on the color wheel so red shades either side of 0 stay red.
+    pub fn average_hue_sat(&self) -> Option<(u16, u8)> {
+        let colors: Vec<(u16, u8)> = self
+            .lightstates
+            .as_ref()?
+            .values()
+            .filter_map(|state| Some((state.hue?, state.sat?)))
+            .collect();
+
+        if colors.is_empty() {
+            return None;
+        }
+
+        let count = colors.len() as f64;
+        let (x, y) = colors.iter().fold((0.0, 0.0), |(x, y), (hue, _)| {
+            let angle = f64::from(*hue) / 65536.0 * std::f64::consts::TAU;
+            (x + angle.cos(), y + angle.sin())
+        });
+        let angle = y.atan2(x).rem_euclid(std::f64::consts::TAU);
+        let hue = (angle / std::f64::consts::TAU * 65536.0).round() as u32 % 65536;
+        let sat = colors.iter().map(|(_, sat)| f64::from(*sat)).sum::<f64>() / count;
+
+        Some((hue as u16, sat.round() as u8))
+    }
 }
 
 impl CreateSceneRequest {
+    /// Raise light brightness below `floor` up to it, returning a warning per adjusted light
+    pub fn apply_brightness_floor(&mut self, floor: u8) -> Vec<String> {
+        let mut adjusted: Vec<(&String, u8)> = self
+            .lightstates
+            .iter_mut()
+            .filter_map(|(light_id, state)| match state.bri {
+                Some(bri) if bri < floor => {
+                    state.bri = Some(floor);
+                    Some((light_id, bri))
+                }
+                _ => None,
+            })
+            .collect();
+        adjusted.sort();
+
+        adjusted
+            .into_iter()
+            .map(|(light_id, bri)| {
+                format!(
+                    "Scene '{}': light {light_id} brightness {bri} raised to minimum {floor}",
+                    self.name
+                )
+            })
+            .collect()
+    }
+
     /// Create a new scene request for success status (green)
     pub fn new_success_scene(name: String, lights: Vec<String>) -> Self {
         let mut lightstates = HashMap::new();
@@ -593,6 +1121,7 @@ impl CreateSceneRequest {
             lights,
             recycle: true,
             lightstates,
+            appdata: Some(huestatus_appdata()),
         }
     }
 
@@ -624,6 +1153,7 @@ impl CreateSceneRequest {
             lights,
             recycle: true,
             lightstates,
+            appdata: Some(huestatus_appdata()),
         }
     }
 
@@ -655,6 +1185,7 @@ impl CreateSceneRequest {
             lights,
             recycle: true,
             lightstates,
+            appdata: Some(huestatus_appdata()),
         }
     }
 
@@ -666,39 +1197,169 @@ impl CreateSceneRequest {
             });
         }
 
-        if self.lights.is_empty() {
-            return Err(HueStatusError::InvalidSceneData {
-                reason: "Scene must have at least one light".to_string(),
-            });
-        }
+        validate_scene_lights(&self.lights, &self.lightstates)
+    }
+}
 
-        if self.lightstates.is_empty() {
-            return Err(HueStatusError::InvalidSceneData {
-                reason: "Scene must have light states".to_string(),
-            });
+impl UpdateSceneRequest {
+    /// Create an update request with the given lights and their states
+    pub fn new(lights: Vec<String>, lightstates: HashMap<String, LightState>) -> Self {
+        Self {
+            lights,
+            lightstates,
         }
+    }
 
-        // Validate that all lights have corresponding lightstates
-        for light_id in &self.lights {
-            if !self.lightstates.contains_key(light_id) {
-                return Err(HueStatusError::InvalidSceneData {
-                    reason: format!("Light {light_id} has no corresponding light state"),
-                });
-            }
+    /// Validate scene update request
+    pub fn validate(&self) -> Result<()> {
+        validate_scene_lights(&self.lights, &self.lightstates)
+    }
+}
+
+impl From<CreateSceneRequest> for UpdateSceneRequest {
+    fn from(request: CreateSceneRequest) -> Self {
+        Self::new(request.lights, request.lightstates)
+    }
+}
+
+/// Recreate a bridge scene, e.g. to export it to a file
+///
+/// Read-only state (`reachable`, `mode`) and the appdata marker are dropped, so
+/// the copy is created as the user's own scene.
+impl From<Scene> for CreateSceneRequest {
+    fn from(scene: Scene) -> Self {
+        let lightstates = scene
+            .lightstates
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(light_id, mut state)| {
+                state.reachable = None;
+                state.mode = None;
+                (light_id, state)
+            })
+            .collect();
+
+        Self {
+            name: scene.name,
+            lights: scene.lights,
+            recycle: scene.recycle,
+            lightstates,
+            appdata: None,
         }
+    }
+}
 
-        Ok(())
+/// Check that a scene has lights and a light state for each of them
+fn validate_scene_lights(
+    lights: &[String],
+    lightstates: &HashMap<String, LightState>,
+) -> Result<()> {
+    if lights.is_empty() {
+        return Err(HueStatusError::InvalidSceneData {
+            reason: "Scene must have at least one light".to_string(),
+        });
+    }
+
+    if lightstates.is_empty() {
+        return Err(HueStatusError::InvalidSceneData {
+            reason: "Scene must have light states".to_string(),
+        });
+    }
+
+    // Validate that all lights have corresponding lightstates
+    for light_id in lights {
+        if !lightstates.contains_key(light_id) {
+            return Err(HueStatusError::InvalidSceneData {
+                reason: format!("Light {light_id} has no corresponding light state"),
+            });
+        }
     }
+
+    Ok(())
 }
 
 impl SceneActionRequest {
     /// Create a new scene action request
     pub fn new(scene_id: String) -> Self {
-        Self { scene: scene_id }
+        Self {
+            scene: scene_id,
+            transitiontime: None,
+        }
+    }
+
+    /// Set the transition time in deciseconds
+    pub fn with_transition_time(mut self, transition_time: Option<u16>) -> Self {
+        self.transitiontime = transition_time;
+        self
+    }
+}
+
+impl LightStateUpdate {
+    /// Create an update that turns lights off
+    pub fn off() -> Self {
+        Self {
+            on: Some(false),
+            ..Self::default()
+        }
+    }
+
+    /// Create an update that changes brightness relative to the current level
+    pub fn brightness_step(step: i16) -> Self {
+        Self {
+            bri_inc: Some(step),
+            ..Self::default()
+        }
+    }
+
+    /// Create an update that starts an alert effect
+    pub fn alert(mode: AlertMode) -> Self {
+        Self {
+            alert: Some(mode.as_str().to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the transition time in deciseconds
+    pub fn with_transition_time(mut self, transition_time: Option<u16>) -> Self {
+        self.transitiontime = transition_time;
+        self
     }
 }
 
 impl LightState {
+    /// Describe what a light in this state shows, e.g. "on, 80%, orange"
+    ///
+    /// The color name follows the color mode: a white temperature for
+    /// `ct`, otherwise the hue and saturation (which the bridge also reports
+    /// for `xy`). Lights without color state show only the brightness.
+    pub fn color_summary(&self) -> String {
+        if !self.on {
+            return "off".to_string();
+        }
+
+        let mut parts = vec!["on".to_string()];
+        if let Some(bri) = self.bri {
+            parts.push(format!("{}%", bri_to_percent(bri)));
+        }
+
+        let color = match (self.colormode.as_deref(), self.ct) {
+            (Some("ct"), Some(ct)) => Some(format!(
+                "{} ({}K)",
+                color_temperature_name(ct),
+                mireds_to_kelvin(ct)
+            )),
+            _ => match (self.hue, self.sat) {
+                (Some(hue), Some(sat)) => Some(hue_sat_color_name(hue, sat).to_string()),
+                _ => self.ct.map(|ct| {
+                    format!("{} ({}K)", color_temperature_name(ct), mireds_to_kelvin(ct))
+                }),
+            },
+        };
+        parts.extend(color);
+
+        parts.join(", ")
+    }
+
     /// Create a new light state for success status (green)
     pub fn new_success_state() -> Self {
         Self {
@@ -849,6 +1510,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_update_scene_request_validation() {
+        let lights = vec!["1".to_string(), "2".to_string()];
+        let request: UpdateSceneRequest =
+            CreateSceneRequest::new_success_scene("test-scene".to_string(), lights).into();
+        assert!(request.validate().is_ok());
+
+        let mut missing_state = request.clone();
+        missing_state.lightstates.remove("2");
+        assert!(missing_state.validate().is_err());
+
+        let empty = UpdateSceneRequest::new(Vec::new(), HashMap::new());
+        assert!(empty.validate().is_err());
+    }
+
     #[test]
     fn test_light_state_validation() {
         let mut state = LightState::new_success_state();
@@ -876,6 +1552,317 @@ mod tests {
         assert!(empty_scene.validate().is_err());
     }
 
+    #[test]
+    fn test_scene_action_request_transition_time() {
+        let action = SceneActionRequest::new("abc".to_string());
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(json, serde_json::json!({ "scene": "abc" }));
+
+        let action = action.with_transition_time(Some(0));
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "scene": "abc", "transitiontime": 0 })
+        );
+    }
+
+    #[test]
+    fn test_hue_sat_to_rgb() {
+        assert_eq!(hue_sat_to_rgb(0, 254), [255, 0, 0]);
+        assert_eq!(hue_sat_to_rgb(21845, 254), [0, 255, 0]);
+        assert_eq!(hue_sat_to_rgb(43690, 254), [0, 0, 255]);
+        assert_eq!(hue_sat_to_rgb(12345, 0), [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_color_names() {
+        assert_eq!(hue_sat_color_name(0, 254), "red");
+        assert_eq!(hue_sat_color_name(5461, 254), "orange");
+        assert_eq!(hue_sat_color_name(21845, 254), "green");
+        assert_eq!(hue_sat_color_name(43690, 254), "blue");
+        assert_eq!(hue_sat_color_name(49151, 254), "purple");
+        assert_eq!(hue_sat_color_name(65000, 254), "red");
+        assert_eq!(hue_sat_color_name(43690, 10), "white");
+
+        assert_eq!(color_temperature_name(370), "warm white");
+        assert_eq!(color_temperature_name(250), "neutral white");
+        assert_eq!(color_temperature_name(153), "cool white");
+        assert_eq!(bri_to_percent(254), 100);
+        assert_eq!(bri_to_percent(127), 50);
+    }
+
+    #[test]
+    fn test_color_distance() {
+        assert_eq!(color_distance((0, 254), (0, 254)), 0.0);
+        assert!((color_distance((0, 254), (21845, 254)) - 81.6).abs() < 0.1);
+        assert!(color_distance((0, 254), (1000, 254)) < 10.0);
+        assert_eq!(color_distance((0, 0), (43690, 254)).round(), 82.0);
+    }
+
+    #[test]
+    fn test_kelvin_to_mireds() {
+        assert_eq!(kelvin_to_mireds(2000), 500);
+        assert_eq!(kelvin_to_mireds(6500), 153);
+
+        let range = ColorTemperatureCapability { min: 153, max: 454 };
+        assert_eq!(range.clamp(kelvin_to_mireds(2000)), 454);
+        assert_eq!(range.clamp(kelvin_to_mireds(4000)), 250);
+    }
+
+    #[test]
+    fn test_percent_to_bri() {
+        assert_eq!(percent_to_bri(0), 0);
+        assert_eq!(percent_to_bri(1), 1);
+        assert_eq!(percent_to_bri(50), 126);
+        assert_eq!(percent_to_bri(100), 254);
+        assert_eq!(percent_to_bri(150), 254);
+
+        assert_eq!(parse_brightness("180"), Ok(180));
+        assert_eq!(parse_brightness("100%"), Ok(254));
+        assert_eq!(parse_brightness(" 1 %"), Ok(1));
+        assert!(parse_brightness("255").is_err());
+        assert!(parse_brightness("101%").is_err());
+        assert!(parse_brightness("bright").is_err());
+    }
+
+    #[test]
+    fn test_brightness_change() {
+        assert_eq!("+10%".parse(), Ok(BrightnessChange::Percent(10)));
+        assert_eq!("-20".parse(), Ok(BrightnessChange::Raw(-20)));
+        assert!("10".parse::<BrightnessChange>().is_err());
+        assert!("+101%".parse::<BrightnessChange>().is_err());
+        assert!("-300".parse::<BrightnessChange>().is_err());
+
+        assert_eq!(BrightnessChange::Percent(10).apply(100), 125);
+        assert_eq!(BrightnessChange::Raw(-20).apply(100), 80);
+        assert_eq!(BrightnessChange::Percent(-100).apply(100), 1);
+        assert_eq!(BrightnessChange::Raw(200).apply(100), 254);
+    }
+
+    #[test]
+    fn test_scene_average_hue_sat() {
+        let mut request = CreateSceneRequest::new_failure_scene(
+            "huestatus-failure".to_string(),
+            vec!["1".to_string(), "2".to_string()],
+        );
+        // Reds on both sides of 0 must average to red, not cyan
+        if let Some(state) = request.lightstates.get_mut("2") {
+            state.hue = Some(65000);
+            state.sat = Some(200);
+        }
+        let scene = Scene {
+            name: request.name,
+            lights: request.lights,
+            owner: "owner".to_string(),
+            recycle: false,
+            locked: false,
+            appdata: None,
+            picture: None,
+            image: None,
+            lastupdated: "2024-01-01T00:00:00".to_string(),
+            version: 2,
+            lightstates: Some(request.lightstates),
+        };
+
+        let (hue, sat) = scene.average_hue_sat().unwrap();
+        assert!(!(1000..=64500).contains(&hue));
+        assert_eq!(sat, 227);
+
+        let summary = Scene {
+            lightstates: None,
+            ..scene
+        };
+        assert_eq!(summary.average_hue_sat(), None);
+    }
+
+    #[test]
+    fn test_create_scene_request_from_scene() {
+        let mut lightstates = CreateSceneRequest::new_success_scene(
+            "huestatus-success".to_string(),
+            vec!["1".to_string()],
+        )
+        .lightstates;
+        if let Some(state) = lightstates.get_mut("1") {
+            state.reachable = Some(true);
+        }
+        let scene = Scene {
+            name: "Backup".to_string(),
+            lights: vec!["1".to_string()],
+            owner: "owner".to_string(),
+            recycle: false,
+            locked: false,
+            appdata: Some(huestatus_appdata()),
+            picture: None,
+            image: None,
+            lastupdated: "2024-01-01T00:00:00".to_string(),
+            version: 2,
+            lightstates: Some(lightstates),
+        };
+
+        let request = CreateSceneRequest::from(scene);
+        assert_eq!(request.name, "Backup");
+        assert!(!request.recycle);
+        assert!(request.appdata.is_none());
+        assert_eq!(request.lightstates["1"].reachable, None);
+        assert!(request.validate().is_ok());
+
+        // Exports are read back by `scenes import`
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: CreateSceneRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.lights, request.lights);
+    }
+
+    #[test]
+    fn test_apply_brightness_floor() {
+        let mut request = CreateSceneRequest::new_success_scene(
+            "huestatus-success".to_string(),
+            vec!["1".to_string(), "2".to_string()],
+        );
+        if let Some(state) = request.lightstates.get_mut("2") {
+            state.bri = Some(1);
+        }
+
+        let warnings = request.apply_brightness_floor(30);
+        assert_eq!(
+            warnings,
+            vec!["Scene 'huestatus-success': light 2 brightness 1 raised to minimum 30"]
+        );
+        assert_eq!(request.lightstates["1"].bri, Some(254));
+        assert_eq!(request.lightstates["2"].bri, Some(30));
+    }
+
+    #[test]
+    fn test_light_state_update_serialization() {
+        let update = LightStateUpdate::off().with_transition_time(Some(4));
+        let json = serde_json::to_value(&update).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "on": false, "transitiontime": 4 })
+        );
+
+        let dip = serde_json::to_value(LightStateUpdate::brightness_step(-150)).unwrap();
+        assert_eq!(dip, serde_json::json!({ "bri_inc": -150 }));
+
+        let empty = serde_json::to_value(LightStateUpdate::default()).unwrap();
+        assert_eq!(empty, serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_alert_mode() {
+        assert_eq!("LSelect".parse(), Ok(AlertMode::LSelect));
+        assert_eq!("none".parse(), Ok(AlertMode::None));
+        assert!("blink".parse::<AlertMode>().is_err());
+
+        let update = serde_json::to_value(LightStateUpdate::alert(AlertMode::Select)).unwrap();
+        assert_eq!(update, serde_json::json!({ "alert": "select" }));
+    }
+
+    #[test]
+    fn test_light_with_sparse_record() {
+        let json = serde_json::json!({
+            "name": "Third-party bulb",
+            "state": { "on": true, "bri": 128, "reachable": true },
+            "type": "Dimmable light",
+            "config": { "startup": { "mode": "safety", "configured": true } }
+        });
+
+        let light: Light = serde_json::from_value(json).unwrap();
+        assert_eq!(light.name, "Third-party bulb");
+        assert!(light.modelid.is_empty());
+        assert!(light.manufacturername.is_empty());
+        assert!(light.config.unwrap().archetype.is_empty());
+    }
+
+    #[test]
+    fn test_effective_color_summary() {
+        let light = |state: serde_json::Value| -> Light {
+            serde_json::from_value(serde_json::json!({
+                "name": "Desk",
+                "state": state,
+                "type": "Extended color light"
+            }))
+            .unwrap()
+        };
+
+        let color = light(serde_json::json!({
+            "on": true, "bri": 203, "hue": 5461, "sat": 254, "colormode": "hs"
+        }));
+        assert_eq!(color.effective_color_summary(), "on, 80%, orange");
+
+        let white = light(serde_json::json!({
+            "on": true, "bri": 254, "hue": 8402, "sat": 140, "ct": 366, "colormode": "ct"
+        }));
+        assert_eq!(
+            white.effective_color_summary(),
+            "on, 100%, warm white (2732K)"
+        );
+
+        let dimmable = light(serde_json::json!({ "on": true, "bri": 127 }));
+        assert_eq!(dimmable.effective_color_summary(), "on, 50%");
+        assert_eq!(
+            light(serde_json::json!({ "on": false, "bri": 127 })).effective_color_summary(),
+            "off"
+        );
+    }
+
+    #[test]
+    fn test_bridge_configuration_with_missing_fields() {
+        let json = serde_json::json!({
+            "name": "Philips hue",
+            "bridgeid": "001788FFFE000000",
+            "modelid": "BSB002",
+            "swversion": "1967054020",
+            "apiversion": "1.67.0",
+            "swupdate2": { "checkforupdate": false },
+            "newfirmwarefield": { "enabled": true }
+        });
+
+        let config: BridgeConfiguration = serde_json::from_value(json).unwrap();
+        assert_eq!(config.bridgeid, "001788FFFE000000");
+        assert_eq!(config.apiversion, "1.67.0");
+        assert!(config.mac.is_empty());
+        assert!(config.whitelist.is_empty());
+        assert!(!config.swupdate2.checkforupdate);
+    }
+
+    #[test]
+    fn test_find_scene_by_name() {
+        let scene = |name: &str| Scene {
+            name: name.to_string(),
+            lights: vec!["1".to_string()],
+            owner: "owner".to_string(),
+            recycle: false,
+            locked: false,
+            appdata: None,
+            picture: None,
+            image: None,
+            lastupdated: "2024-01-01T00:00:00".to_string(),
+            version: 2,
+            lightstates: None,
+        };
+        let scenes = HashMap::from([
+            ("a".to_string(), scene("Build Green")),
+            ("b".to_string(), scene("Relax")),
+            ("c".to_string(), scene("relax")),
+        ]);
+
+        let (id, found) = find_scene_by_name(&scenes, "build green").unwrap();
+        assert_eq!(id, "a");
+        assert_eq!(found.name, "Build Green");
+
+        assert!(matches!(
+            find_scene_by_name(&scenes, "missing"),
+            Err(HueStatusError::SceneNotFound { .. })
+        ));
+
+        match find_scene_by_name(&scenes, "RELAX") {
+            Err(HueStatusError::AmbiguousSceneName { candidates, .. }) => {
+                assert_eq!(candidates, "Relax (b), relax (c)");
+            }
+            other => panic!("expected ambiguous name error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_hue_error_types() {
         let auth_error = HueError {
@@ -896,5 +1883,71 @@ mod tests {
         };
         assert!(button_error.is_link_button_error());
         assert!(button_error.is_recoverable());
+
+        let missing_error = HueError {
+            error: HueErrorDetails {
+                error_type: 3,
+                address: "/scenes/abc".to_string(),
+                description: "resource, /scenes/abc, not available".to_string(),
+            },
+        };
+        assert!(matches!(
+            HueStatusError::from(missing_error),
+            HueStatusError::ResourceNotAvailable { .. }
+        ));
+    }
+
+    #[test]
+    fn test_hue_error_array_into_result() {
+        let error = |error_type, address: &str, description: &str| HueError {
+            error: HueErrorDetails {
+                error_type,
+                address: address.to_string(),
+                description: description.to_string(),
+            },
+        };
+
+        let single: HueResponse<String> =
+            HueResponse::ErrorArray(vec![error(1, "/", "unauthorized user")]);
+        assert!(matches!(
+            single.into_result(),
+            Err(HueStatusError::AuthenticationFailed)
+        ));
+
+        let multiple: HueResponse<String> = HueResponse::ErrorArray(vec![
+            error(201, "/lights/1/state/hue", "not modifiable"),
+            error(201, "/lights/2/state/hue", "not modifiable"),
+        ]);
+        match multiple.into_result() {
+            Err(HueStatusError::ApiError { message }) => {
+                assert!(message.starts_with("2 API errors: "));
+                assert!(message.contains("/lights/1/state/hue"));
+                assert!(message.contains("/lights/2/state/hue"));
+            }
+            other => panic!("expected a combined ApiError, got {other:?}"),
+        }
+
+        // An auth error among per-light errors keeps its classification
+        let with_auth: HueResponse<String> = HueResponse::ErrorArray(vec![
+            error(201, "/lights/1/state/hue", "not modifiable"),
+            error(1, "/lights/2/state", "unauthorized user"),
+            error(101, "/", "link button not pressed"),
+        ]);
+        assert!(matches!(
+            with_auth.into_result(),
+            Err(HueStatusError::AuthenticationFailed)
+        ));
+
+        let with_missing: HueResponse<String> = HueResponse::ErrorArray(vec![
+            error(201, "/lights/1/state/hue", "not modifiable"),
+            error(3, "/scenes/abc", "resource, /scenes/abc, not available"),
+        ]);
+        match with_missing.into_result() {
+            Err(HueStatusError::ResourceNotAvailable { resource }) => {
+                assert!(resource.starts_with("resource, /scenes/abc, not available"));
+                assert!(resource.contains("also: /lights/1/state/hue: not modifiable"));
+            }
+            other => panic!("expected ResourceNotAvailable, got {other:?}"),
+        }
     }
 }
@@ -0,0 +1,219 @@
+use crate::config::{validate_config, AdvancedSettings, Config, Settings};
+use crate::error::{HueStatusError, Result};
+
+/// Fluent builder for assembling a validated configuration
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    bridge_ip: Option<String>,
+    application_key: Option<String>,
+    success_scene: Option<(String, String)>,
+    failure_scene: Option<(String, String)>,
+    auto_created: bool,
+    settings: Settings,
+    advanced: AdvancedSettings,
+}
+
+impl ConfigBuilder {
+    /// Create a new builder with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bridge IP address
+    pub fn bridge_ip(mut self, ip: impl Into<String>) -> Self {
+        self.bridge_ip = Some(ip.into());
+        self
+    }
+
+    /// Set the application key
+    pub fn app_key(mut self, key: impl Into<String>) -> Self {
+        self.application_key = Some(key.into());
+        self
+    }
+
+    /// Set the success scene ID and name
+    pub fn success_scene(mut self, id: impl Into<String>, name: impl Into<String>) -> Self {
+        self.success_scene = Some((id.into(), name.into()));
+        self
+    }
+
+    /// Set the failure scene ID and name
+    pub fn failure_scene(mut self, id: impl Into<String>, name: impl Into<String>) -> Self {
+        self.failure_scene = Some((id.into(), name.into()));
+        self
+    }
+
+    /// Mark the scenes as created by huestatus
+    pub fn auto_created(mut self, auto_created: bool) -> Self {
+        self.auto_created = auto_created;
+        self
+    }
+
+    /// Set the API timeout in seconds
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.settings.timeout_seconds = seconds;
+        self
+    }
+
+    /// Set the number of retry attempts
+    pub fn retry_attempts(mut self, attempts: usize) -> Self {
+        self.settings.retry_attempts = attempts;
+        self
+    }
+
+    /// Set the retry delay in seconds
+    pub fn retry_delay(mut self, seconds: u64) -> Self {
+        self.settings.retry_delay_seconds = seconds;
+        self
+    }
+
+    /// Enable verbose logging
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.settings.verbose_logging = verbose;
+        self
+    }
+
+    /// Enable quiet mode
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.settings.quiet_mode = quiet;
+        self
+    }
+
+    /// Set the scene transition time in deciseconds
+    pub fn transition_time(mut self, deciseconds: u16) -> Self {
+        self.settings.transition_time = Some(deciseconds);
+        self
+    }
+
+    /// Set the group the status scenes are recalled on
+    pub fn recall_group(mut self, group_id: impl Into<String>) -> Self {
+        self.settings.recall_group_id = group_id.into();
+        self
+    }
+
+    /// Briefly dim the lights after showing a status
+    pub fn confirm_flash(mut self, confirm_flash: bool) -> Self {
+        self.settings.confirm_flash = confirm_flash;
+        self
+    }
+
+    /// Set a custom HTTP user agent
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.settings.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Skip TLS certificate verification for a local bridge with a self-signed cert
+    pub fn insecure(mut self, insecure: bool) -> Self {
+        self.settings.insecure = insecure;
+        self
+    }
+
+    /// Set the lowest brightness used in status scenes
+    pub fn min_brightness(mut self, min_brightness: u8) -> Self {
+        self.settings.min_brightness = min_brightness;
+        self
+    }
+
+    /// Replace all application settings
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Replace all advanced settings
+    pub fn advanced(mut self, advanced: AdvancedSettings) -> Self {
+        self.advanced = advanced;
+        self
+    }
+
+    /// Build and validate the configuration
+    pub fn build(self) -> Result<Config> {
+        let bridge_ip = required(self.bridge_ip, "bridge IP")?;
+        let application_key = required(self.application_key, "application key")?;
+        let (success_id, success_name) = required(self.success_scene, "success scene")?;
+        let (failure_id, failure_name) = required(self.failure_scene, "failure scene")?;
+
+        let mut config = Config::new(
+            bridge_ip,
+            application_key,
+            Config::create_scene_config(success_id, success_name, self.auto_created),
+            Config::create_scene_config(failure_id, failure_name, self.auto_created),
+        );
+        config.settings = self.settings;
+        config.advanced = self.advanced;
+
+        validate_config(&config)?;
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<ConfigBuilder> for Config {
+    type Error = HueStatusError;
+
+    fn try_from(builder: ConfigBuilder) -> Result<Self> {
+        builder.build()
+    }
+}
+
+impl Config {
+    /// Start building a configuration
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+}
+
+/// Require a builder field to be set
+fn required<T>(value: Option<T>, field: &str) -> Result<T> {
+    value.ok_or_else(|| HueStatusError::InvalidConfig {
+        reason: format!("Missing {field}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete_builder() -> ConfigBuilder {
+        Config::builder()
+            .bridge_ip("192.168.1.100")
+            .app_key("test-application-key-with-proper-length")
+            .success_scene("success-id", "huestatus-success")
+            .failure_scene("failure-id", "huestatus-failure")
+    }
+
+    #[test]
+    fn test_builder_builds_valid_config() {
+        let config = complete_builder()
+            .timeout(20)
+            .retry_attempts(5)
+            .auto_created(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.bridge.ip, "192.168.1.100");
+        assert_eq!(config.scenes.success.id, "success-id");
+        assert_eq!(config.scenes.failure.name, "huestatus-failure");
+        assert!(config.scenes.failure.auto_created);
+        assert_eq!(config.settings.timeout_seconds, 20);
+        assert_eq!(config.settings.retry_attempts, 5);
+    }
+
+    #[test]
+    fn test_builder_requires_fields() {
+        let result = Config::builder().bridge_ip("192.168.1.100").build();
+        assert!(
+            matches!(result, Err(HueStatusError::InvalidConfig { reason }) if reason.contains("application key"))
+        );
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        let result = complete_builder().timeout(0).build();
+        assert!(result.is_err());
+
+        let result = Config::try_from(complete_builder().bridge_ip("not-an-ip"));
+        assert!(result.is_err());
+    }
+}
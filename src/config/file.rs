@@ -1,8 +1,12 @@
 use crate::config::Config;
 use crate::error::{HueStatusError, Result};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
+/// Path value that selects reading the configuration from stdin
+pub const STDIN_CONFIG_PATH: &str = "-";
+
 /// Load configuration from file
 pub fn load_config(path: &Path) -> Result<Config> {
     // Check if file exists
@@ -19,8 +23,58 @@ pub fn load_config(path: &Path) -> Result<Config> {
         _ => HueStatusError::IoError { source: e },
     })?;
 
-    // Parse JSON
-    let mut config: Config = serde_json::from_str(&content).map_err(|e| {
+    let mut config = parse_config(&content)?;
+
+    // Migrate if needed
+    if config.version.needs_migration() {
+        config.migrate()?;
+        // Save migrated configuration
+        save_config(&config, path)?;
+    }
+
+    // Validate configuration
+    config.validate()?;
+
+    Ok(config)
+}
+
+/// Load configuration from a reader without touching the filesystem
+pub fn load_config_from_reader<R: Read>(mut reader: R) -> Result<Config> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|e| HueStatusError::IoError { source: e })?;
+
+    if content.trim().is_empty() {
+        return Err(HueStatusError::InvalidConfig {
+            reason: "Configuration input is empty".to_string(),
+        });
+    }
+
+    let mut config = parse_config(&content)?;
+
+    // Migrate in memory only; there is no file to write back to
+    if config.version.needs_migration() {
+        config.migrate()?;
+    }
+
+    config.validate()?;
+
+    Ok(config)
+}
+
+/// Load configuration from stdin
+pub fn load_config_from_stdin() -> Result<Config> {
+    load_config_from_reader(std::io::stdin().lock())
+}
+
+/// Parse configuration JSON and check version compatibility
+///
+/// Configs written before versioning have no `version` field; they are read
+/// as 1.0 so the whole migration chain runs, instead of `ConfigVersion`'s
+/// default (the current version) skipping it.
+pub(crate) fn parse_config(content: &str) -> Result<Config> {
+    let json_error = |e: serde_json::Error| {
         if e.is_syntax() {
             HueStatusError::ConfigCorrupted
         } else {
@@ -28,26 +82,22 @@ pub fn load_config(path: &Path) -> Result<Config> {
                 reason: format!("JSON parsing error: {e}"),
             }
         }
-    })?;
+    };
+
+    // Parse JSON
+    let mut value: serde_json::Value = serde_json::from_str(content).map_err(json_error)?;
+    if let Some(object) = value.as_object_mut() {
+        object
+            .entry("version")
+            .or_insert_with(|| serde_json::json!("1.0"));
+    }
+    let config: Config = serde_json::from_value(value).map_err(json_error)?;
 
     // Check version compatibility
     if !config.version.is_compatible() {
         return Err(HueStatusError::ConfigVersionIncompatible);
     }
 
-    // Migrate if needed
-    if config.version.needs_migration() {
-        config.migrate()?;
-        // Save migrated configuration
-        save_config(&config, path)?;
-    }
-
-    // Validate configuration
-    config.validate()?;
-
-    // Apply environment variable overrides
-    config.apply_env_overrides()?;
-
     Ok(config)
 }
 
@@ -118,8 +168,13 @@ pub fn backup_config(path: &Path) -> Result<()> {
 }
 
 /// Load configuration from custom path or default location
+///
+/// A custom path of `-` reads the configuration from stdin instead.
 pub fn load_config_from_path_or_default(custom_path: Option<&Path>) -> Result<Config> {
     let path = if let Some(custom) = custom_path {
+        if custom.as_os_str() == STDIN_CONFIG_PATH {
+            return load_config_from_stdin();
+        }
         custom.to_path_buf()
     } else {
         Config::get_config_file_path()?
@@ -249,7 +304,7 @@ pub fn is_config_writable(path: &Path) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Config;
+    use crate::config::{Config, ConfigVersion};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -282,6 +337,108 @@ mod tests {
         assert_eq!(config.scenes.failure.id, loaded.scenes.failure.id);
     }
 
+    #[test]
+    fn test_load_config_from_reader() {
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config(
+                "success-id".to_string(),
+                "success-scene".to_string(),
+                true,
+            ),
+            Config::create_scene_config(
+                "failure-id".to_string(),
+                "failure-scene".to_string(),
+                true,
+            ),
+        );
+        let json = serde_json::to_string(&config).unwrap();
+
+        let loaded = load_config_from_reader(json.as_bytes()).unwrap();
+        assert_eq!(loaded.scenes.success.id, "success-id");
+
+        let result = load_config_from_reader("  \n".as_bytes());
+        assert!(matches!(result, Err(HueStatusError::InvalidConfig { .. })));
+
+        let result = load_config_from_reader("{not json".as_bytes());
+        assert!(matches!(result, Err(HueStatusError::ConfigCorrupted)));
+    }
+
+    #[test]
+    fn test_unknown_settings_survive_resave() {
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("success-id".to_string(), "success".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "failure".to_string(), true),
+        );
+        let mut json = serde_json::to_value(&config).unwrap();
+        json["settings"]["future_setting"] = serde_json::json!({"enabled": true});
+        json["advanced"]["future_tuning"] = serde_json::json!(42);
+
+        let loaded = load_config_from_reader(json.to_string().as_bytes()).unwrap();
+        assert!(loaded.settings.extra.contains_key("future_setting"));
+
+        let temp_file = NamedTempFile::new().unwrap();
+        save_config(&loaded, temp_file.path()).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(temp_file.path()).unwrap()).unwrap();
+
+        assert_eq!(saved["settings"]["future_setting"]["enabled"], true);
+        assert_eq!(saved["advanced"]["future_tuning"], 42);
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated() {
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("success-id".to_string(), "success".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "failure".to_string(), true),
+        );
+        let mut json = serde_json::to_value(&config).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("version");
+        object.remove("advanced");
+        json["settings"]["retry_attempts"] = serde_json::json!(0);
+        json["settings"]["retry_delay_seconds"] = serde_json::json!(0);
+
+        assert_eq!(
+            parse_config(&json.to_string()).unwrap().version,
+            ConfigVersion::V1_0
+        );
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), json.to_string()).unwrap();
+        let loaded = load_config(temp_file.path()).unwrap();
+        assert_eq!(loaded.version, ConfigVersion::V1_2);
+        assert_eq!(loaded.settings.retry_attempts, 3);
+        assert_eq!(loaded.settings.retry_delay_seconds, 1);
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(temp_file.path()).unwrap()).unwrap();
+        assert_eq!(saved["version"], "1.2");
+    }
+
+    #[test]
+    fn test_two_scene_config_loads_unchanged() {
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("success-id".to_string(), "success".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "failure".to_string(), true),
+        );
+        let mut json = serde_json::to_value(&config).unwrap();
+        json.as_object_mut().unwrap().remove("colors");
+        assert!(json["scenes"].get("running").is_none());
+
+        let loaded = load_config_from_reader(json.to_string().as_bytes()).unwrap();
+        assert!(loaded.scenes.running.is_none());
+        assert_eq!(loaded.colors.running.hue, 7282);
+        assert!(loaded.validate().is_ok());
+    }
+
     #[test]
     fn test_config_not_found() {
         let result = load_config(Path::new("/nonexistent/path/config.json"));
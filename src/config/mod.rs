@@ -1,10 +1,14 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
+pub mod builder;
 pub mod file;
 pub mod validation;
 
+pub use builder::*;
 pub use file::*;
 pub use validation::*;
 
@@ -14,6 +18,75 @@ const MAX_PATH_LENGTH: usize = 4096;
 /// Fallback configuration directory name
 const FALLBACK_CONFIG_NAME: &str = "huestatus-config";
 
+/// Environment variables required to build a configuration without a file
+pub const REQUIRED_ENV_VARS: [&str; 4] = [
+    "HUESTATUS_BRIDGE_IP",
+    "HUESTATUS_APP_KEY",
+    "HUESTATUS_SUCCESS_SCENE_ID",
+    "HUESTATUS_FAILURE_SCENE_ID",
+];
+
+/// Dotted key paths accepted by `Config::get_value` and `Config::set_value`
+pub const CONFIG_KEYS: [&str; 34] = [
+    "bridge.ip",
+    "scenes.success.id",
+    "scenes.success.name",
+    "scenes.failure.id",
+    "scenes.failure.name",
+    "settings.timeout_seconds",
+    "settings.retry_attempts",
+    "settings.retry_delay_seconds",
+    "settings.verbose_logging",
+    "settings.quiet_mode",
+    "settings.auto_refresh_scenes",
+    "settings.validate_scenes_on_startup",
+    "settings.transition_time",
+    "settings.recall_group_id",
+    "settings.confirm_flash",
+    "settings.distinct_scene_lights",
+    "settings.user_agent",
+    "settings.discovery_url",
+    "settings.insecure",
+    "settings.min_brightness",
+    "settings.min_color_distance",
+    "advanced.connection_pool_size",
+    "advanced.cache_duration_minutes",
+    "advanced.scene_validation_interval_hours",
+    "advanced.max_lights_per_scene",
+    "colors.success.hue",
+    "colors.success.saturation",
+    "colors.success.brightness",
+    "colors.failure.hue",
+    "colors.failure.saturation",
+    "colors.failure.brightness",
+    "colors.running.hue",
+    "colors.running.saturation",
+    "colors.running.brightness",
+];
+
+/// Environment variable overriding the bridge discovery service URL
+pub const DISCOVERY_URL_ENV_VAR: &str = "HUESTATUS_DISCOVERY_URL";
+
+/// Environment variable selecting a per-project configuration file
+pub const PROJECT_ENV_VAR: &str = "HUESTATUS_PROJECT";
+
+/// A setting that differs from its default, as listed by `config show --diff`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigDifference {
+    /// Dotted key path, e.g. `settings.timeout_seconds`
+    pub field: String,
+    /// Value in the loaded configuration
+    pub current: serde_json::Value,
+    /// Default value (`null` for keys huestatus doesn't know, e.g. from a newer version)
+    pub default: serde_json::Value,
+}
+
+static CURRENT_PROJECT: OnceLock<Option<String>> = OnceLock::new();
+
+/// Serializes tests that read or modify process environment variables
+#[cfg(test)]
+pub(crate) static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 /// Configuration file version for future compatibility
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub enum ConfigVersion {
@@ -75,9 +148,15 @@ pub struct SceneConfig {
     pub name: String,
     /// Whether scene was auto-created by huestatus
     pub auto_created: bool,
+    /// Whether scene was created by `scenes import`, so `scenes refresh` keeps it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub imported: bool,
     /// Last time scene was validated
     #[serde(default)]
     pub last_validated: Option<DateTime<Utc>>,
+    /// Bridge `lastupdated` value when huestatus last wrote the scene, to detect outside edits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
 }
 
 /// All configured scenes
@@ -87,6 +166,55 @@ pub struct ScenesConfig {
     pub success: SceneConfig,
     /// Failure scene configuration
     pub failure: SceneConfig,
+    /// In-progress scene configuration (older configs gain it on `scenes refresh`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running: Option<SceneConfig>,
+}
+
+/// Hue/saturation/brightness color used to build a status scene
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColorConfig {
+    /// Hue (0-65535, 0 = red)
+    pub hue: u16,
+    /// Saturation (0-254)
+    pub saturation: u8,
+    /// Brightness (1-254)
+    pub brightness: u8,
+}
+
+/// Colors of the status scenes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorsConfig {
+    /// Success scene color (default green)
+    #[serde(default = "default_success_color")]
+    pub success: ColorConfig,
+    /// Failure scene color (default red)
+    #[serde(default = "default_failure_color")]
+    pub failure: ColorConfig,
+    /// Running scene color (default amber)
+    #[serde(default = "default_running_color")]
+    pub running: ColorConfig,
+    /// Gradients spread across the lights instead of a solid color, by status type
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub gradients: BTreeMap<String, Vec<ColorConfig>>,
+    /// Breathing effect for the failure scene
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_effect: Option<BreathingEffect>,
+    /// Light effect for the success scene (e.g. "colorloop"), on lights that support it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success_effect: Option<String>,
+}
+
+/// Alternating bright and dim lights for an "attention needed" scene
+///
+/// A scene is a static snapshot, so this doesn't animate by itself; recalling
+/// the scene repeatedly (or alongside a dimmed one) gives the breathing look.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BreathingEffect {
+    /// Brightness of every other light (1-254)
+    pub min_brightness: u8,
+    /// Brightness of the remaining lights (1-254)
+    pub max_brightness: u8,
 }
 
 /// Application settings
@@ -113,6 +241,36 @@ pub struct Settings {
     /// Validate scenes on startup
     #[serde(default)]
     pub validate_scenes_on_startup: bool,
+    /// Scene transition time in deciseconds (0 = instant, unset = bridge default)
+    #[serde(default)]
+    pub transition_time: Option<u16>,
+    /// Group the status scenes are recalled on ("0" = all lights)
+    #[serde(default = "default_recall_group_id")]
+    pub recall_group_id: String,
+    /// Briefly dim the lights after showing a status to confirm it was received
+    #[serde(default)]
+    pub confirm_flash: bool,
+    /// Success and failure are meant to use different lights; `validate` warns if they match
+    #[serde(default)]
+    pub distinct_scene_lights: bool,
+    /// Custom HTTP user agent (defaults to `huestatus/<version>`)
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Bridge discovery service URL (defaults to the Philips service)
+    #[serde(default)]
+    pub discovery_url: Option<String>,
+    /// Skip TLS certificate verification (local bridges with self-signed certs only)
+    #[serde(default)]
+    pub insecure: bool,
+    /// Lowest brightness used in status scenes, so the indicator stays visible
+    #[serde(default = "default_min_brightness")]
+    pub min_brightness: u8,
+    /// Smallest distance (0-100) between status colors before `validate` warns they look alike
+    #[serde(default = "default_min_color_distance")]
+    pub min_color_distance: f64,
+    /// Unknown keys (e.g. from a newer version), kept so they survive a re-save
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Advanced settings for performance optimization
@@ -127,6 +285,12 @@ pub struct AdvancedSettings {
     /// Scene validation interval in hours
     #[serde(default = "default_validation_interval")]
     pub scene_validation_interval_hours: u64,
+    /// Most lights a status scene may hold; the bridge's free light states can lower it
+    #[serde(default = "default_max_lights_per_scene")]
+    pub max_lights_per_scene: usize,
+    /// Unknown keys (e.g. from a newer version), kept so they survive a re-save
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Main configuration structure
@@ -145,6 +309,9 @@ pub struct Config {
     /// Advanced settings
     #[serde(default)]
     pub advanced: AdvancedSettings,
+    /// Status scene colors
+    #[serde(default)]
+    pub colors: ColorsConfig,
 }
 
 // Default value functions
@@ -164,10 +331,50 @@ fn default_auto_refresh() -> bool {
     true
 }
 
+fn default_min_brightness() -> u8 {
+    30
+}
+
+fn default_min_color_distance() -> f64 {
+    20.0
+}
+
+fn default_recall_group_id() -> String {
+    "0".to_string()
+}
+
+fn default_success_color() -> ColorConfig {
+    ColorConfig {
+        hue: 21845, // Green: 120° × 65536/360°
+        saturation: 254,
+        brightness: 254,
+    }
+}
+
+fn default_failure_color() -> ColorConfig {
+    ColorConfig {
+        hue: 0, // Red: 0°
+        saturation: 254,
+        brightness: 254,
+    }
+}
+
+fn default_running_color() -> ColorConfig {
+    ColorConfig {
+        hue: 7282, // Amber: 40° × 65536/360°
+        saturation: 254,
+        brightness: 254,
+    }
+}
+
 fn default_pool_size() -> usize {
     5
 }
 
+fn default_max_lights_per_scene() -> usize {
+    crate::scenes::DEFAULT_MAX_LIGHTS_PER_SCENE
+}
+
 fn default_cache_duration() -> u64 {
     30
 }
@@ -186,6 +393,16 @@ impl Default for Settings {
             quiet_mode: false,
             auto_refresh_scenes: default_auto_refresh(),
             validate_scenes_on_startup: false,
+            transition_time: None,
+            recall_group_id: default_recall_group_id(),
+            confirm_flash: false,
+            distinct_scene_lights: false,
+            user_agent: None,
+            discovery_url: None,
+            insecure: false,
+            min_brightness: default_min_brightness(),
+            min_color_distance: default_min_color_distance(),
+            extra: HashMap::new(),
         }
     }
 }
@@ -196,6 +413,75 @@ impl Default for AdvancedSettings {
             connection_pool_size: default_pool_size(),
             cache_duration_minutes: default_cache_duration(),
             scene_validation_interval_hours: default_validation_interval(),
+            max_lights_per_scene: default_max_lights_per_scene(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
+impl Default for ColorsConfig {
+    fn default() -> Self {
+        ColorsConfig {
+            success: default_success_color(),
+            failure: default_failure_color(),
+            running: default_running_color(),
+            gradients: BTreeMap::new(),
+            failure_effect: None,
+            success_effect: None,
+        }
+    }
+}
+
+impl ColorsConfig {
+    /// Get the color for a status type
+    pub fn get(&self, scene_type: &str) -> Option<ColorConfig> {
+        match scene_type {
+            "success" => Some(self.success),
+            "failure" => Some(self.failure),
+            "running" => Some(self.running),
+            _ => None,
+        }
+    }
+
+    /// Get the gradient for a status type, if one is configured
+    pub fn gradient(&self, scene_type: &str) -> Option<&[ColorConfig]> {
+        self.gradients
+            .get(scene_type)
+            .map(Vec::as_slice)
+            .filter(|colors| !colors.is_empty())
+    }
+
+    /// Warn about pairs of status colors closer than `min_distance` (see `color_distance`)
+    pub fn similar_colors(&self, min_distance: f64) -> Vec<String> {
+        let pairs = [
+            ("success", self.success, "failure", self.failure),
+            ("success", self.success, "running", self.running),
+            ("failure", self.failure, "running", self.running),
+        ];
+
+        pairs
+            .into_iter()
+            .filter_map(|(a_name, a, b_name, b)| {
+                let distance = crate::bridge::color_distance(
+                    (a.hue, a.saturation),
+                    (b.hue, b.saturation),
+                );
+                (distance < min_distance).then(|| {
+                    format!(
+                        "The {a_name} and {b_name} colors look alike (distance {distance:.0}, warning below {min_distance:.0}); pick hues further apart"
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Use the same brightness for every status color
+    pub fn set_brightness(&mut self, brightness: u8) {
+        for color in [&mut self.success, &mut self.failure, &mut self.running] {
+            color.brightness = brightness;
+        }
+        for color in self.gradients.values_mut().flatten() {
+            color.brightness = brightness;
         }
     }
 }
@@ -219,12 +505,47 @@ impl Config {
             scenes: ScenesConfig {
                 success: success_scene,
                 failure: failure_scene,
+                running: None,
             },
             settings: Settings::default(),
             advanced: AdvancedSettings::default(),
+            colors: ColorsConfig::default(),
         }
     }
 
+    /// Build a configuration entirely from environment variables
+    ///
+    /// Requires every variable in [`REQUIRED_ENV_VARS`]; the remaining
+    /// settings use their defaults and the usual `HUESTATUS_*` overrides.
+    pub fn from_env() -> crate::error::Result<Self> {
+        let mut config = Config::new(
+            required_env_var("HUESTATUS_BRIDGE_IP")?,
+            required_env_var("HUESTATUS_APP_KEY")?,
+            Self::create_scene_config(
+                required_env_var("HUESTATUS_SUCCESS_SCENE_ID")?,
+                "huestatus-success".to_string(),
+                false,
+            ),
+            Self::create_scene_config(
+                required_env_var("HUESTATUS_FAILURE_SCENE_ID")?,
+                "huestatus-failure".to_string(),
+                false,
+            ),
+        );
+
+        config.apply_env_overrides()?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Check if the environment provides everything `from_env` needs
+    pub fn env_config_available() -> bool {
+        REQUIRED_ENV_VARS
+            .iter()
+            .all(|name| std::env::var(name).is_ok_and(|value| !value.is_empty()))
+    }
+
     /// Get configuration directory path
     pub fn get_config_dir() -> crate::error::Result<PathBuf> {
         dirs::config_dir()
@@ -233,9 +554,62 @@ impl Config {
             .pipe(Ok)
     }
 
-    /// Get configuration file path
+    /// Get configuration file path for the active project
     pub fn get_config_file_path() -> crate::error::Result<PathBuf> {
-        Self::get_config_dir().map(|dir| dir.join("config.json"))
+        let project = Self::current_project();
+        Self::get_config_dir().map(|dir| Self::project_config_path(&dir, project.as_deref()))
+    }
+
+    /// Config file inside `config_dir`: `config.json`, or `projects/{name}.json` for a project
+    pub fn project_config_path(config_dir: &Path, project: Option<&str>) -> PathBuf {
+        match project {
+            Some(name) => config_dir.join("projects").join(format!("{name}.json")),
+            None => config_dir.join("config.json"),
+        }
+    }
+
+    /// Scene IDs used by the configurations in `config_dir`: the default one and every project's
+    ///
+    /// Projects can share a bridge, so scene cleanup must leave all of these alone.
+    /// Files are only parsed, not validated or migrated; unreadable ones are skipped.
+    pub fn scene_ids_in_use(config_dir: &Path) -> Vec<String> {
+        let mut paths = vec![Self::project_config_path(config_dir, None)];
+        if let Ok(entries) = std::fs::read_dir(config_dir.join("projects")) {
+            paths.extend(
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "json")),
+            );
+        }
+
+        paths
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .filter_map(|content| file::parse_config(&content).ok())
+            .flat_map(|config| {
+                ["success", "failure", "running"]
+                    .into_iter()
+                    .filter_map(|scene_type| config.get_scene(scene_type))
+                    .map(|scene| scene.id.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Select the project for this process (only the first call has an effect)
+    pub fn init_project(project: Option<String>) {
+        let _ = CURRENT_PROJECT.set(project);
+    }
+
+    /// Get the active project, falling back to `HUESTATUS_PROJECT`
+    pub fn current_project() -> Option<String> {
+        CURRENT_PROJECT
+            .get_or_init(|| {
+                std::env::var(PROJECT_ENV_VAR)
+                    .ok()
+                    .filter(|name| validate_project_name(name).is_ok())
+            })
+            .clone()
     }
 
     /// Check if configuration file exists
@@ -246,6 +620,9 @@ impl Config {
     }
 
     /// Load configuration from file
+    ///
+    /// Only the file contents are returned; call [`Config::apply_env_overrides`]
+    /// to layer `HUESTATUS_*` environment variables on top.
     pub fn load() -> crate::error::Result<Self> {
         let config_path = Self::get_config_file_path()?;
         file::load_config(&config_path)
@@ -277,6 +654,11 @@ impl Config {
         match scene_type {
             "success" => self.scenes.success.last_validated = now,
             "failure" => self.scenes.failure.last_validated = now,
+            "running" => {
+                if let Some(running) = &mut self.scenes.running {
+                    running.last_validated = now;
+                }
+            }
             _ => {}
         }
     }
@@ -289,6 +671,11 @@ impl Config {
         now.signed_duration_since(self.bridge.last_verified) > stale_duration
     }
 
+    /// Check if the bridge was verified no longer than `max_age` ago
+    pub fn is_bridge_verified_within(&self, max_age: chrono::Duration) -> bool {
+        Utc::now().signed_duration_since(self.bridge.last_verified) <= max_age
+    }
+
     /// Check if capabilities cache is stale
     pub fn is_capabilities_cache_stale(&self) -> bool {
         if let Some(cache) = &self.bridge.capabilities_cache {
@@ -306,6 +693,11 @@ impl Config {
         let last_validated = match scene_type {
             "success" => self.scenes.success.last_validated,
             "failure" => self.scenes.failure.last_validated,
+            "running" => self
+                .scenes
+                .running
+                .as_ref()
+                .and_then(|running| running.last_validated),
             _ => return true,
         };
 
@@ -319,12 +711,17 @@ impl Config {
         }
     }
 
-    /// Migrate configuration to latest version
+    /// Migrate configuration to latest version, one version step at a time
     pub fn migrate(&mut self) -> crate::error::Result<()> {
-        if !self.version.needs_migration() {
-            return Ok(());
+        while self.version.needs_migration() {
+            self.migrate_step();
         }
 
+        Ok(())
+    }
+
+    /// Migrate configuration to the next version
+    fn migrate_step(&mut self) {
         match self.version {
             ConfigVersion::V1_0 => {
                 // Migrate from v1.0 to v1.1
@@ -339,16 +736,14 @@ impl Config {
             }
             ConfigVersion::V1_1 => {
                 // Migrate from v1.1 to v1.2
-                // Add advanced settings
-                self.advanced = AdvancedSettings::default();
+                // Advanced settings are filled with defaults when the file has none,
+                // so any that are present were set by the user and are kept
                 self.version = ConfigVersion::V1_2;
             }
             ConfigVersion::V1_2 => {
                 // Current version, no migration needed
             }
         }
-
-        Ok(())
     }
 
     /// Validate configuration
@@ -356,11 +751,88 @@ impl Config {
         validation::validate_config(self)
     }
 
+    /// Set a value by its dotted key path (e.g. `settings.timeout_seconds`)
+    ///
+    /// Only the value is parsed here; run `validate` afterwards to check its range.
+    pub fn set_value(&mut self, key: &str, value: &str) -> crate::error::Result<()> {
+        let settings = &mut self.settings;
+        let advanced = &mut self.advanced;
+        let colors = &mut self.colors;
+
+        match key {
+            "bridge.ip" => self.bridge.ip = value.to_string(),
+            "scenes.success.id" => self.scenes.success.id = value.to_string(),
+            "scenes.success.name" => self.scenes.success.name = value.to_string(),
+            "scenes.failure.id" => self.scenes.failure.id = value.to_string(),
+            "scenes.failure.name" => self.scenes.failure.name = value.to_string(),
+            "settings.timeout_seconds" => settings.timeout_seconds = parse_value(key, value)?,
+            "settings.retry_attempts" => settings.retry_attempts = parse_value(key, value)?,
+            "settings.retry_delay_seconds" => {
+                settings.retry_delay_seconds = parse_value(key, value)?
+            }
+            "settings.verbose_logging" => settings.verbose_logging = parse_bool(key, value)?,
+            "settings.quiet_mode" => settings.quiet_mode = parse_bool(key, value)?,
+            "settings.auto_refresh_scenes" => {
+                settings.auto_refresh_scenes = parse_bool(key, value)?
+            }
+            "settings.validate_scenes_on_startup" => {
+                settings.validate_scenes_on_startup = parse_bool(key, value)?
+            }
+            "settings.transition_time" => settings.transition_time = parse_optional(key, value)?,
+            "settings.recall_group_id" => settings.recall_group_id = value.to_string(),
+            "settings.confirm_flash" => settings.confirm_flash = parse_bool(key, value)?,
+            "settings.distinct_scene_lights" => {
+                settings.distinct_scene_lights = parse_bool(key, value)?
+            }
+            "settings.user_agent" => settings.user_agent = parse_optional(key, value)?,
+            "settings.discovery_url" => settings.discovery_url = parse_optional(key, value)?,
+            "settings.insecure" => settings.insecure = parse_bool(key, value)?,
+            "settings.min_brightness" => settings.min_brightness = parse_bri(key, value)?,
+            "settings.min_color_distance" => settings.min_color_distance = parse_value(key, value)?,
+            "advanced.connection_pool_size" => {
+                advanced.connection_pool_size = parse_value(key, value)?
+            }
+            "advanced.cache_duration_minutes" => {
+                advanced.cache_duration_minutes = parse_value(key, value)?
+            }
+            "advanced.scene_validation_interval_hours" => {
+                advanced.scene_validation_interval_hours = parse_value(key, value)?
+            }
+            "advanced.max_lights_per_scene" => {
+                advanced.max_lights_per_scene = parse_value(key, value)?
+            }
+            "colors.success.hue" => colors.success.hue = parse_value(key, value)?,
+            "colors.success.saturation" => colors.success.saturation = parse_value(key, value)?,
+            "colors.success.brightness" => colors.success.brightness = parse_bri(key, value)?,
+            "colors.failure.hue" => colors.failure.hue = parse_value(key, value)?,
+            "colors.failure.saturation" => colors.failure.saturation = parse_value(key, value)?,
+            "colors.failure.brightness" => colors.failure.brightness = parse_bri(key, value)?,
+            "colors.running.hue" => colors.running.hue = parse_value(key, value)?,
+            "colors.running.saturation" => colors.running.saturation = parse_value(key, value)?,
+            "colors.running.brightness" => colors.running.brightness = parse_bri(key, value)?,
+            _ => return Err(unknown_key_error(key)),
+        }
+
+        Ok(())
+    }
+
+    /// Get a value by its dotted key path (e.g. `settings.timeout_seconds`)
+    pub fn get_value(&self, key: &str) -> crate::error::Result<serde_json::Value> {
+        if !CONFIG_KEYS.contains(&key) {
+            return Err(unknown_key_error(key));
+        }
+
+        let pointer = format!("/{}", key.replace('.', "/"));
+        let value = serde_json::to_value(self)?;
+        Ok(value.pointer(&pointer).cloned().unwrap_or_default())
+    }
+
     /// Get scene configuration by type
     pub fn get_scene(&self, scene_type: &str) -> Option<&SceneConfig> {
         match scene_type {
             "success" => Some(&self.scenes.success),
             "failure" => Some(&self.scenes.failure),
+            "running" => self.scenes.running.as_ref(),
             _ => None,
         }
     }
@@ -370,6 +842,7 @@ impl Config {
         match scene_type {
             "success" => Some(&mut self.scenes.success),
             "failure" => Some(&mut self.scenes.failure),
+            "running" => self.scenes.running.as_mut(),
             _ => None,
         }
     }
@@ -380,11 +853,16 @@ impl Config {
             id,
             name,
             auto_created,
+            imported: false,
             last_validated: None,
+            last_updated: None,
         }
     }
 
     /// Apply environment variable overrides
+    ///
+    /// Precedence is command-line flag > environment variable > config file,
+    /// so callers apply CLI flags after this.
     pub fn apply_env_overrides(&mut self) -> crate::error::Result<()> {
         use std::env;
 
@@ -395,48 +873,277 @@ impl Config {
 
         // Override timeout
         if let Ok(timeout) = env::var("HUESTATUS_TIMEOUT") {
-            self.settings.timeout_seconds = timeout.parse().map_err(|_| {
-                crate::error::HueStatusError::EnvironmentVariableError {
-                    var_name: "HUESTATUS_TIMEOUT".to_string(),
-                }
-            })?;
+            self.settings.timeout_seconds =
+                parse_env_var("HUESTATUS_TIMEOUT", &timeout, "integer seconds")?;
+        }
+
+        // Override retry settings
+        if let Ok(retry_attempts) = env::var("HUESTATUS_RETRY_ATTEMPTS") {
+            self.settings.retry_attempts = parse_env_var(
+                "HUESTATUS_RETRY_ATTEMPTS",
+                &retry_attempts,
+                "a whole number",
+            )?;
+        }
+
+        if let Ok(retry_delay) = env::var("HUESTATUS_RETRY_DELAY") {
+            self.settings.retry_delay_seconds =
+                parse_env_var("HUESTATUS_RETRY_DELAY", &retry_delay, "integer seconds")?;
+        }
+
+        // Override scene IDs
+        if let Ok(success_id) = env::var("HUESTATUS_SUCCESS_SCENE_ID") {
+            if !success_id.is_empty() {
+                self.scenes.success.id = success_id;
+            }
+        }
+
+        if let Ok(failure_id) = env::var("HUESTATUS_FAILURE_SCENE_ID") {
+            if !failure_id.is_empty() {
+                self.scenes.failure.id = failure_id;
+            }
+        }
+
+        // Override user agent
+        if let Ok(user_agent) = env::var("HUESTATUS_USER_AGENT") {
+            if !user_agent.is_empty() {
+                self.settings.user_agent = Some(user_agent);
+            }
+        }
+
+        // Override discovery service URL
+        if let Ok(discovery_url) = env::var(DISCOVERY_URL_ENV_VAR) {
+            if !discovery_url.is_empty() {
+                self.settings.discovery_url = Some(discovery_url);
+            }
         }
 
         // Override verbose mode
         if let Ok(verbose) = env::var("HUESTATUS_VERBOSE") {
-            self.settings.verbose_logging = verbose.parse().unwrap_or(false);
+            self.settings.verbose_logging = parse_env_bool("HUESTATUS_VERBOSE", &verbose)?;
         }
 
         // Override quiet mode
         if let Ok(quiet) = env::var("HUESTATUS_QUIET") {
-            self.settings.quiet_mode = quiet.parse().unwrap_or(false);
+            self.settings.quiet_mode = parse_env_bool("HUESTATUS_QUIET", &quiet)?;
         }
 
         Ok(())
     }
 
     /// Get effective timeout considering environment variables
-    pub fn effective_timeout(&self) -> u64 {
-        std::env::var("HUESTATUS_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(self.settings.timeout_seconds)
+    ///
+    /// Fails on the same invalid values as `apply_env_overrides`.
+    pub fn effective_timeout(&self) -> crate::error::Result<u64> {
+        match std::env::var("HUESTATUS_TIMEOUT") {
+            Ok(timeout) => parse_env_var("HUESTATUS_TIMEOUT", &timeout, "integer seconds"),
+            Err(_) => Ok(self.settings.timeout_seconds),
+        }
     }
 
     /// Get effective verbose mode considering environment variables
-    pub fn effective_verbose(&self) -> bool {
-        std::env::var("HUESTATUS_VERBOSE")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(self.settings.verbose_logging)
+    ///
+    /// Fails on the same invalid values as `apply_env_overrides`.
+    pub fn effective_verbose(&self) -> crate::error::Result<bool> {
+        match std::env::var("HUESTATUS_VERBOSE") {
+            Ok(verbose) => parse_env_bool("HUESTATUS_VERBOSE", &verbose),
+            Err(_) => Ok(self.settings.verbose_logging),
+        }
+    }
+
+    /// Settings and advanced settings that differ from their defaults, sorted by key
+    pub fn differences_from_defaults(&self) -> crate::error::Result<Vec<ConfigDifference>> {
+        let sections = [
+            (
+                "settings",
+                serde_json::to_value(&self.settings)?,
+                serde_json::to_value(Settings::default())?,
+            ),
+            (
+                "advanced",
+                serde_json::to_value(&self.advanced)?,
+                serde_json::to_value(AdvancedSettings::default())?,
+            ),
+        ];
+
+        let mut differences = Vec::new();
+        for (section, current, default) in sections {
+            let (Some(current), Some(default)) = (current.as_object(), default.as_object()) else {
+                continue;
+            };
+            let fields: std::collections::BTreeSet<_> =
+                current.keys().chain(default.keys()).collect();
+            for field in fields {
+                let current = current.get(field).cloned().unwrap_or_default();
+                let default = default.get(field).cloned().unwrap_or_default();
+                if current != default {
+                    differences.push(ConfigDifference {
+                        field: format!("{section}.{field}"),
+                        current,
+                        default,
+                    });
+                }
+            }
+        }
+        Ok(differences)
+    }
+
+    /// Short overview of the configuration, with the application key masked
+    pub fn summary(&self) -> String {
+        let scene = |scene: &SceneConfig| format!("{} ({})", scene.name, scene.id);
+        let color = |color: &ColorConfig| {
+            format!(
+                "H:{} S:{} B:{}",
+                color.hue, color.saturation, color.brightness
+            )
+        };
+        let running = self
+            .scenes
+            .running
+            .as_ref()
+            .map_or_else(|| "not configured".to_string(), scene);
+
+        [
+            format!(
+                "Bridge: {} (key {}, verified {})",
+                self.bridge.ip,
+                mask_key(&self.bridge.application_key),
+                age_string(self.bridge.last_verified)
+            ),
+            format!(
+                "Scenes: success {}, failure {}, running {running}",
+                scene(&self.scenes.success),
+                scene(&self.scenes.failure)
+            ),
+            format!(
+                "Requests: {}s timeout, {} retries {}s apart",
+                self.settings.timeout_seconds,
+                self.settings.retry_attempts,
+                self.settings.retry_delay_seconds
+            ),
+            format!(
+                "Colors (hue/saturation): success {}, failure {}, running {}",
+                color(&self.colors.success),
+                color(&self.colors.failure),
+                color(&self.colors.running)
+            ),
+        ]
+        .join("\n")
     }
 
     /// Get effective quiet mode considering environment variables
-    pub fn effective_quiet(&self) -> bool {
-        std::env::var("HUESTATUS_QUIET")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(self.settings.quiet_mode)
+    ///
+    /// Fails on the same invalid values as `apply_env_overrides`.
+    pub fn effective_quiet(&self) -> crate::error::Result<bool> {
+        match std::env::var("HUESTATUS_QUIET") {
+            Ok(quiet) => parse_env_bool("HUESTATUS_QUIET", &quiet),
+            Err(_) => Ok(self.settings.quiet_mode),
+        }
+    }
+}
+
+/// Show only the start of an application key
+pub fn mask_key(key: &str) -> String {
+    format!("{}…", key.chars().take(4).collect::<String>())
+}
+
+/// How long ago a time was, e.g. "3 hours ago"
+fn age_string(time: DateTime<Utc>) -> String {
+    let age = Utc::now().signed_duration_since(time);
+
+    if age < chrono::Duration::minutes(1) {
+        "just now".to_string()
+    } else if age < chrono::Duration::hours(1) {
+        format!("{} minutes ago", age.num_minutes())
+    } else if age < chrono::Duration::days(1) {
+        format!("{} hours ago", age.num_hours())
+    } else {
+        format!("{} days ago", age.num_days())
+    }
+}
+
+/// Error for a key path that isn't in `CONFIG_KEYS`
+fn unknown_key_error(key: &str) -> crate::error::HueStatusError {
+    crate::error::HueStatusError::InvalidConfig {
+        reason: format!(
+            "Unknown config key '{key}' (known keys: {})",
+            CONFIG_KEYS.join(", ")
+        ),
+    }
+}
+
+/// Parse a config value, naming the key in the error
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> crate::error::Result<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| crate::error::HueStatusError::InvalidConfig {
+            reason: format!("Invalid value '{value}' for {key}"),
+        })
+}
+
+/// Parse a brightness config value, either 0-254 or a percentage such as `50%`
+fn parse_bri(key: &str, value: &str) -> crate::error::Result<u8> {
+    crate::bridge::parse_brightness(value).map_err(|reason| {
+        crate::error::HueStatusError::InvalidConfig {
+            reason: format!("{reason} for {key}"),
+        }
+    })
+}
+
+/// Parse a boolean config value (`true`/`false`, `yes`/`no`, `on`/`off`, `1`/`0`)
+fn parse_bool(key: &str, value: &str) -> crate::error::Result<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(crate::error::HueStatusError::InvalidConfig {
+            reason: format!("Invalid value '{value}' for {key} (expected true or false)"),
+        }),
+    }
+}
+
+/// Parse an optional config value, where an empty value or `none` clears it
+fn parse_optional<T: std::str::FromStr>(key: &str, value: &str) -> crate::error::Result<Option<T>> {
+    if value.trim().is_empty() || value.trim().eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    parse_value(key, value).map(Some)
+}
+
+/// Read a required, non-empty environment variable
+fn required_env_var(name: &str) -> crate::error::Result<String> {
+    let reason = match std::env::var(name) {
+        Ok(value) if !value.is_empty() => return Ok(value),
+        Ok(_) => "it is empty".to_string(),
+        Err(std::env::VarError::NotPresent) => "it is not set".to_string(),
+        Err(std::env::VarError::NotUnicode(_)) => "it is not valid UTF-8".to_string(),
+    };
+    Err(env_var_error(name, reason))
+}
+
+/// Parse an environment variable override, naming the bad value on error
+fn parse_env_var<T>(name: &str, value: &str, expected: &str) -> crate::error::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e| env_var_error(name, format!("expected {expected}, got '{value}' ({e})")))
+}
+
+/// Parse a boolean environment variable (same spellings as `parse_bool`)
+fn parse_env_bool(name: &str, value: &str) -> crate::error::Result<bool> {
+    parse_bool(name, value)
+        .map_err(|_| env_var_error(name, format!("expected true or false, got '{value}'")))
+}
+
+/// Build an `EnvironmentVariableError` for `name` with a human-readable reason
+fn env_var_error(name: &str, reason: String) -> crate::error::HueStatusError {
+    crate::error::HueStatusError::EnvironmentVariableError {
+        var_name: name.to_string(),
+        reason,
     }
 }
 
@@ -535,6 +1242,63 @@ mod tests {
         assert_eq!(config.scenes.failure.id, "failure-id");
     }
 
+    #[test]
+    fn test_bridge_verified_within() {
+        let mut config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-key".to_string(),
+            Config::create_scene_config("success-id".to_string(), "ok".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "ko".to_string(), true),
+        );
+        assert!(config.is_bridge_verified_within(chrono::Duration::minutes(5)));
+
+        config.bridge.last_verified = Utc::now() - chrono::Duration::hours(2);
+        assert!(!config.is_bridge_verified_within(chrono::Duration::hours(1)));
+        assert!(config.is_bridge_verified_within(chrono::Duration::days(1)));
+    }
+
+    #[test]
+    fn test_config_summary_masks_key() {
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key".to_string(),
+            Config::create_scene_config("success-id".to_string(), "ok".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "ko".to_string(), true),
+        );
+
+        let summary = config.summary();
+        assert!(summary.starts_with("Bridge: 192.168.1.100 (key test…, verified just now)"));
+        assert!(summary.contains("success ok (success-id)"));
+        assert!(summary.contains("running not configured"));
+        assert!(!summary.contains("test-application-key"));
+    }
+
+    #[test]
+    fn test_differences_from_defaults() {
+        let mut config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-key".to_string(),
+            Config::create_scene_config("success-id".to_string(), "ok".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "ko".to_string(), true),
+        );
+        assert!(config.differences_from_defaults().unwrap().is_empty());
+
+        config.set_value("settings.timeout_seconds", "25").unwrap();
+        config
+            .settings
+            .extra
+            .insert("future_option".to_string(), serde_json::json!(true));
+
+        let differences = config.differences_from_defaults().unwrap();
+        let fields: Vec<_> = differences.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["settings.future_option", "settings.timeout_seconds"]
+        );
+        assert_eq!(differences[1].current, serde_json::json!(25));
+        assert_eq!(differences[0].default, serde_json::Value::Null);
+    }
+
     #[test]
     fn test_scene_getters() {
         let config = Config::new(
@@ -557,6 +1321,58 @@ mod tests {
         assert!(config.get_scene("invalid").is_none());
     }
 
+    #[test]
+    fn test_project_config_path() {
+        let dir = Path::new("/home/user/.config/huestatus");
+
+        assert_eq!(
+            Config::project_config_path(dir, None),
+            dir.join("config.json")
+        );
+        assert_eq!(
+            Config::project_config_path(dir, Some("api-server")),
+            dir.join("projects").join("api-server.json")
+        );
+    }
+
+    #[test]
+    fn test_scene_ids_in_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = |success: &str, failure: &str| {
+            Config::new(
+                "192.168.1.100".to_string(),
+                "test-key".to_string(),
+                Config::create_scene_config(
+                    success.to_string(),
+                    "huestatus-success".to_string(),
+                    true,
+                ),
+                Config::create_scene_config(
+                    failure.to_string(),
+                    "huestatus-failure".to_string(),
+                    true,
+                ),
+            )
+        };
+        assert!(Config::scene_ids_in_use(dir.path()).is_empty());
+
+        save_config(
+            &config("1", "2"),
+            &Config::project_config_path(dir.path(), None),
+        )
+        .unwrap();
+        save_config(
+            &config("7", "8"),
+            &Config::project_config_path(dir.path(), Some("api-server")),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("projects").join("broken.json"), "{").unwrap();
+
+        let mut ids = Config::scene_ids_in_use(dir.path());
+        ids.sort();
+        assert_eq!(ids, vec!["1", "2", "7", "8"]);
+    }
+
     #[test]
     fn test_default_settings() {
         let settings = Settings::default();
@@ -567,6 +1383,10 @@ mod tests {
         assert!(!settings.quiet_mode);
         assert!(settings.auto_refresh_scenes);
         assert!(!settings.validate_scenes_on_startup);
+        assert_eq!(settings.transition_time, None);
+        assert_eq!(settings.recall_group_id, "0");
+        assert!(!settings.confirm_flash);
+        assert_eq!(settings.min_brightness, 30);
     }
 
     #[test]
@@ -577,6 +1397,192 @@ mod tests {
         assert_eq!(advanced.scene_validation_interval_hours, 24);
     }
 
+    #[test]
+    fn test_config_from_env() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        std::env::set_var("HUESTATUS_BRIDGE_IP", "192.168.1.50");
+        std::env::set_var("HUESTATUS_APP_KEY", "env-application-key-1234");
+        std::env::set_var("HUESTATUS_SUCCESS_SCENE_ID", "env-success");
+        std::env::set_var("HUESTATUS_FAILURE_SCENE_ID", "env-failure");
+
+        assert!(Config::env_config_available());
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.bridge.ip, "192.168.1.50");
+        assert_eq!(config.bridge.application_key, "env-application-key-1234");
+        assert_eq!(config.scenes.success.id, "env-success");
+        assert_eq!(config.scenes.failure.id, "env-failure");
+        assert!(!config.scenes.success.auto_created);
+
+        std::env::remove_var("HUESTATUS_APP_KEY");
+        assert!(!Config::env_config_available());
+        assert!(matches!(
+            Config::from_env(),
+            Err(crate::error::HueStatusError::EnvironmentVariableError { var_name, reason })
+                if var_name == "HUESTATUS_APP_KEY" && reason == "it is not set"
+        ));
+
+        for name in REQUIRED_ENV_VARS {
+            std::env::remove_var(name);
+        }
+    }
+
+    #[test]
+    fn test_apply_env_overrides() {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-key".to_string(),
+            Config::create_scene_config(
+                "success-id".to_string(),
+                "success-scene".to_string(),
+                true,
+            ),
+            Config::create_scene_config(
+                "failure-id".to_string(),
+                "failure-scene".to_string(),
+                true,
+            ),
+        );
+
+        std::env::set_var("HUESTATUS_RETRY_ATTEMPTS", "5");
+        std::env::set_var("HUESTATUS_RETRY_DELAY", "2");
+        std::env::set_var("HUESTATUS_SUCCESS_SCENE_ID", "env-success");
+        std::env::set_var("HUESTATUS_FAILURE_SCENE_ID", "env-failure");
+
+        config.apply_env_overrides().unwrap();
+        assert_eq!(config.settings.retry_attempts, 5);
+        assert_eq!(config.settings.retry_delay_seconds, 2);
+        assert_eq!(config.scenes.success.id, "env-success");
+        assert_eq!(config.scenes.failure.id, "env-failure");
+
+        std::env::set_var("HUESTATUS_RETRY_ATTEMPTS", "many");
+        assert!(matches!(
+            config.apply_env_overrides(),
+            Err(crate::error::HueStatusError::EnvironmentVariableError { var_name, reason })
+                if var_name == "HUESTATUS_RETRY_ATTEMPTS"
+                    && reason.starts_with("expected a whole number, got 'many'")
+        ));
+
+        std::env::remove_var("HUESTATUS_RETRY_ATTEMPTS");
+        std::env::set_var("HUESTATUS_VERBOSE", "loud");
+        assert!(matches!(
+            config.apply_env_overrides(),
+            Err(crate::error::HueStatusError::EnvironmentVariableError { reason, .. })
+                if reason == "expected true or false, got 'loud'"
+        ));
+        assert!(config.effective_verbose().is_err());
+        std::env::set_var("HUESTATUS_VERBOSE", "yes");
+        config.apply_env_overrides().unwrap();
+        assert!(config.settings.verbose_logging);
+        assert!(config.effective_verbose().unwrap());
+        std::env::remove_var("HUESTATUS_VERBOSE");
+
+        std::env::set_var("HUESTATUS_QUIET", "loud");
+        assert!(matches!(
+            config.effective_quiet(),
+            Err(crate::error::HueStatusError::EnvironmentVariableError { var_name, reason })
+                if var_name == "HUESTATUS_QUIET" && reason == "expected true or false, got 'loud'"
+        ));
+        std::env::set_var("HUESTATUS_QUIET", "on");
+        assert!(config.effective_quiet().unwrap());
+        std::env::remove_var("HUESTATUS_QUIET");
+        assert_eq!(
+            config.effective_quiet().unwrap(),
+            config.settings.quiet_mode
+        );
+
+        std::env::set_var("HUESTATUS_TIMEOUT", "soon");
+        assert!(matches!(
+            config.effective_timeout(),
+            Err(crate::error::HueStatusError::EnvironmentVariableError { var_name, reason })
+                if var_name == "HUESTATUS_TIMEOUT"
+                    && reason.starts_with("expected integer seconds, got 'soon'")
+        ));
+        std::env::set_var("HUESTATUS_TIMEOUT", "15");
+        assert_eq!(config.effective_timeout().unwrap(), 15);
+        std::env::remove_var("HUESTATUS_TIMEOUT");
+        assert_eq!(
+            config.effective_timeout().unwrap(),
+            config.settings.timeout_seconds
+        );
+
+        std::env::remove_var("HUESTATUS_RETRY_ATTEMPTS");
+        std::env::set_var("HUESTATUS_RETRY_DELAY", "-1");
+        assert!(config.apply_env_overrides().is_err());
+
+        for name in [
+            "HUESTATUS_RETRY_DELAY",
+            "HUESTATUS_SUCCESS_SCENE_ID",
+            "HUESTATUS_FAILURE_SCENE_ID",
+        ] {
+            std::env::remove_var(name);
+        }
+    }
+
+    #[test]
+    fn test_get_and_set_value() {
+        let mut config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("success-id".to_string(), "success".to_string(), true),
+            Config::create_scene_config("failure-id".to_string(), "failure".to_string(), true),
+        );
+
+        config.set_value("settings.timeout_seconds", "20").unwrap();
+        config.set_value("settings.confirm_flash", "yes").unwrap();
+        config.set_value("settings.transition_time", "4").unwrap();
+        assert_eq!(config.settings.timeout_seconds, 20);
+        assert!(config.settings.confirm_flash);
+        assert_eq!(config.settings.transition_time, Some(4));
+
+        config
+            .set_value("settings.transition_time", "none")
+            .unwrap();
+        assert_eq!(config.settings.transition_time, None);
+
+        assert!(config
+            .set_value("settings.timeout_seconds", "soon")
+            .is_err());
+        assert!(config.set_value("settings.quiet_mode", "maybe").is_err());
+        assert!(config.set_value("settings.unknown", "1").is_err());
+        assert_eq!(config.settings.timeout_seconds, 20);
+
+        config
+            .set_value("colors.running.brightness", "50%")
+            .unwrap();
+        assert_eq!(config.colors.running.brightness, 126);
+
+        assert_eq!(
+            config.get_value("settings.timeout_seconds").unwrap(),
+            serde_json::json!(20)
+        );
+        assert_eq!(
+            config.get_value("settings.transition_time").unwrap(),
+            serde_json::Value::Null
+        );
+        assert!(config.get_value("bridge.application_key").is_err());
+
+        // Out-of-range values parse but fail validation
+        config.set_value("settings.timeout_seconds", "0").unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_similar_colors() {
+        let mut colors = ColorsConfig::default();
+        assert!(colors
+            .similar_colors(default_min_color_distance())
+            .is_empty());
+
+        colors.running.hue = 500;
+        let warnings = colors.similar_colors(default_min_color_distance());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("The failure and running colors look alike"));
+        assert!(colors.similar_colors(0.0).is_empty());
+    }
+
     #[test]
     fn test_path_length_validation() {
         // Test normal length path
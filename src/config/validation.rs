@@ -22,6 +22,9 @@ pub fn validate_config(config: &Config) -> Result<()> {
     // Validate advanced settings
     validate_advanced_settings(config)?;
 
+    // Validate status colors
+    validate_colors(config)?;
+
     Ok(())
 }
 
@@ -89,6 +92,40 @@ fn validate_scenes_config(config: &Config) -> Result<()> {
         });
     }
 
+    // Validate the optional running scene
+    if let Some(running) = &config.scenes.running {
+        validate_scene_config(running, "running")?;
+
+        if running.id == config.scenes.success.id || running.id == config.scenes.failure.id {
+            return Err(HueStatusError::InvalidConfig {
+                reason: "Running scene shares its ID with another status scene".to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate status scene colors
+fn validate_colors(config: &Config) -> Result<()> {
+    for scene_type in ["success", "failure", "running"] {
+        let Some(color) = config.colors.get(scene_type) else {
+            continue;
+        };
+
+        if color.saturation > 254 {
+            return Err(HueStatusError::InvalidConfig {
+                reason: format!("{scene_type} color saturation must be at most 254"),
+            });
+        }
+
+        if !(1..=254).contains(&color.brightness) {
+            return Err(HueStatusError::InvalidConfig {
+                reason: format!("{scene_type} color brightness must be between 1 and 254"),
+            });
+        }
+    }
+
     Ok(())
 }
 
@@ -166,6 +203,36 @@ fn validate_settings(config: &Config) -> Result<()> {
         });
     }
 
+    // Validate recall group
+    if config.settings.recall_group_id.trim().is_empty() {
+        return Err(HueStatusError::InvalidConfig {
+            reason: "Recall group ID cannot be empty".to_string(),
+        });
+    }
+
+    // Validate brightness floor (0 would be off, 255 is above the Hue maximum)
+    if !(1..=254).contains(&config.settings.min_brightness) {
+        return Err(HueStatusError::InvalidConfig {
+            reason: "Minimum brightness must be between 1 and 254".to_string(),
+        });
+    }
+
+    // Validate color distance threshold (0 disables the warning)
+    if !(0.0..=100.0).contains(&config.settings.min_color_distance) {
+        return Err(HueStatusError::InvalidConfig {
+            reason: "Minimum color distance must be between 0 and 100".to_string(),
+        });
+    }
+
+    // Validate discovery service URL
+    if let Some(url) = &config.settings.discovery_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(HueStatusError::InvalidConfig {
+                reason: "Discovery URL must start with http:// or https://".to_string(),
+            });
+        }
+    }
+
     // Validate conflicting settings
     if config.settings.verbose_logging && config.settings.quiet_mode {
         return Err(HueStatusError::InvalidConfig {
@@ -176,6 +243,23 @@ fn validate_settings(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Validate a `--project` name (it becomes a file name under the config directory)
+pub fn validate_project_name(name: &str) -> Result<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if name.is_empty() || name.len() > 64 || name.starts_with('.') || !valid_chars {
+        return Err(HueStatusError::InvalidConfig {
+            reason: format!(
+                "Invalid project name '{name}': use up to 64 letters, digits, '-', '_' or '.'"
+            ),
+        });
+    }
+
+    Ok(())
+}
+
 /// Validate advanced settings configuration
 fn validate_advanced_settings(config: &Config) -> Result<()> {
     // Validate connection pool size
@@ -191,6 +275,12 @@ fn validate_advanced_settings(config: &Config) -> Result<()> {
         });
     }
 
+    if config.advanced.max_lights_per_scene == 0 {
+        return Err(HueStatusError::InvalidConfig {
+            reason: "Max lights per scene cannot be zero".to_string(),
+        });
+    }
+
     // Validate cache duration
     if config.advanced.cache_duration_minutes == 0 {
         return Err(HueStatusError::InvalidConfig {
@@ -471,6 +561,16 @@ mod tests {
         assert!(validate_retry_delay(61).is_err());
     }
 
+    #[test]
+    fn test_validate_project_name() {
+        assert!(validate_project_name("api-server").is_ok());
+        assert!(validate_project_name("repo_1.web").is_ok());
+        assert!(validate_project_name("").is_err());
+        assert!(validate_project_name("..").is_err());
+        assert!(validate_project_name("../escape").is_err());
+        assert!(validate_project_name("a/b").is_err());
+    }
+
     #[test]
     fn test_validate_config() {
         let config = Config::new(
@@ -489,6 +589,34 @@ mod tests {
         );
 
         assert!(validate_config(&config).is_ok());
+
+        let mut config = config;
+        config.settings.recall_group_id = String::new();
+        assert!(validate_config(&config).is_err());
+
+        config.settings.recall_group_id = "0".to_string();
+        config.settings.min_brightness = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.settings.min_brightness = 30;
+        config.settings.min_color_distance = 150.0;
+        assert!(validate_config(&config).is_err());
+
+        config.settings.min_color_distance = 20.0;
+        config.settings.discovery_url = Some("discovery.local".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.settings.discovery_url = Some("http://discovery.local/".to_string());
+        config.colors.running.brightness = 0;
+        assert!(validate_config(&config).is_err());
+
+        config.colors.running.brightness = 254;
+        config.scenes.running = Some(Config::create_scene_config(
+            "success-id".to_string(),
+            "running-scene".to_string(),
+            true,
+        ));
+        assert!(validate_config(&config).is_err());
     }
 
     #[test]
@@ -1,4 +1,6 @@
+use crate::i18n::{message_in, Locale};
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
 /// Custom error types for huestatus application
 #[derive(Error, Debug)]
@@ -33,6 +35,15 @@ pub enum HueStatusError {
     #[error("Scene execution failed: {reason}")]
     SceneExecutionFailed { reason: String },
 
+    #[error("Scene name '{name}' is ambiguous. Matching scenes: {candidates}")]
+    AmbiguousSceneName { name: String, candidates: String },
+
+    #[error("Scene '{scene_name}' has no lights. Run 'huestatus setup --force' to refresh it.")]
+    EmptyScene { scene_name: String },
+
+    #[error("Resource not available on the bridge: {resource}")]
+    ResourceNotAvailable { resource: String },
+
     #[error("Network error: {source}")]
     NetworkError {
         #[from]
@@ -45,6 +56,13 @@ pub enum HueStatusError {
     #[error("Timeout error: {operation}")]
     TimeoutError { operation: String },
 
+    #[error("Bridge is overloaded and asked to slow down: {reason}")]
+    RateLimited {
+        reason: String,
+        /// Seconds the bridge asked to wait, from a `Retry-After` header
+        retry_after_seconds: Option<u64>,
+    },
+
     #[error("IO error: {source}")]
     IoError {
         #[from]
@@ -93,14 +111,17 @@ pub enum HueStatusError {
     #[error("Unsupported platform: {platform}")]
     UnsupportedPlatform { platform: String },
 
-    #[error("Environment variable error: {var_name}")]
-    EnvironmentVariableError { var_name: String },
+    #[error("Environment variable error: {var_name}: {reason}")]
+    EnvironmentVariableError { var_name: String, reason: String },
 
     #[error("Path too long: {path}")]
     PathTooLong { path: String },
 
     #[error("Capacity overflow during {operation}")]
     CapacityOverflow { operation: String },
+
+    #[error("Cancelled: {operation}")]
+    Cancelled { operation: String },
 }
 
 impl HueStatusError {
@@ -110,12 +131,14 @@ impl HueStatusError {
             HueStatusError::ConfigNotFound
             | HueStatusError::InvalidConfig { .. }
             | HueStatusError::ConfigCorrupted
-            | HueStatusError::ConfigVersionIncompatible => 1,
+            | HueStatusError::ConfigVersionIncompatible
+            | HueStatusError::ResourceNotAvailable { .. } => 1,
 
             HueStatusError::BridgeNotFound
             | HueStatusError::BridgeConnectionFailed { .. }
             | HueStatusError::NetworkError { .. }
             | HueStatusError::TimeoutError { .. }
+            | HueStatusError::RateLimited { .. }
             | HueStatusError::ApiError { .. }
             | HueStatusError::DiscoveryServiceUnreachable { .. }
             | HueStatusError::MdnsDiscoveryFailed { .. } => 2,
@@ -124,6 +147,8 @@ impl HueStatusError {
 
             HueStatusError::SceneNotFound { .. }
             | HueStatusError::SceneExecutionFailed { .. }
+            | HueStatusError::AmbiguousSceneName { .. }
+            | HueStatusError::EmptyScene { .. }
             | HueStatusError::SceneStorageLimitExceeded { .. }
             | HueStatusError::InvalidSceneData { .. } => 4,
 
@@ -141,77 +166,131 @@ impl HueStatusError {
             | HueStatusError::EnvironmentVariableError { .. }
             | HueStatusError::PathTooLong { .. }
             | HueStatusError::CapacityOverflow { .. } => 6,
+
+            // Same as a shell interrupted by SIGINT
+            HueStatusError::Cancelled { .. } => 130,
         }
     }
 
-    /// Get user-friendly error message with suggested actions
-    pub fn user_message(&self) -> String {
+    /// Stable identifier for the error, as accepted by `huestatus explain`
+    pub fn error_code(&self) -> &'static str {
         match self {
-            HueStatusError::ConfigNotFound => {
-                "No configuration found. Run 'huestatus --setup' to get started.".to_string()
+            HueStatusError::ConfigNotFound => "config_not_found",
+            HueStatusError::InvalidConfig { .. } => "invalid_config",
+            HueStatusError::ConfigCorrupted => "config_corrupted",
+            HueStatusError::ConfigVersionIncompatible => "config_version_incompatible",
+            HueStatusError::BridgeNotFound => "bridge_not_found",
+            HueStatusError::BridgeConnectionFailed { .. } => "bridge_connection_failed",
+            HueStatusError::AuthenticationFailed => "authentication_failed",
+            HueStatusError::LinkButtonNotPressed => "link_button_not_pressed",
+            HueStatusError::SceneNotFound { .. } => "scene_not_found",
+            HueStatusError::SceneExecutionFailed { .. } => "scene_execution_failed",
+            HueStatusError::AmbiguousSceneName { .. } => "ambiguous_scene_name",
+            HueStatusError::EmptyScene { .. } => "empty_scene",
+            HueStatusError::ResourceNotAvailable { .. } => "resource_not_available",
+            HueStatusError::NetworkError { .. } => "network_error",
+            HueStatusError::ApiError { .. } => "api_error",
+            HueStatusError::TimeoutError { .. } => "timeout",
+            HueStatusError::RateLimited { .. } => "rate_limited",
+            HueStatusError::IoError { .. } => "io_error",
+            HueStatusError::JsonError { .. } => "json_error",
+            HueStatusError::NoLightsFound => "no_lights_found",
+            HueStatusError::CapabilityCheckFailed { .. } => "capability_check_failed",
+            HueStatusError::SetupFailed { .. } => "setup_failed",
+            HueStatusError::ValidationFailed { .. } => "validation_failed",
+            HueStatusError::PermissionDenied { .. } => "permission_denied",
+            HueStatusError::DiscoveryServiceUnreachable { .. } => "discovery_service_unreachable",
+            HueStatusError::MdnsDiscoveryFailed { .. } => "mdns_discovery_failed",
+            HueStatusError::SceneStorageLimitExceeded { .. } => "scene_storage_limit_exceeded",
+            HueStatusError::InvalidSceneData { .. } => "invalid_scene_data",
+            HueStatusError::ColorConversionError { .. } => "color_conversion_error",
+            HueStatusError::ConfigDirectoryCreationFailed { .. } => {
+                "config_directory_creation_failed"
             }
+            HueStatusError::UnsupportedPlatform { .. } => "unsupported_platform",
+            HueStatusError::EnvironmentVariableError { .. } => "environment_variable",
+            HueStatusError::PathTooLong { .. } => "path_too_long",
+            HueStatusError::CapacityOverflow { .. } => "capacity_overflow",
+            HueStatusError::Cancelled { .. } => "cancelled",
+        }
+    }
+
+    /// Get user-friendly error message with suggested actions, in the active locale
+    pub fn user_message(&self) -> String {
+        self.user_message_in(Locale::current())
+    }
+
+    /// Get user-friendly error message in a specific locale
+    pub fn user_message_in(&self, locale: Locale) -> String {
+        let msg = |key: &str, args: &[(&str, &str)]| message_in(locale, key, args);
+
+        match self {
+            HueStatusError::ConfigNotFound => msg("error.config_not_found", &[]),
             HueStatusError::InvalidConfig { reason } => {
-                format!("Configuration invalid: {reason}. Run 'huestatus --setup' to fix.")
-            }
-            HueStatusError::ConfigCorrupted => {
-                "Configuration file is corrupted. Run 'huestatus --setup' to recreate.".to_string()
+                msg("error.invalid_config", &[("reason", reason)])
             }
+            HueStatusError::ConfigCorrupted => msg("error.config_corrupted", &[]),
             HueStatusError::ConfigVersionIncompatible => {
-                "Configuration version is incompatible. Run 'huestatus --setup' to update.".to_string()
-            }
-            HueStatusError::BridgeNotFound => {
-                "Hue bridge not found. Check that your bridge is connected and on the same network.".to_string()
+                msg("error.config_version_incompatible", &[])
             }
+            HueStatusError::BridgeNotFound => msg("error.bridge_not_found", &[]),
             HueStatusError::BridgeConnectionFailed { reason } => {
-                format!("Cannot connect to bridge: {reason}. Check network connection.")
-            }
-            HueStatusError::AuthenticationFailed => {
-                "Authentication failed. Run 'huestatus --setup' to re-authenticate.".to_string()
-            }
-            HueStatusError::LinkButtonNotPressed => {
-                "Link button not pressed. Press the button on your Hue bridge and try again.".to_string()
+                msg("error.bridge_connection_failed", &[("reason", reason)])
             }
+            HueStatusError::AuthenticationFailed => msg("error.authentication_failed", &[]),
+            HueStatusError::LinkButtonNotPressed => msg("error.link_button_not_pressed", &[]),
             HueStatusError::SceneNotFound { scene_name } => {
-                format!("Scene '{scene_name}' not found. Run 'huestatus --setup' to recreate scenes.")
+                msg("error.scene_not_found", &[("scene_name", scene_name)])
             }
             HueStatusError::SceneExecutionFailed { reason } => {
-                format!("Scene execution failed: {reason}. Check bridge connection.")
+                msg("error.scene_execution_failed", &[("reason", reason)])
             }
-            HueStatusError::NoLightsFound => {
-                "No lights found. Ensure your Hue bridge has lights connected and they are turned on.".to_string()
+            HueStatusError::AmbiguousSceneName { name, candidates } => msg(
+                "error.ambiguous_scene_name",
+                &[("name", name), ("candidates", candidates)],
+            ),
+            HueStatusError::EmptyScene { scene_name } => {
+                msg("error.empty_scene", &[("scene_name", scene_name)])
             }
-            HueStatusError::SceneStorageLimitExceeded { max_scenes } => {
-                format!("Bridge scene storage full (max: {max_scenes}). Delete some scenes and try again.")
+            HueStatusError::ResourceNotAvailable { resource } => {
+                msg("error.resource_not_available", &[("resource", resource)])
             }
+            HueStatusError::NoLightsFound => msg("error.no_lights_found", &[]),
+            HueStatusError::SceneStorageLimitExceeded { max_scenes } => msg(
+                "error.scene_storage_limit_exceeded",
+                &[("max_scenes", &max_scenes.to_string())],
+            ),
             HueStatusError::TimeoutError { operation } => {
-                format!("Operation timed out: {operation}. Check network connection and try again.")
+                msg("error.timeout", &[("operation", operation)])
             }
             HueStatusError::PermissionDenied { reason } => {
-                format!("Permission denied: {reason}. Check file permissions.")
+                msg("error.permission_denied", &[("reason", reason)])
             }
             HueStatusError::DiscoveryServiceUnreachable { reason } => {
-                format!("Cannot reach Philips discovery service: {reason}. Check internet connection.")
+                msg("error.discovery_service_unreachable", &[("reason", reason)])
             }
             HueStatusError::MdnsDiscoveryFailed { reason } => {
-                format!("mDNS discovery failed: {reason}. Try manual bridge IP entry.")
+                msg("error.mdns_discovery_failed", &[("reason", reason)])
             }
             HueStatusError::SetupFailed { reason } => {
-                format!("Setup failed: {reason}. Please try again.")
+                msg("error.setup_failed", &[("reason", reason)])
             }
             HueStatusError::ValidationFailed { reason } => {
-                format!("Validation failed: {reason}. Run 'huestatus --setup' to fix.")
+                msg("error.validation_failed", &[("reason", reason)])
             }
             HueStatusError::UnsupportedPlatform { platform } => {
-                format!("Platform '{platform}' is not supported.")
-            }
-            HueStatusError::EnvironmentVariableError { var_name } => {
-                format!("Environment variable '{var_name}' is invalid or missing.")
-            }
-            HueStatusError::PathTooLong { path } => {
-                format!("Configuration path is too long: {path}. Try using a shorter path or set HUESTATUS_CONFIG_DIR environment variable.")
+                msg("error.unsupported_platform", &[("platform", platform)])
             }
+            HueStatusError::EnvironmentVariableError { var_name, reason } => msg(
+                "error.environment_variable",
+                &[("var_name", var_name), ("reason", reason)],
+            ),
+            HueStatusError::PathTooLong { path } => msg("error.path_too_long", &[("path", path)]),
             HueStatusError::CapacityOverflow { operation } => {
-                format!("Memory capacity overflow during {operation}. This may be caused by extremely long file paths in WSL environment.")
+                msg("error.capacity_overflow", &[("operation", operation)])
+            }
+            HueStatusError::Cancelled { operation } => {
+                msg("error.cancelled", &[("operation", operation)])
             }
             _ => self.to_string(),
         }
@@ -227,6 +306,8 @@ impl HueStatusError {
                 | HueStatusError::ConfigVersionIncompatible
                 | HueStatusError::AuthenticationFailed
                 | HueStatusError::SceneNotFound { .. }
+                | HueStatusError::EmptyScene { .. }
+                | HueStatusError::ResourceNotAvailable { .. }
                 | HueStatusError::NoLightsFound
                 | HueStatusError::ValidationFailed { .. }
                 | HueStatusError::PathTooLong { .. }
@@ -241,6 +322,7 @@ impl HueStatusError {
                 | HueStatusError::BridgeConnectionFailed { .. }
                 | HueStatusError::NetworkError { .. }
                 | HueStatusError::TimeoutError { .. }
+                | HueStatusError::RateLimited { .. }
                 | HueStatusError::ApiError { .. }
                 | HueStatusError::DiscoveryServiceUnreachable { .. }
                 | HueStatusError::MdnsDiscoveryFailed { .. }
@@ -253,6 +335,7 @@ impl HueStatusError {
             self,
             HueStatusError::NetworkError { .. }
                 | HueStatusError::TimeoutError { .. }
+                | HueStatusError::RateLimited { .. }
                 | HueStatusError::BridgeConnectionFailed { .. }
                 | HueStatusError::SceneExecutionFailed { .. }
                 | HueStatusError::DiscoveryServiceUnreachable { .. }
@@ -298,10 +381,43 @@ pub fn json_error(err: serde_json::Error) -> HueStatusError {
     }
 }
 
+/// Fail with `Cancelled` if the token has been cancelled
+pub(crate) fn check_cancelled(token: Option<&CancellationToken>, operation: &str) -> Result<()> {
+    match token {
+        Some(token) if token.is_cancelled() => Err(HueStatusError::Cancelled {
+            operation: operation.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Wait until the token is cancelled; never completes without a token
+pub(crate) async fn cancelled(token: Option<&CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            HueStatusError::BridgeNotFound.error_code(),
+            "bridge_not_found"
+        );
+        assert_eq!(
+            HueStatusError::EmptyScene {
+                scene_name: "test".to_string()
+            }
+            .error_code(),
+            "empty_scene"
+        );
+    }
+
     #[test]
     fn test_exit_codes() {
         assert_eq!(HueStatusError::ConfigNotFound.exit_code(), 1);
@@ -314,6 +430,30 @@ mod tests {
             .exit_code(),
             4
         );
+        assert_eq!(
+            HueStatusError::Cancelled {
+                operation: "test".to_string()
+            }
+            .exit_code(),
+            130
+        );
+    }
+
+    #[test]
+    fn test_localized_user_messages() {
+        let error = HueStatusError::SceneNotFound {
+            scene_name: "test-scene".to_string(),
+        };
+        assert!(error.user_message_in(Locale::Ja).contains("test-scene"));
+        assert_ne!(
+            error.user_message_in(Locale::Ja),
+            error.user_message_in(Locale::En)
+        );
+
+        // Display stays English for logs
+        assert!(error
+            .to_string()
+            .starts_with("Scene 'test-scene' not found"));
     }
 
     #[test]
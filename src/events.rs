@@ -0,0 +1,51 @@
+use crate::error::{HueStatusError, Result};
+use serde::Deserialize;
+
+/// Status types an event may ask for
+pub const EVENT_STATUSES: &[&str] = &["success", "failure", "running"];
+
+/// One status event, read as a line of JSON such as `{"status":"failure","reason":"tests"}`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StatusEvent {
+    /// Status to show ("success", "failure" or "running")
+    pub status: String,
+    /// Why the status changed, for the log
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl StatusEvent {
+    /// Parse one line of newline-delimited JSON
+    pub fn parse(line: &str) -> Result<Self> {
+        let event: Self = serde_json::from_str(line)?;
+        if !EVENT_STATUSES.contains(&event.status.as_str()) {
+            return Err(HueStatusError::ValidationFailed {
+                reason: format!(
+                    "unknown status '{}' (use {})",
+                    event.status,
+                    EVENT_STATUSES.join(", ")
+                ),
+            });
+        }
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        let event = StatusEvent::parse(r#"{"status":"failure","reason":"tests"}"#).unwrap();
+        assert_eq!(event.status, "failure");
+        assert_eq!(event.reason.as_deref(), Some("tests"));
+
+        let event = StatusEvent::parse(r#"{"status":"success","build":42}"#).unwrap();
+        assert_eq!(event.reason, None);
+
+        assert!(StatusEvent::parse(r#"{"status":"party"}"#).is_err());
+        assert!(StatusEvent::parse(r#"{"reason":"tests"}"#).is_err());
+        assert!(StatusEvent::parse("not json").is_err());
+    }
+}
@@ -0,0 +1,321 @@
+/// Extended troubleshooting help for an error code
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorGuide {
+    /// Error code, as returned by `HueStatusError::error_code`
+    pub code: &'static str,
+    /// One-line description of the problem
+    pub title: &'static str,
+    /// Common reasons for the error
+    pub causes: &'static [&'static str],
+    /// Steps that usually resolve it, most likely first
+    pub fixes: &'static [&'static str],
+}
+
+/// Troubleshooting guides, ordered by error code group
+pub const ERROR_GUIDES: &[ErrorGuide] = &[
+    ErrorGuide {
+        code: "config_not_found",
+        title: "No configuration file was found",
+        causes: &[
+            "huestatus has not been set up on this machine yet",
+            "--config or --project points at a file that doesn't exist",
+            "The configuration directory was moved or deleted",
+        ],
+        fixes: &[
+            "Run: huestatus setup",
+            "Check the expected location with: huestatus config path",
+            "Set HUESTATUS_BRIDGE_IP, HUESTATUS_APP_KEY and the scene ID variables to run without a file",
+        ],
+    },
+    ErrorGuide {
+        code: "invalid_config",
+        title: "The configuration contains an invalid value",
+        causes: &[
+            "A value is out of range (e.g. timeout above 300 seconds)",
+            "Verbose logging and quiet mode are both enabled",
+            "The file was edited by hand and a field is malformed",
+        ],
+        fixes: &[
+            "Fix the reported value with: huestatus config set <key>=<value>",
+            "Inspect the effective settings with: huestatus config show",
+            "Recreate the configuration with: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "config_corrupted",
+        title: "The configuration file is not valid JSON",
+        causes: &[
+            "The file was truncated by an interrupted write",
+            "A manual edit left a syntax error",
+        ],
+        fixes: &[
+            "Check the file at: huestatus config path",
+            "Recreate the configuration with: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "config_version_incompatible",
+        title: "The configuration was written by an incompatible version",
+        causes: &["The file was created by a newer or much older huestatus release"],
+        fixes: &[
+            "Upgrade huestatus to the latest release",
+            "Recreate the configuration with: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "bridge_not_found",
+        title: "No Hue bridge could be found on the network",
+        causes: &[
+            "The bridge is powered off or not connected to the router",
+            "This device is on a different network or VLAN than the bridge",
+            "mDNS and the Philips discovery service are blocked",
+        ],
+        fixes: &[
+            "Ensure your Hue bridge is connected and powered on",
+            "Check that your device is on the same network as the bridge",
+            "Run: huestatus setup",
+        ],
+    },
+    ErrorGuide {
+        code: "bridge_connection_failed",
+        title: "The bridge was found but refused or dropped the connection",
+        causes: &[
+            "The bridge IP address changed (e.g. a new DHCP lease)",
+            "The bridge is rebooting or installing a firmware update",
+            "A firewall blocks HTTP traffic to the bridge",
+        ],
+        fixes: &[
+            "Verify the bridge IP with: huestatus config get bridge.ip",
+            "Wait a minute and try again",
+            "Rediscover the bridge with: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "network_error",
+        title: "A network request to the bridge failed",
+        causes: &[
+            "The network connection is down or unstable",
+            "The bridge uses a self-signed certificate over HTTPS",
+        ],
+        fixes: &[
+            "Check your network connection",
+            "Verify the bridge IP address is correct",
+            "For local bridges with self-signed certificates, try --insecure",
+        ],
+    },
+    ErrorGuide {
+        code: "timeout",
+        title: "The bridge did not answer in time",
+        causes: &[
+            "The bridge is busy or the Wi-Fi link is slow",
+            "The timeout is set too low for this network",
+        ],
+        fixes: &[
+            "Check your network connection",
+            "Try increasing the timeout with --timeout <seconds>",
+            "Measure typical latency with: huestatus bench",
+        ],
+    },
+    ErrorGuide {
+        code: "rate_limited",
+        title: "The bridge is overloaded and asked to slow down",
+        causes: &[
+            "Several tools or scripts are sending commands to the bridge at once",
+            "A status is shown in a tight loop",
+        ],
+        fixes: &[
+            "Wait a moment and try again; huestatus already backs off before retrying",
+            "Space out status commands, or combine them with --stdin-json",
+            "Check for other apps polling the bridge",
+        ],
+    },
+    ErrorGuide {
+        code: "api_error",
+        title: "The bridge rejected the request",
+        causes: &[
+            "A resource referenced by the configuration no longer exists",
+            "The bridge firmware doesn't support the request",
+        ],
+        fixes: &[
+            "Run the command again with --verbose to see the bridge response",
+            "Validate the configuration with: huestatus validate",
+        ],
+    },
+    ErrorGuide {
+        code: "discovery_service_unreachable",
+        title: "The Philips discovery service could not be reached",
+        causes: &[
+            "This device has no internet access",
+            "A proxy or firewall blocks discovery.meethue.com",
+        ],
+        fixes: &[
+            "Check your internet connection",
+            "Run setup on the same network as the bridge so local discovery can find it",
+        ],
+    },
+    ErrorGuide {
+        code: "mdns_discovery_failed",
+        title: "Local mDNS discovery failed",
+        causes: &[
+            "Multicast traffic is blocked on this network",
+            "The bridge is on a different subnet",
+        ],
+        fixes: &[
+            "Allow mDNS (UDP port 5353) on your firewall",
+            "Run: huestatus setup, which falls back to a network scan",
+        ],
+    },
+    ErrorGuide {
+        code: "authentication_failed",
+        title: "The bridge no longer accepts the stored application key",
+        causes: &[
+            "The application key was removed from the bridge",
+            "The bridge was factory reset",
+        ],
+        fixes: &["Re-authenticate with: huestatus setup --force"],
+    },
+    ErrorGuide {
+        code: "link_button_not_pressed",
+        title: "The bridge link button was not pressed during setup",
+        causes: &["Setup asks the bridge for a key, which requires pressing its link button first"],
+        fixes: &[
+            "Press the round link button on top of the bridge",
+            "Run setup again within 30 seconds",
+        ],
+    },
+    ErrorGuide {
+        code: "scene_not_found",
+        title: "A configured status scene doesn't exist on the bridge",
+        causes: &[
+            "The scene was deleted in the Hue app",
+            "The configuration belongs to a different bridge",
+        ],
+        fixes: &[
+            "Rebuild the status scenes with: huestatus scenes refresh",
+            "Or run: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "scene_execution_failed",
+        title: "The bridge did not recall the scene",
+        causes: &[
+            "The lights in the scene are unreachable",
+            "The bridge was briefly unavailable",
+        ],
+        fixes: &[
+            "Check that the lights are powered on",
+            "Try again with --retry-attempts <n>",
+        ],
+    },
+    ErrorGuide {
+        code: "ambiguous_scene_name",
+        title: "Several scenes share the requested name",
+        causes: &["Scenes with the same name exist in different rooms"],
+        fixes: &[
+            "Find the right scene ID with: huestatus scenes list",
+            "Recall it with: huestatus recall --scene-id <id>",
+        ],
+    },
+    ErrorGuide {
+        code: "empty_scene",
+        title: "A status scene has no lights",
+        causes: &["The lights in the scene were removed from the bridge"],
+        fixes: &[
+            "Rebuild the status scenes with: huestatus scenes refresh",
+            "Enable settings.auto_refresh_scenes to rebuild them automatically",
+        ],
+    },
+    ErrorGuide {
+        code: "resource_not_available",
+        title: "The bridge has no scene, light or group with the configured ID",
+        causes: &[
+            "The resource was deleted in the Hue app",
+            "The configuration was copied from a different bridge",
+        ],
+        fixes: &[
+            "Rebuild the status scenes with: huestatus scenes refresh",
+            "Check the recall group with: huestatus groups",
+            "Or run: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "no_lights_found",
+        title: "No color-capable, reachable lights were found",
+        causes: &[
+            "All lights are powered off at the switch",
+            "The bridge only has white-only lights",
+        ],
+        fixes: &[
+            "Power on your color lights and wait until they show as reachable in the Hue app",
+            "Run: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "scene_storage_limit_exceeded",
+        title: "The bridge has no room for more scenes",
+        causes: &["The bridge stores a limited number of scenes"],
+        fixes: &[
+            "Delete unused scenes in the Hue app",
+            "Run: huestatus setup --force",
+        ],
+    },
+    ErrorGuide {
+        code: "permission_denied",
+        title: "A file could not be read or written",
+        causes: &["The configuration directory is owned by another user"],
+        fixes: &[
+            "Check the permissions of: huestatus config path",
+            "Use --config <path> to point at a writable file",
+        ],
+    },
+];
+
+/// Look up the troubleshooting guide for an error code
+pub fn guide(code: &str) -> Option<&'static ErrorGuide> {
+    ERROR_GUIDES.iter().find(|guide| guide.code == code)
+}
+
+impl ErrorGuide {
+    /// Render the guide as plain text
+    pub fn render(&self) -> String {
+        let mut text = format!("{} ({})\n", self.title, self.code);
+
+        text.push_str("\nCommon causes:\n");
+        for cause in self.causes {
+            text.push_str(&format!("  • {cause}\n"));
+        }
+
+        text.push_str("\nHow to fix:\n");
+        for (i, fix) in self.fixes.iter().enumerate() {
+            text.push_str(&format!("  {}. {fix}\n", i + 1));
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::HueStatusError;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_guide_lookup() {
+        let code = HueStatusError::BridgeNotFound.error_code();
+        let guide = guide(code).unwrap();
+        assert_eq!(guide.code, "bridge_not_found");
+        assert!(guide.render().contains("How to fix:"));
+
+        assert!(super::guide("unknown_code").is_none());
+    }
+
+    #[test]
+    fn test_guides_are_complete() {
+        let mut codes = HashSet::new();
+        for guide in ERROR_GUIDES {
+            assert!(codes.insert(guide.code), "duplicate guide {}", guide.code);
+            assert!(!guide.causes.is_empty() && !guide.fixes.is_empty());
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use crate::config::Config;
+use crate::error::{HueStatusError, Result};
+use crate::scenes::SceneExecutionResult;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the execution history, stored next to the configuration
+pub const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// One recorded status command, stored as a single JSON line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the command ran
+    pub timestamp: DateTime<Utc>,
+    /// Status that was shown ("success", "failure" or "running")
+    pub status: String,
+    /// Whether the scene was recalled successfully
+    pub success: bool,
+    /// Recalled scene ID, if a scene was resolved (not for `--color-name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scene_id: Option<String>,
+    /// Execution time in milliseconds
+    #[serde(default)]
+    pub execution_time_ms: u64,
+    /// Time until a light reported the scene state, when measured with `--measure-apply`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_latency_ms: Option<u64>,
+    /// Error code when the command failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Record a completed status command
+    pub fn from_result(status: &str, result: &SceneExecutionResult) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            status: status.to_string(),
+            success: result.success,
+            scene_id: Some(result.scene_id.clone()).filter(|id| !id.is_empty()),
+            execution_time_ms: result.execution_time_ms,
+            apply_latency_ms: result.apply_latency_ms,
+            error: None,
+        }
+    }
+
+    /// Record a status command that failed with an error
+    pub fn from_error(status: &str, error: &HueStatusError) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            status: status.to_string(),
+            success: false,
+            scene_id: None,
+            execution_time_ms: 0,
+            apply_latency_ms: None,
+            error: Some(error.error_code().to_string()),
+        }
+    }
+}
+
+/// Selects which history entries to show
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HistoryFilter {
+    /// Only entries newer than this
+    pub since: Option<Duration>,
+    /// Only the most recent N entries (applied after `since`)
+    pub last: Option<usize>,
+}
+
+impl HistoryFilter {
+    /// Apply the filter to entries in chronological order
+    pub fn apply(&self, entries: Vec<HistoryEntry>, now: DateTime<Utc>) -> Vec<HistoryEntry> {
+        let mut entries: Vec<_> = match self.since {
+            Some(since) => match now.checked_sub_signed(since) {
+                Some(cutoff) => entries
+                    .into_iter()
+                    .filter(|entry| entry.timestamp >= cutoff)
+                    .collect(),
+                // The window reaches back past the earliest representable time
+                None => entries,
+            },
+            None => entries,
+        };
+
+        if let Some(last) = self.last {
+            let skip = entries.len().saturating_sub(last);
+            entries.drain(..skip);
+        }
+
+        entries
+    }
+}
+
+/// Counts of the entries in a history window
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HistorySummary {
+    /// Number of entries
+    pub total: usize,
+    /// Number of entries per status type
+    pub by_status: BTreeMap<String, usize>,
+    /// Number of commands that failed to show their status
+    pub errors: usize,
+}
+
+impl HistorySummary {
+    /// Summarize a set of entries
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let mut summary = Self {
+            total: entries.len(),
+            ..Self::default()
+        };
+        for entry in entries {
+            *summary.by_status.entry(entry.status.clone()).or_default() += 1;
+            if !entry.success {
+                summary.errors += 1;
+            }
+        }
+        summary
+    }
+
+    /// Number of entries with the given status type
+    pub fn count(&self, status: &str) -> usize {
+        self.by_status.get(status).copied().unwrap_or(0)
+    }
+}
+
+/// Get the default history file path
+pub fn history_file_path() -> Result<PathBuf> {
+    Ok(Config::get_config_dir()?.join(HISTORY_FILE_NAME))
+}
+
+/// Append an entry to the history file, creating it if needed
+pub fn append_entry(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Load all entries from the history file, oldest first
+///
+/// A missing file is an empty history; malformed lines (e.g. from an
+/// interrupted write) are skipped.
+pub fn load_entries(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Parse a relative duration such as `30m`, `1h` or `2d`
+pub fn parse_relative_duration(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration '{value}' (expected e.g. 30m, 1h or 2d)"))?;
+
+    let duration = match unit {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => {
+            return Err(format!(
+                "invalid duration unit in '{value}' (use s, m, h, d or w)"
+            ))
+        }
+    };
+    duration.ok_or_else(|| format!("duration '{value}' is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(minutes_ago: i64, status: &str, success: bool, now: DateTime<Utc>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: now - Duration::minutes(minutes_ago),
+            status: status.to_string(),
+            success,
+            scene_id: None,
+            execution_time_ms: 10,
+            apply_latency_ms: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_relative_duration() {
+        assert_eq!(parse_relative_duration("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_relative_duration("1h"), Ok(Duration::hours(1)));
+        assert_eq!(parse_relative_duration("2d"), Ok(Duration::days(2)));
+        assert!(parse_relative_duration("h").is_err());
+        assert!(parse_relative_duration("10").is_err());
+        assert!(parse_relative_duration("5y").is_err());
+    }
+
+    #[test]
+    fn test_parse_relative_duration_overflow() {
+        assert!(parse_relative_duration("9223372036854775807w").is_err());
+        assert!(parse_relative_duration("99999999999999999d").is_err());
+        assert!(parse_relative_duration("99999999999999999999s").is_err());
+
+        // Representable, but reaches back past the earliest DateTime
+        let since = parse_relative_duration("99999999999d").unwrap();
+        let now = Utc::now();
+        let filter = HistoryFilter {
+            since: Some(since),
+            last: None,
+        };
+        assert_eq!(
+            filter
+                .apply(vec![entry(5, "success", true, now)], now)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_filter_and_summary() {
+        let now = Utc::now();
+        let entries = vec![
+            entry(180, "success", true, now),
+            entry(50, "failure", true, now),
+            entry(20, "success", true, now),
+            entry(5, "failure", false, now),
+        ];
+
+        let since = HistoryFilter {
+            since: Some(Duration::hours(1)),
+            last: None,
+        };
+        let window = since.apply(entries.clone(), now);
+        assert_eq!(window.len(), 3);
+
+        let summary = HistorySummary::from_entries(&window);
+        assert_eq!(summary.count("success"), 1);
+        assert_eq!(summary.count("failure"), 2);
+        assert_eq!(summary.errors, 1);
+
+        let last = HistoryFilter {
+            since: Some(Duration::hours(1)),
+            last: Some(2),
+        };
+        let window = last.apply(entries, now);
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].status, "success");
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE_NAME);
+        assert!(load_entries(&path).unwrap().is_empty());
+
+        let now = Utc::now();
+        append_entry(&path, &entry(2, "success", true, now)).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "{truncated\n").unwrap();
+        append_entry(&path, &entry(1, "failure", true, now)).unwrap();
+
+        let entries = load_entries(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].status, "failure");
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::OnceLock;
+
+/// Environment variable selecting the message language
+pub const LANG_ENV_VAR: &str = "HUESTATUS_LANG";
+
+/// Language used for user-facing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+static CURRENT_LOCALE: OnceLock<Locale> = OnceLock::new();
+
+impl Locale {
+    /// Parse a language tag such as "ja", "ja_JP.UTF-8" or "en-US"
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_', '.']).next()?.to_ascii_lowercase();
+
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// Read the locale from `HUESTATUS_LANG`
+    pub fn from_env() -> Option<Self> {
+        std::env::var(LANG_ENV_VAR)
+            .ok()
+            .and_then(|tag| Self::parse(&tag))
+    }
+
+    /// Set the locale for this process (only the first call has an effect)
+    pub fn init(locale: Locale) {
+        let _ = CURRENT_LOCALE.set(locale);
+    }
+
+    /// Get the active locale, falling back to `HUESTATUS_LANG` and then English
+    pub fn current() -> Self {
+        *CURRENT_LOCALE.get_or_init(|| Self::from_env().unwrap_or_default())
+    }
+
+    /// Get the message table for this locale
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Ja => JA,
+        }
+    }
+}
+
+/// Look up a message in the active locale and fill in its `{placeholders}`
+pub fn message(key: &str, args: &[(&str, &str)]) -> String {
+    message_in(Locale::current(), key, args)
+}
+
+/// Look up a message in a specific locale, falling back to English
+pub fn message_in(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    let template = lookup(locale.catalog(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key);
+
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Find a template by key
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog
+        .iter()
+        .find(|(entry_key, _)| *entry_key == key)
+        .map(|(_, template)| *template)
+}
+
+/// English messages (the reference catalog)
+const EN: &[(&str, &str)] = &[
+    ("error.config_not_found", "No configuration found. Run 'huestatus --setup' to get started."),
+    ("error.invalid_config", "Configuration invalid: {reason}. Run 'huestatus --setup' to fix."),
+    ("error.config_corrupted", "Configuration file is corrupted. Run 'huestatus --setup' to recreate."),
+    ("error.config_version_incompatible", "Configuration version is incompatible. Run 'huestatus --setup' to update."),
+    ("error.bridge_not_found", "Hue bridge not found. Check that your bridge is connected and on the same network."),
+    ("error.bridge_connection_failed", "Cannot connect to bridge: {reason}. Check network connection."),
+    ("error.authentication_failed", "Authentication failed. Run 'huestatus --setup' to re-authenticate."),
+    ("error.link_button_not_pressed", "Link button not pressed. Press the button on your Hue bridge and try again."),
+    ("error.scene_not_found", "Scene '{scene_name}' not found. Run 'huestatus --setup' to recreate scenes."),
+    ("error.scene_execution_failed", "Scene execution failed: {reason}. Check bridge connection."),
+    ("error.ambiguous_scene_name", "Several scenes are named '{name}': {candidates}. Use --scene-id to pick one."),
+    ("error.empty_scene", "Scene '{scene_name}' has no lights. Run 'huestatus setup --force' to refresh it."),
+    ("error.resource_not_available", "The bridge has no such resource: {resource}. Run 'huestatus setup --force' if it was deleted."),
+    ("error.no_lights_found", "No lights found. Ensure your Hue bridge has lights connected and they are turned on."),
+    ("error.scene_storage_limit_exceeded", "Bridge scene storage full (max: {max_scenes}). Delete some scenes and try again."),
+    ("error.timeout", "Operation timed out: {operation}. Check network connection and try again."),
+    ("error.permission_denied", "Permission denied: {reason}. Check file permissions."),
+    ("error.discovery_service_unreachable", "Cannot reach Philips discovery service: {reason}. Check internet connection."),
+    ("error.mdns_discovery_failed", "mDNS discovery failed: {reason}. Try manual bridge IP entry."),
+    ("error.setup_failed", "Setup failed: {reason}. Please try again."),
+    ("error.validation_failed", "Validation failed: {reason}. Run 'huestatus --setup' to fix."),
+    ("error.unsupported_platform", "Platform '{platform}' is not supported."),
+    ("error.environment_variable", "Environment variable '{var_name}' is invalid: {reason}."),
+    ("error.path_too_long", "Configuration path is too long: {path}. Try using a shorter path or set HUESTATUS_CONFIG_DIR environment variable."),
+    ("error.capacity_overflow", "Memory capacity overflow during {operation}. This may be caused by extremely long file paths in WSL environment."),
+    ("error.cancelled", "{operation} was cancelled."),
+    ("setup.welcome", "Welcome to huestatus! Let's configure your Philips Hue lights."),
+    ("setup.use_bridge", "Use this bridge?"),
+    ("setup.answer_yes_no", "Please enter 'y' or 'n'"),
+    ("setup.enter_bridge_ip", "Enter bridge IP address: "),
+    ("setup.invalid_ip", "Please enter a valid IP address."),
+    ("setup.discovery_failed", "⚠️ Automatic bridge discovery failed."),
+    ("setup.confirm_network_scan", "Scan the local network for the bridge? This sends a request to every host on the subnet."),
+    ("setup.enter_ip_manually", "Please enter your Hue bridge IP address manually."),
+    ("setup.bridge_ip_prompt", "Bridge IP address: "),
+    ("setup.bridge_found", "✅Bridge found at {ip}"),
+    ("setup.bridge_not_found_at", "❌No Hue bridge found at {ip}. Please try again."),
+    ("setup.press_link_button", "🔑Press the link button on your Hue bridge now."),
+    ("setup.link_button_location", "The button is the large round button on top of the bridge."),
+    ("setup.link_button_deadline", "You have {seconds} seconds to press it..."),
+    ("setup.auth_required", "Authentication Required"),
+    ("setup.auth_connect", "To connect to your Hue bridge at {ip}:"),
+    ("setup.auth_step_press", "1. Press the large round button on top of your bridge"),
+    ("setup.auth_step_blink", "2. The button will start blinking"),
+    ("setup.auth_step_enter", "3. Press Enter within 30 seconds"),
+    ("setup.auth_press_enter", "Press the bridge button now, then press Enter..."),
+    ("setup.confine_to_room", "Show status only in room '{room}'?"),
+    ("setup.config_exists", "⚠️Configuration already exists!"),
+    ("setup.use_force", "Use --force to overwrite the existing configuration."),
+    ("setup.use_validate", "Or use 'huestatus --validate' to check your current setup."),
+];
+
+/// Japanese messages
+const JA: &[(&str, &str)] = &[
+    ("error.config_not_found", "設定が見つかりません。'huestatus --setup' を実行して設定してください。"),
+    ("error.invalid_config", "設定が無効です: {reason}。'huestatus --setup' を実行して修正してください。"),
+    ("error.config_corrupted", "設定ファイルが壊れています。'huestatus --setup' を実行して作り直してください。"),
+    ("error.config_version_incompatible", "設定のバージョンに互換性がありません。'huestatus --setup' を実行して更新してください。"),
+    ("error.bridge_not_found", "Hue ブリッジが見つかりません。ブリッジが接続され、同じネットワーク上にあるか確認してください。"),
+    ("error.bridge_connection_failed", "ブリッジに接続できません: {reason}。ネットワーク接続を確認してください。"),
+    ("error.authentication_failed", "認証に失敗しました。'huestatus --setup' を実行して再認証してください。"),
+    ("error.link_button_not_pressed", "リンクボタンが押されていません。Hue ブリッジのボタンを押してから再試行してください。"),
+    ("error.scene_not_found", "シーン '{scene_name}' が見つかりません。'huestatus --setup' を実行してシーンを作り直してください。"),
+    ("error.scene_execution_failed", "シーンの実行に失敗しました: {reason}。ブリッジとの接続を確認してください。"),
+    ("error.ambiguous_scene_name", "'{name}' という名前のシーンが複数あります: {candidates}。--scene-id で指定してください。"),
+    ("error.empty_scene", "シーン '{scene_name}' にライトがありません。'huestatus setup --force' を実行してシーンを更新してください。"),
+    ("error.resource_not_available", "ブリッジにリソースが存在しません: {resource}。削除された場合は 'huestatus setup --force' を実行してください。"),
+    ("error.no_lights_found", "ライトが見つかりません。Hue ブリッジにライトが接続され、電源が入っているか確認してください。"),
+    ("error.scene_storage_limit_exceeded", "ブリッジのシーン保存領域がいっぱいです (最大: {max_scenes})。シーンを削除してから再試行してください。"),
+    ("error.timeout", "操作がタイムアウトしました: {operation}。ネットワーク接続を確認して再試行してください。"),
+    ("error.permission_denied", "アクセスが拒否されました: {reason}。ファイルの権限を確認してください。"),
+    ("error.discovery_service_unreachable", "Philips の検出サービスに接続できません: {reason}。インターネット接続を確認してください。"),
+    ("error.mdns_discovery_failed", "mDNS による検出に失敗しました: {reason}。ブリッジの IP アドレスを手動で入力してください。"),
+    ("error.setup_failed", "セットアップに失敗しました: {reason}。もう一度お試しください。"),
+    ("error.validation_failed", "検証に失敗しました: {reason}。'huestatus --setup' を実行して修正してください。"),
+    ("error.unsupported_platform", "プラットフォーム '{platform}' はサポートされていません。"),
+    ("error.environment_variable", "環境変数 '{var_name}' が無効です: {reason}。"),
+    ("error.path_too_long", "設定ファイルのパスが長すぎます: {path}。短いパスを使うか、環境変数 HUESTATUS_CONFIG_DIR を設定してください。"),
+    ("error.capacity_overflow", "{operation} の実行中にメモリ容量があふれました。WSL 環境での非常に長いファイルパスが原因の可能性があります。"),
+    ("error.cancelled", "{operation} はキャンセルされました。"),
+    ("setup.welcome", "huestatus へようこそ! Philips Hue ライトを設定しましょう。"),
+    ("setup.use_bridge", "このブリッジを使用しますか?"),
+    ("setup.answer_yes_no", "'y' か 'n' を入力してください"),
+    ("setup.enter_bridge_ip", "ブリッジの IP アドレスを入力: "),
+    ("setup.invalid_ip", "有効な IP アドレスを入力してください。"),
+    ("setup.discovery_failed", "⚠️ ブリッジを自動検出できませんでした。"),
+    ("setup.confirm_network_scan", "ローカルネットワークをスキャンしてブリッジを探しますか? サブネット上のすべてのホストにリクエストを送信します。"),
+    ("setup.enter_ip_manually", "Hue ブリッジの IP アドレスを手動で入力してください。"),
+    ("setup.bridge_ip_prompt", "ブリッジの IP アドレス: "),
+    ("setup.bridge_found", "✅{ip} でブリッジが見つかりました"),
+    ("setup.bridge_not_found_at", "❌{ip} に Hue ブリッジが見つかりません。もう一度お試しください。"),
+    ("setup.press_link_button", "🔑今すぐ Hue ブリッジのリンクボタンを押してください。"),
+    ("setup.link_button_location", "ボタンはブリッジ上部にある大きな丸いボタンです。"),
+    ("setup.link_button_deadline", "{seconds} 秒以内に押してください..."),
+    ("setup.auth_required", "認証が必要です"),
+    ("setup.auth_connect", "{ip} の Hue ブリッジに接続するには:"),
+    ("setup.auth_step_press", "1. ブリッジ上部の大きな丸いボタンを押します"),
+    ("setup.auth_step_blink", "2. ボタンが点滅し始めます"),
+    ("setup.auth_step_enter", "3. 30 秒以内に Enter を押します"),
+    ("setup.auth_press_enter", "ブリッジのボタンを押してから Enter を押してください..."),
+    ("setup.confine_to_room", "ステータスを部屋 '{room}' だけに表示しますか?"),
+    ("setup.config_exists", "⚠️設定はすでに存在します!"),
+    ("setup.use_force", "既存の設定を上書きするには --force を使用してください。"),
+    ("setup.use_validate", "現在の設定を確認するには 'huestatus --validate' を使用してください。"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_parse() {
+        assert_eq!(Locale::parse("ja"), Some(Locale::Ja));
+        assert_eq!(Locale::parse("ja_JP.UTF-8"), Some(Locale::Ja));
+        assert_eq!(Locale::parse("en-US"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr"), None);
+    }
+
+    #[test]
+    fn test_message_formatting() {
+        let text = message_in(Locale::En, "error.scene_not_found", &[("scene_name", "ok")]);
+        assert_eq!(
+            text,
+            "Scene 'ok' not found. Run 'huestatus --setup' to recreate scenes."
+        );
+
+        let text = message_in(Locale::Ja, "setup.bridge_found", &[("ip", "10.0.0.2")]);
+        assert!(text.contains("10.0.0.2"));
+
+        assert_eq!(message_in(Locale::Ja, "missing.key", &[]), "missing.key");
+    }
+
+    #[test]
+    fn test_catalogs_have_matching_keys() {
+        for (key, _) in EN {
+            assert!(lookup(JA, key).is_some(), "missing Japanese message: {key}");
+        }
+        assert_eq!(EN.len(), JA.len());
+    }
+}
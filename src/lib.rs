@@ -1,10 +1,19 @@
+pub mod app;
 pub mod bridge;
 pub mod config;
 pub mod error;
+pub mod events;
+pub mod explain;
+pub mod history;
+pub mod i18n;
+pub mod metrics;
+pub mod output;
 pub mod scenes;
 pub mod setup;
 
+pub use app::Huestatus;
 pub use error::{HueStatusError, Result};
+pub use tokio_util::sync::CancellationToken;
 
 /// Application version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
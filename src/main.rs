@@ -1,13 +1,28 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgMatches, Command};
 use console::style;
 use huestatus::{
-    bridge::BridgeClient,
-    config::Config,
+    bridge::{
+        parse_brightness, percent_to_bri, AlertMode, BrightnessChange, CreateSceneRequest,
+        HUESTATUS_SCENE_PREFIX, REMOTE_TOKEN_ENV_VAR,
+    },
+    config::{
+        load_config, load_config_from_path_or_default, mask_key, save_config,
+        validate_project_name, validate_scene_name, BreathingEffect, ColorConfig, ColorsConfig,
+        Config, DISCOVERY_URL_ENV_VAR,
+    },
     error::{HueStatusError, Result},
-    scenes::SceneManager,
-    setup::{SetupOptions, SetupProcess},
-    APP_DESCRIPTION, APP_NAME, VERSION,
+    events::StatusEvent,
+    explain,
+    history::{self, HistoryEntry, HistoryFilter, HistorySummary},
+    i18n::Locale,
+    metrics,
+    output::{self, OutputFormat, Table},
+    scenes::{ColorDefinition, ColorPresets, LightStatus, SceneCreator},
+    setup::{self, ConfigRepair, SetupOptions, SetupProcess},
+    Huestatus, APP_DESCRIPTION, APP_NAME, VERSION,
 };
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
 
 /// CLI application entry point
@@ -22,39 +37,51 @@ async fn main() {
 
     // Parse command line arguments
     let matches = create_cli().get_matches();
+    if let Err(e) = validate_arg_combinations(&matches) {
+        e.exit();
+    }
+
+    // Select the message language before anything is printed
+    if let Some(locale) = matches.get_one::<Locale>("lang") {
+        Locale::init(*locale);
+    }
+
+    // Resolve the config file for `--project` before any config access
+    if let Some(project) = matches.get_one::<String>("project") {
+        Config::init_project(Some(project.clone()));
+    }
+
+    if matches.get_flag("no-color") {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
 
     // Extract global options
-    let verbose = matches.get_flag("verbose");
-    let quiet = matches.get_flag("quiet");
-    let _config_path = matches.get_one::<String>("config").cloned();
-    let timeout = matches.get_one::<u64>("timeout").copied().unwrap_or(10);
-    let retry_attempts = matches
-        .get_one::<usize>("retry-attempts")
-        .copied()
-        .unwrap_or(3);
-    let retry_delay = matches.get_one::<u64>("retry-delay").copied().unwrap_or(1);
+    let options = GlobalOptions::from_matches(&matches);
+    let verbose = options.verbose;
+    let quiet = options.quiet;
+    let show_suggestions = !matches.get_flag("no-suggestions");
+
+    // Give a freshly booted network time to come up before talking to the bridge
+    if let Some(max_wait) = options.wait_for_bridge {
+        if matches
+            .subcommand_name()
+            .is_some_and(|name| BRIDGE_COMMANDS.contains(&name))
+            || matches.get_flag("stdin-json")
+        {
+            if let Err(e) = wait_for_bridge(&options, max_wait).await {
+                exit_with_error(&e, quiet, verbose, show_suggestions);
+            }
+        }
+    }
 
     // Run the appropriate command
     let result = match matches.subcommand() {
-        Some(("success", _)) => {
-            execute_status_command(
-                "success",
-                verbose,
-                quiet,
-                timeout,
-                retry_attempts,
-                retry_delay,
-            )
-            .await
-        }
-        Some(("failure", _)) => {
+        Some((status_type @ ("success" | "failure" | "running"), status_matches)) => {
             execute_status_command(
-                "failure",
-                verbose,
-                quiet,
-                timeout,
-                retry_attempts,
-                retry_delay,
+                status_type,
+                &options,
+                StatusArgs::from_matches(status_matches),
             )
             .await
         }
@@ -62,18 +89,192 @@ async fn main() {
             let force = setup_matches.get_flag("force");
             let interactive = !setup_matches.get_flag("non-interactive");
             let test_scenes = setup_matches.get_flag("test");
+            let first_match = setup_matches.get_flag("first-match");
+            let network_scan = setup_matches.get_flag("network-scan");
+            let check_only = setup_matches.get_flag("check-only");
+            let remote_token = setup_matches
+                .get_one::<String>("remote-token")
+                .cloned()
+                .or_else(|| std::env::var(REMOTE_TOKEN_ENV_VAR).ok())
+                .filter(|token| !token.is_empty());
 
-            execute_setup_command(SetupOptions {
+            let setup_options = SetupOptions {
                 force,
                 interactive,
                 verbose,
                 test_scenes,
+                first_match,
+                network_scan,
+                insecure: options.insecure,
+                min_brightness: options.min_brightness,
+                brightness: options.brightness,
+                check_only,
+                group_id: options.group.clone(),
+                assume_yes: options.assume_yes,
+                discovery_url: setup_discovery_url(&options),
+                remote_token,
+                strict_scene_names: setup_matches.get_flag("strict"),
                 ..SetupOptions::default()
-            })
-            .await
+            };
+            if setup_options.insecure {
+                warn_insecure();
+            }
+            // Keep stdout for the JSON document
+            setup::init_progress_to_stderr(options.json);
+
+            execute_setup_command(setup_options, &options).await
+        }
+        Some(("recall", recall_matches)) => {
+            let target =
+                if let Some(color) = recall_matches.get_one::<ColorDefinition>("color-name") {
+                    SceneTarget::Color(color.clone())
+                } else if let Some(scene_id) = recall_matches.get_one::<String>("scene-id") {
+                    SceneTarget::Id(scene_id.clone())
+                } else {
+                    SceneTarget::Name(
+                        recall_matches
+                            .get_one::<String>("scene")
+                            .expect("scene, scene-id or color-name is required")
+                            .clone(),
+                    )
+                };
+            execute_recall_command(&options, target).await
+        }
+        Some(("groups", _)) => execute_groups_command(&options).await,
+        Some(("config", config_matches)) => match config_matches.subcommand() {
+            Some(("path", _)) => execute_config_path_command(&options),
+            Some(("show", show_matches)) if show_matches.get_flag("diff") => {
+                execute_config_diff_command(&options)
+            }
+            Some(("show", _)) => execute_config_show_command(&options),
+            Some(("get", get_matches)) => {
+                let key = get_matches
+                    .get_one::<String>("key")
+                    .expect("key is required");
+                execute_config_get_command(&options, key)
+            }
+            Some(("set", set_matches)) => {
+                let assignment = set_matches
+                    .get_one::<String>("assignment")
+                    .expect("assignment is required");
+                execute_config_set_command(&options, assignment)
+            }
+            _ => unreachable!("config requires a subcommand"),
+        },
+        Some(("scenes", scenes_matches)) => match scenes_matches.subcommand() {
+            Some(("list", _)) => execute_scenes_list_command(&options).await,
+            Some(("refresh", refresh_matches)) => {
+                let saved = match refresh_matches.get_one::<String>("effect") {
+                    Some(effect) => save_effect(
+                        &options,
+                        effect,
+                        refresh_matches.get_one::<u8>("effect-min").copied(),
+                        refresh_matches.get_one::<u8>("effect-max").copied(),
+                    ),
+                    None => Ok(()),
+                };
+                match saved {
+                    Ok(()) => {
+                        execute_scenes_refresh_command(
+                            &options,
+                            refresh_matches.get_flag("force"),
+                            refresh_matches.get_flag("strict"),
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Some(("export", export_matches)) => {
+                execute_scenes_export_command(
+                    &options,
+                    export_matches
+                        .get_one::<String>("status")
+                        .expect("status is required"),
+                    export_matches.get_one::<PathBuf>("out"),
+                )
+                .await
+            }
+            Some(("import", import_matches)) => {
+                execute_scenes_import_command(
+                    &options,
+                    import_matches
+                        .get_one::<String>("status")
+                        .expect("status is required"),
+                    import_matches
+                        .get_one::<PathBuf>("from-file")
+                        .expect("from-file is required"),
+                )
+                .await
+            }
+            Some(("clone", clone_matches)) => {
+                execute_scenes_clone_command(
+                    &options,
+                    clone_matches
+                        .get_one::<String>("source-id")
+                        .expect("source-id is required"),
+                    clone_matches
+                        .get_one::<String>("new-name")
+                        .expect("new-name is required"),
+                    clone_matches.get_one::<u8>("brightness").copied(),
+                    clone_matches.get_one::<ColorDefinition>("color-name"),
+                )
+                .await
+            }
+            Some(("gradient", gradient_matches)) => {
+                let colors: Vec<String> = gradient_matches
+                    .get_many::<String>("colors")
+                    .unwrap_or_default()
+                    .cloned()
+                    .collect();
+                execute_scenes_gradient_command(
+                    &options,
+                    gradient_matches
+                        .get_one::<String>("status")
+                        .expect("status is required"),
+                    &colors,
+                )
+                .await
+            }
+            _ => unreachable!("scenes requires a subcommand"),
+        },
+        Some(("off", _)) => execute_off_command(&options).await,
+        Some(("brightness", brightness_matches)) => {
+            let change = *brightness_matches
+                .get_one::<BrightnessChange>("change")
+                .expect("change is required");
+            execute_brightness_command(&options, change).await
+        }
+        Some(("bench", bench_matches)) => {
+            let iterations = *bench_matches
+                .get_one::<u32>("iterations")
+                .expect("iterations has a default");
+            execute_bench_command(&options, iterations as usize).await
+        }
+        Some(("validate", validate_matches)) => {
+            execute_validate_command(&options, validate_matches.get_flag("show-states")).await
+        }
+        Some(("explain", explain_matches)) => {
+            execute_explain_command(&options, explain_matches.get_one::<String>("code"))
+        }
+        Some(("history", history_matches)) => {
+            let filter = HistoryFilter {
+                since: history_matches
+                    .get_one::<chrono::Duration>("since")
+                    .copied(),
+                last: history_matches
+                    .get_one::<u32>("last")
+                    .map(|last| *last as usize),
+            };
+            execute_history_command(&options, filter)
         }
-        Some(("validate", _)) => execute_validate_command(verbose).await,
-        Some(("doctor", _)) => execute_doctor_command().await,
+        Some(("metrics", _)) => execute_metrics_command(&options).await,
+        Some(("doctor", doctor_matches)) => {
+            // Keep stdout for the JSON document
+            setup::init_progress_to_stderr(options.json);
+            execute_doctor_command(&options, doctor_matches.get_flag("fix")).await
+        }
+        _ if matches.get_flag("stdin-json") => execute_stdin_json_command(&options).await,
         _ => {
             // No subcommand provided, show help
             let mut cmd = create_cli();
@@ -86,20 +287,239 @@ async fn main() {
     // Handle result and exit
     match result {
         Ok(()) => process::exit(0),
-        Err(e) => {
-            if !quiet {
-                eprintln!("{}", format_error(&e));
+        Err(e) => exit_with_error(&e, quiet, verbose, show_suggestions),
+    }
+}
+
+/// Report an error and exit with its exit code
+fn exit_with_error(e: &HueStatusError, quiet: bool, verbose: bool, show_suggestions: bool) -> ! {
+    if !quiet {
+        eprintln!("{}", format_error(e));
+
+        if verbose {
+            eprintln!("\nDebug information:");
+            eprintln!("Error type: {e:?}");
+            eprintln!("Exit code: {}", e.exit_code());
+        }
+
+        // Show helpful suggestions
+        if show_suggestions {
+            show_error_suggestions(e);
+        }
+    }
+    process::exit(e.exit_code());
+}
+
+/// Commands that talk to the configured bridge, and so honor `--wait-for-bridge`
+const BRIDGE_COMMANDS: &[&str] = &[
+    "success",
+    "failure",
+    "running",
+    "recall",
+    "groups",
+    "scenes",
+    "off",
+    "brightness",
+    "bench",
+    "validate",
+    "metrics",
+];
+
+/// Reject flag combinations that clap can't express across subcommand levels
+///
+/// Runs before any work, so a command line that would silently ignore a
+/// flag fails with a usage error instead.
+fn validate_arg_combinations(matches: &ArgMatches) -> std::result::Result<(), clap::Error> {
+    let conflict = |message: String| {
+        Err(create_cli().error(clap::error::ErrorKind::ArgumentConflict, message))
+    };
+    let subcommand = matches.subcommand_name();
+
+    if let Some(name) = subcommand.filter(|_| matches.get_flag("stdin-json")) {
+        return conflict(format!(
+            "--stdin-json reads statuses from stdin and can't be combined with the '{name}' subcommand"
+        ));
+    }
+
+    if let Some(name) = subcommand.filter(|name| !BRIDGE_COMMANDS.contains(name)) {
+        if matches.get_one::<u64>("wait-for-bridge").is_some() {
+            return conflict(format!(
+                "--wait-for-bridge only applies to commands that use the configured bridge, not '{name}'"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait until the configured bridge answers, for `--wait-for-bridge`
+async fn wait_for_bridge(options: &GlobalOptions, max_wait: u64) -> Result<()> {
+    let config = options.load_config()?;
+    Huestatus::new(config)?
+        .client()
+        .wait_until_reachable(std::time::Duration::from_secs(max_wait))
+        .await
+}
+
+/// Global command-line options shared by all commands
+struct GlobalOptions {
+    verbose: bool,
+    quiet: bool,
+    insecure: bool,
+    summary: bool,
+    json: bool,
+    compact: bool,
+    assume_yes: bool,
+    format: OutputFormat,
+    config_path: Option<String>,
+    timeout: Option<u64>,
+    retry_attempts: Option<usize>,
+    retry_delay: Option<u64>,
+    transition_time: Option<u16>,
+    min_brightness: Option<u8>,
+    brightness: Option<u8>,
+    group: Option<String>,
+    wait_for_bridge: Option<u64>,
+    output_file: Option<PathBuf>,
+}
+
+impl GlobalOptions {
+    /// Extract global options from parsed arguments
+    fn from_matches(matches: &ArgMatches) -> Self {
+        Self {
+            verbose: matches.get_flag("verbose"),
+            quiet: matches.get_flag("quiet"),
+            insecure: matches.get_flag("insecure"),
+            summary: matches.get_flag("summary"),
+            json: matches.get_flag("json"),
+            compact: matches.get_flag("compact"),
+            assume_yes: matches.get_flag("yes"),
+            format: matches
+                .get_one::<OutputFormat>("format")
+                .copied()
+                .unwrap_or_default(),
+            config_path: matches.get_one::<String>("config").cloned(),
+            output_file: matches.get_one::<PathBuf>("output-file").cloned(),
+            timeout: matches.get_one::<u64>("timeout").copied(),
+            retry_attempts: matches.get_one::<usize>("retry-attempts").copied(),
+            retry_delay: matches.get_one::<u64>("retry-delay").copied(),
+            transition_time: matches.get_one::<u16>("transition-time").copied(),
+            min_brightness: matches.get_one::<u8>("min-brightness").copied(),
+            brightness: matches
+                .get_one::<u8>("brightness-percent")
+                .map(|percent| percent_to_bri(*percent)),
+            group: matches.get_one::<String>("group").cloned(),
+            wait_for_bridge: matches.get_one::<u64>("wait-for-bridge").copied(),
+        }
+    }
 
-                if verbose {
-                    eprintln!("\nDebug information:");
-                    eprintln!("Error type: {e:?}");
-                    eprintln!("Exit code: {}", e.exit_code());
+    /// Load configuration from `--config` (`-` reads stdin) or the default location
+    ///
+    /// Without a config file, a complete set of `HUESTATUS_*` environment
+    /// variables is used instead (see `Config::from_env`). Settings resolve
+    /// as command-line flag > environment variable > config file.
+    fn load_config(&self) -> Result<Config> {
+        let mut config =
+            match load_config_from_path_or_default(self.config_path.as_deref().map(Path::new)) {
+                Err(HueStatusError::ConfigNotFound)
+                    if self.config_path.is_none() && Config::env_config_available() =>
+                {
+                    Config::from_env()?
                 }
+                result => result?,
+            };
+
+        config.apply_env_overrides()?;
+        self.apply_overrides(&mut config);
+        config.validate()?;
+
+        if config.settings.insecure {
+            warn_insecure();
+        }
+
+        Ok(config)
+    }
+
+    /// Serialize JSON output, indented unless `--compact` is given
+    fn to_json<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<String> {
+        if self.compact {
+            Ok(serde_json::to_string(value)?)
+        } else {
+            Ok(serde_json::to_string_pretty(value)?)
+        }
+    }
+
+    /// Serialize a `--json` command result, wrapped with the schema version
+    fn json_output<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<String> {
+        self.to_json(&output::versioned(value))
+    }
 
-                // Show helpful suggestions
-                show_error_suggestions(&e);
+    /// Print a `--json` or `--summary` result, or write it to `--output-file`
+    fn emit(&self, text: &str) -> Result<()> {
+        match &self.output_file {
+            Some(path) => output::write_atomically(path, &format!("{text}\n")),
+            None => {
+                println!("{text}");
+                Ok(())
             }
-            process::exit(e.exit_code());
+        }
+    }
+
+    /// Print human-readable output, on stderr when stdout carries the `--json` result
+    fn print_human(&self, text: &str) {
+        if self.json {
+            eprintln!("{text}");
+        } else {
+            println!("{text}");
+        }
+    }
+
+    /// Config file that changes can be written back to (not stdin or environment-only setups)
+    fn writable_config_path(&self) -> Option<PathBuf> {
+        match self.config_path.as_deref() {
+            Some("-") => None,
+            Some(path) => Some(PathBuf::from(path)),
+            None => Config::get_config_file_path()
+                .ok()
+                .filter(|path| path.exists()),
+        }
+    }
+
+    /// Apply command-line flags on top of the loaded settings
+    fn apply_overrides(&self, config: &mut Config) {
+        let settings = &mut config.settings;
+
+        if let Some(timeout) = self.timeout {
+            settings.timeout_seconds = timeout;
+        }
+        if let Some(retry_attempts) = self.retry_attempts {
+            settings.retry_attempts = retry_attempts;
+        }
+        if let Some(retry_delay) = self.retry_delay {
+            settings.retry_delay_seconds = retry_delay;
+        }
+        if let Some(transition_time) = self.transition_time {
+            settings.transition_time = Some(transition_time);
+        }
+        if let Some(min_brightness) = self.min_brightness {
+            settings.min_brightness = min_brightness;
+        }
+        if let Some(brightness) = self.brightness {
+            config.colors.set_brightness(brightness);
+        }
+        if let Some(group) = &self.group {
+            settings.recall_group_id = group.clone();
+        }
+        if self.insecure {
+            settings.insecure = true;
+        }
+        if self.verbose {
+            settings.verbose_logging = true;
+            settings.quiet_mode = false;
+        }
+        if self.quiet {
+            settings.quiet_mode = true;
+            settings.verbose_logging = false;
         }
     }
 }
@@ -112,6 +532,7 @@ fn create_cli() -> Command {
         .author("mimikun <mimikun@users.noreply.github.com>")
         .long_about("A CLI tool for displaying build status using Philips Hue lights.\n\nUse your Hue lights to show success (green) or failure (red) status for CI/CD pipelines, builds, tests, and more.")
         .arg_required_else_help(true)
+        .after_help("Settings are resolved as: command-line flag > HUESTATUS_* environment variable > configuration file.")
         .arg(
             Arg::new("verbose")
                 .short('v')
@@ -129,12 +550,78 @@ fn create_cli() -> Command {
                 .global(true)
                 .conflicts_with("verbose"),
         )
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print a single result line to stdout, even with --quiet")
+                .global(true),
+        )
+        .arg(
+            Arg::new("output-file")
+                .long("output-file")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the --json or --summary result to a file instead of stdout")
+                .global(true),
+        )
+        .arg(
+            Arg::new("stdin-json")
+                .long("stdin-json")
+                .action(clap::ArgAction::SetTrue)
+                .help("Read newline-delimited JSON events like {\"status\":\"failure\",\"reason\":\"tests\"} from stdin and show each status"),
+        )
+        .arg(
+            Arg::new("insecure")
+                .long("insecure")
+                .action(clap::ArgAction::SetTrue)
+                .help("Disable TLS certificate verification for an HTTPS bridge with a self-signed certificate (discovery and the Hue cloud are always verified)")
+                .global(true),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print command results as JSON")
+                .long_help(
+                    "Print command results as JSON, wrapped as \
+                     {\"schema_version\": N, \"data\": ...}. schema_version is \
+                     incremented on breaking changes to any command's output.",
+                )
+                .global(true)
+                .conflicts_with("summary"),
+        )
+        .arg(
+            Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .visible_alias("assume-yes")
+                .action(clap::ArgAction::SetTrue)
+                .help("Answer yes to all confirmation prompts (for unattended use); the network scan still needs --network-scan")
+                .global(true),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print JSON on a single line instead of indented (for piping)")
+                .global(true),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(|value: &str| value.parse::<OutputFormat>())
+                .help("Layout for list commands: plain, table [default: plain; table falls back to plain when not a terminal]")
+                .global(true)
+                .conflicts_with("json"),
+        )
         .arg(
             Arg::new("config")
                 .short('c')
                 .long("config")
                 .value_name("FILE")
-                .help("Use custom configuration file")
+                .help("Use custom configuration file ('-' reads it from stdin)")
                 .global(true),
         )
         .arg(
@@ -162,15 +649,400 @@ fn create_cli() -> Command {
                 .help("Delay between retries in seconds [default: 1]")
                 .global(true),
         )
+        .arg(
+            Arg::new("transition-time")
+                .long("transition-time")
+                .value_name("DECISECONDS")
+                .value_parser(clap::value_parser!(u16))
+                .help("Scene transition time in 1/10 s (0 = instant; the bridge caps very large values)")
+                .global(true),
+        )
+        .arg(
+            Arg::new("lang")
+                .long("lang")
+                .value_name("LANG")
+                .value_parser(parse_locale)
+                .help("Message language: en, ja [default: HUESTATUS_LANG or en]")
+                .global(true),
+        )
+        .arg(
+            Arg::new("project")
+                .long("project")
+                .value_name("NAME")
+                .value_parser(parse_project)
+                .conflicts_with("config")
+                .help("Use the per-project config file projects/<NAME>.json [env: HUESTATUS_PROJECT]")
+                .global(true),
+        )
+        .arg(
+            Arg::new("wait-for-bridge")
+                .long("wait-for-bridge")
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Wait up to SECONDS for the bridge to become reachable before running the command")
+                .global(true),
+        )
+        .arg(
+            Arg::new("no-color")
+                .long("no-color")
+                .action(clap::ArgAction::SetTrue)
+                .help("Disable colored output")
+                .global(true),
+        )
+        .arg(
+            Arg::new("no-suggestions")
+                .long("no-suggestions")
+                .visible_alias("no-setup-suggestion")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print errors without the troubleshooting suggestions")
+                .global(true),
+        )
+        .arg(
+            Arg::new("min-brightness")
+                .long("min-brightness")
+                .value_name("BRI")
+                .value_parser(parse_min_brightness)
+                .help("Lowest brightness used when creating status scenes: 1-254 or a percentage like 10% [default: 30]")
+                .global(true),
+        )
+        .arg(
+            Arg::new("brightness-percent")
+                .long("brightness-percent")
+                .value_name("PERCENT")
+                .value_parser(clap::value_parser!(u8).range(1..=100))
+                .help("Brightness of the status scenes when creating them, 1-100 (100% is the Hue maximum of 254)")
+                .global(true),
+        )
+        .arg(
+            Arg::new("group")
+                .short('g')
+                .long("group")
+                .value_name("GROUP_ID")
+                .help("Group to show the status on; setup only uses its lights [default: from config, \"0\" = all lights]")
+                .global(true),
+        )
         .subcommand(
             Command::new("success")
                 .about("Show success status (green lights)")
-                .long_about("Activate the success scene to display green lights, indicating a successful build, test, or operation."),
+                .long_about("Activate the success scene to display green lights, indicating a successful build, test, or operation.")
+                .arg(verify_change_arg())
+                .arg(measure_apply_arg())
+                .arg(only_on_change_arg())
+                .arg(max_verification_age_arg())
+                .arg(confirm_arg())
+                .arg(color_name_arg())
+                .arg(lights_arg())
+                .arg(color_temp_arg())
+                .arg(hold_arg()),
         )
         .subcommand(
             Command::new("failure")
                 .about("Show failure status (red lights)")
-                .long_about("Activate the failure scene to display red lights, indicating a failed build, test, or operation."),
+                .long_about("Activate the failure scene to display red lights, indicating a failed build, test, or operation.")
+                .arg(verify_change_arg())
+                .arg(measure_apply_arg())
+                .arg(only_on_change_arg())
+                .arg(max_verification_age_arg())
+                .arg(confirm_arg())
+                .arg(color_name_arg())
+                .arg(lights_arg())
+                .arg(color_temp_arg())
+                .arg(hold_arg())
+                .arg(alert_arg()),
+        )
+        .subcommand(
+            Command::new("running")
+                .about("Show running status (amber lights)")
+                .long_about("Activate the running scene to display amber lights, indicating a build, test, or operation in progress. Configs created before this scene existed gain it on `huestatus scenes refresh`.")
+                .arg(verify_change_arg())
+                .arg(measure_apply_arg())
+                .arg(only_on_change_arg())
+                .arg(max_verification_age_arg())
+                .arg(confirm_arg())
+                .arg(color_name_arg())
+                .arg(lights_arg())
+                .arg(color_temp_arg())
+                .arg(hold_arg()),
+        )
+        .subcommand(
+            Command::new("recall")
+                .about("Recall an arbitrary scene")
+                .long_about("Recall a scene by ID or name without using the configured success/failure scenes. Useful for testing scenes created outside huestatus.")
+                .arg(
+                    Arg::new("scene-id")
+                        .long("scene-id")
+                        .value_name("ID")
+                        .help("ID of the scene to recall"),
+                )
+                .arg(
+                    Arg::new("scene")
+                        .short('s')
+                        .long("scene")
+                        .value_name("NAME")
+                        .help("Name of the scene to recall (case-insensitive, must be unique)"),
+                )
+                .arg(color_name_arg())
+                .group(
+                    clap::ArgGroup::new("target")
+                        .args(["scene-id", "scene", "color-name"])
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("groups")
+                .about("List bridge groups and rooms")
+                .long_about("List the groups and rooms on the bridge, to find an ID for --group or recall_group_id."),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Inspect or change the configuration")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("path").about("Print the configuration file path"),
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Print the effective configuration (application key masked)")
+                        .arg(
+                            Arg::new("diff")
+                                .long("diff")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Only list settings that differ from the defaults"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("get")
+                        .about("Print a single configuration value")
+                        .long_about("Print the effective value of a configuration key by its dotted key path, e.g. `huestatus config get settings.timeout_seconds`.")
+                        .arg(
+                            Arg::new("key")
+                                .value_name("KEY")
+                                .help("Dotted key path")
+                                .required(true),
+                        ),
+                )
+                .subcommand(
+                    Command::new("set")
+                        .about("Change a single configuration value")
+                        .long_about("Set a configuration value by its dotted key path, e.g. `huestatus config set settings.timeout_seconds=20`. The change is validated before the file is saved.")
+                        .arg(
+                            Arg::new("assignment")
+                                .value_name("KEY=VALUE")
+                                .help("Dotted key path and new value")
+                                .required(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("scenes")
+                .about("Inspect bridge scenes")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("list")
+                        .about("List bridge scenes with a color preview")
+                        .long_about("List the scenes on the bridge, with a color swatch of each scene's average hue and saturation."),
+                )
+                .subcommand(
+                    Command::new("refresh")
+                        .about("Rebuild the status scenes from the current lights")
+                        .long_about("Update the status scenes to use the currently suitable lights. Scenes that still exist on the bridge keep their IDs; missing ones are recreated and the config file is updated. Scenes edited in the Hue app since huestatus last wrote them, and imported scenes, are kept unless --force is given.")
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Overwrite status scenes that were changed outside huestatus or imported"),
+                        )
+                        .arg(strict_scene_names_arg())
+                        .arg(
+                            Arg::new("effect")
+                                .long("effect")
+                                .value_name("EFFECT")
+                                .value_parser(["breathing", "colorloop", "none"])
+                                .help("Save an effect in the config file: breathing (failure scene), colorloop (success scene) or none (remove both)"),
+                        )
+                        .arg(
+                            Arg::new("effect-min")
+                                .long("effect-min")
+                                .value_name("BRIGHTNESS")
+                                .value_parser(parse_min_brightness)
+                                .requires("effect")
+                                .help("Dim brightness for --effect breathing, 1-254 or a percentage (default 25%)"),
+                        )
+                        .arg(
+                            Arg::new("effect-max")
+                                .long("effect-max")
+                                .value_name("BRIGHTNESS")
+                                .value_parser(parse_min_brightness)
+                                .requires("effect")
+                                .help("Bright brightness for --effect breathing (default: the failure color's)"),
+                        )
+                        .after_help("--effect breathing alternates the failure scene's lights between bright and dim. Hue scenes are static, so this is a snapshot, not an animation; recall the failure scene repeatedly (e.g. from a loop alternating with `off`) to make it breathe.\n\n--effect colorloop cycles the success scene's lights through all hues. Lights without color support get no effect."),
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("Save a status scene as JSON")
+                        .long_about("Write a status scene's lights and light states as JSON that `scenes import` reads, e.g. to back it up or recreate it on another bridge.")
+                        .arg(
+                            Arg::new("status")
+                                .value_parser(["success", "failure", "running"])
+                                .required(true)
+                                .help("Status scene to export"),
+                        )
+                        .arg(
+                            Arg::new("out")
+                                .long("out")
+                                .short('o')
+                                .value_name("FILE")
+                                .value_parser(clap::value_parser!(PathBuf))
+                                .help("Write to FILE instead of standard output"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("import")
+                        .about("Create a status scene from a JSON file")
+                        .long_about("Create a scene on the bridge from a JSON file shaped like a Hue scene creation request (name, lights, recycle and lightstates, as written by `scenes export`) and use it as the status scene. The scene it replaces is deleted if huestatus created it. Imported scenes are kept by `scenes refresh` unless --force is given.")
+                        .arg(
+                            Arg::new("status")
+                                .value_parser(["success", "failure", "running"])
+                                .required(true)
+                                .help("Status scene to replace"),
+                        )
+                        .arg(
+                            Arg::new("from-file")
+                                .long("from-file")
+                                .value_name("FILE")
+                                .value_parser(clap::value_parser!(PathBuf))
+                                .required(true)
+                                .help("Scene definition to import"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("clone")
+                        .about("Copy a bridge scene under a new name")
+                        .long_about("Create a new scene from an existing one's lights and light states, optionally with a new brightness or color on every light, and print the new scene ID. The new name must fit the Hue limit of 32 characters and can't use the reserved hue_ or huestatus- prefixes.")
+                        .arg(
+                            Arg::new("source-id")
+                                .value_name("SOURCE_ID")
+                                .required(true)
+                                .help("ID of the scene to copy"),
+                        )
+                        .arg(
+                            Arg::new("new-name")
+                                .value_name("NEW_NAME")
+                                .required(true)
+                                .value_parser(parse_clone_name)
+                                .help("Name of the new scene"),
+                        )
+                        .arg(
+                            Arg::new("brightness")
+                                .long("brightness")
+                                .value_name("BRI")
+                                .value_parser(parse_min_brightness)
+                                .help("Brightness for every light: 1-254 or a percentage like 50%"),
+                        )
+                        .arg(
+                            Arg::new("color-name")
+                                .long("color-name")
+                                .value_name("NAME")
+                                .value_parser(parse_color_name)
+                                .help(format!(
+                                    "Preset color for every light: {}",
+                                    ColorPresets::names().join(", ")
+                                )),
+                        ),
+                )
+                .subcommand(
+                    Command::new("gradient")
+                        .about("Show a status as a gradient across the lights")
+                        .long_about("Save a gradient for a status scene and rebuild the status scenes. Colors are preset names or status names (success, failure, running) for the configured colors, e.g. `failure orange` for red to orange. The colors repeat when there are more lights than colors; with a single light the status color is used. --clear goes back to the solid color.")
+                        .arg(
+                            Arg::new("status")
+                                .value_parser(["success", "failure", "running"])
+                                .required(true)
+                                .help("Status scene to change"),
+                        )
+                        .arg(
+                            Arg::new("colors")
+                                .num_args(2..)
+                                .required_unless_present("clear")
+                                .help("Colors in light order"),
+                        )
+                        .arg(
+                            Arg::new("clear")
+                                .long("clear")
+                                .action(clap::ArgAction::SetTrue)
+                                .conflicts_with("colors")
+                                .help("Remove the gradient and use the solid status color"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("explain")
+                .about("Explain an error code in detail")
+                .long_about("Print a troubleshooting guide with common causes and fixes for an error code. Without a code, list the codes that have a guide.")
+                .arg(
+                    Arg::new("code")
+                        .value_name("CODE")
+                        .help("Error code, e.g. bridge_not_found"),
+                ),
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show recent status commands")
+                .long_about("List the recorded success/failure/running commands, oldest first, followed by a count per status. History is stored as JSON lines next to the configuration file.")
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_name("DURATION")
+                        .value_parser(history::parse_relative_duration)
+                        .help("Only show entries from the last DURATION, e.g. 30m, 1h or 2d"),
+                )
+                .arg(
+                    Arg::new("last")
+                        .short('n')
+                        .long("last")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u32).range(1..))
+                        .help("Only show the N most recent entries"),
+                ),
+        )
+        .subcommand(
+            Command::new("metrics")
+                .about("Print metrics in Prometheus text format")
+                .long_about("Print execution counters from the history log and light gauges from the bridge in the Prometheus exposition format, e.g. for a node_exporter textfile collector. If the bridge can't be reached, huestatus_bridge_up is 0 and the light gauges are omitted."),
+        )
+        .subcommand(
+            Command::new("bench")
+                .about("Measure bridge round-trip latency")
+                .long_about("Time repeated lightweight requests to the bridge and report latency statistics, to help choose sensible timeouts.")
+                .hide(true)
+                .arg(
+                    Arg::new("iterations")
+                        .short('n')
+                        .long("iterations")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u32).range(1..))
+                        .default_value("20")
+                        .help("Number of requests to time"),
+                ),
+        )
+        .subcommand(
+            Command::new("off")
+                .about("Turn off the status lights")
+                .long_about("Turn off the lights in the status group, clearing the displayed status."),
+        )
+        .subcommand(
+            Command::new("brightness")
+                .about("Dim or brighten the current status")
+                .long_about("Change the status group's brightness relative to its current level, without rebuilding the scenes. The result is kept between 1 and 254 so the lights stay on.")
+                .arg(
+                    Arg::new("change")
+                        .value_name("CHANGE")
+                        .required(true)
+                        .allow_hyphen_values(true)
+                        .value_parser(|value: &str| value.parse::<BrightnessChange>())
+                        .help("Signed change in Hue units (+25, -25) or percent of the full range (+10%, -10%)"),
+                ),
         )
         .subcommand(
             Command::new("setup")
@@ -194,158 +1066,1687 @@ fn create_cli() -> Command {
                         .long("test")
                         .action(clap::ArgAction::SetTrue)
                         .help("Test scene execution after setup"),
+                )
+                .arg(
+                    Arg::new("network-scan")
+                        .long("network-scan")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Scan the local network when the discovery service and mDNS find no bridge")
+                        .long_help("Scan the local network when the discovery service and mDNS find no bridge. The scan sends a request to every host on the subnet, so it is off by default; interactive setup asks before scanning."),
+                )
+                .arg(
+                    Arg::new("first-match")
+                        .long("first-match")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Stop scanning the network once a bridge is found"),
+                )
+                .arg(
+                    Arg::new("check-only")
+                        .long("check-only")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Report what setup would do without changing the bridge or files")
+                        .conflicts_with("test"),
+                )
+                .arg(strict_scene_names_arg())
+                .arg(
+                    Arg::new("remote-token")
+                        .long("remote-token")
+                        .value_name("TOKEN")
+                        .help("Hue remote API access token, used to find the bridge when local discovery fails (or set HUESTATUS_REMOTE_TOKEN)"),
                 ),
         )
         .subcommand(
             Command::new("validate")
                 .about("Validate current configuration")
-                .long_about("Check if your current configuration is valid and all components are working correctly."),
+                .long_about("Check if your current configuration is valid and all components are working correctly.")
+                .arg(
+                    Arg::new("show-states")
+                        .long("show-states")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the current state of each scene light (on, brightness, color)"),
+                ),
         )
         .subcommand(
             Command::new("doctor")
                 .about("Run diagnostic checks")
-                .long_about("Perform comprehensive diagnostic checks to identify and help resolve any issues."),
+                .long_about("Perform comprehensive diagnostic checks to identify and help resolve any issues.")
+                .arg(
+                    Arg::new("fix")
+                        .long("fix")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Repair detected problems: update a moved bridge IP, refresh caches and rebuild broken status scenes"),
+                )
+                .after_help("With --fix, destructive fixes (such as deleting orphaned huestatus scenes) are confirmed first; --yes applies them without asking."),
         )
 }
 
-/// Execute status command (success or failure)
-async fn execute_status_command(
-    status_type: &str,
-    verbose: bool,
-    quiet: bool,
-    timeout: u64,
-    retry_attempts: usize,
-    retry_delay: u64,
-) -> Result<()> {
-    // Load configuration
-    let config = Config::load().map_err(|e| match e {
-        HueStatusError::ConfigNotFound => HueStatusError::ConfigNotFound,
-        _ => e,
-    })?;
-
-    // Apply command-line overrides
-    let effective_timeout = timeout;
-    let effective_verbose = verbose || config.effective_verbose();
-    let effective_quiet = quiet || config.effective_quiet();
-
-    if effective_verbose && !effective_quiet {
-        eprintln!("🔍 Executing {status_type} status...");
-        eprintln!("📍 Bridge: {}", config.bridge.ip);
-    }
-
-    // Create bridge client
-    let client = BridgeClient::with_config(
-        config.bridge.ip.clone(),
-        effective_timeout,
-        retry_attempts,
-        retry_delay,
-        effective_verbose && !effective_quiet,
-    )?
-    .with_username(config.bridge.application_key.clone());
-
-    // Create scene manager
-    let scene_manager =
-        SceneManager::new(client).with_verbose(effective_verbose && !effective_quiet);
-
-    // Execute the status scene
-    let result = scene_manager
-        .execute_status_scene(status_type, &config)
-        .await?;
-
-    if !effective_quiet {
-        if effective_verbose {
-            println!(
-                "✅ {} status displayed successfully ({}ms)",
-                style(status_type).bold(),
-                result.execution_time_ms
-            );
-        } else {
-            // Silent success for non-verbose, non-quiet mode
-        }
-    }
-
-    Ok(())
+/// Warn that certificate verification is off, even in quiet mode
+fn warn_insecure() {
+    eprintln!("⚠️ TLS certificate verification is disabled; only use this with a bridge on your local network");
 }
 
-/// Execute setup command
-async fn execute_setup_command(options: SetupOptions) -> Result<()> {
-    let mut setup = SetupProcess::new().with_options(options.verbose, options.force, None);
+/// Parse the `--project` value
+fn parse_project(name: &str) -> std::result::Result<String, String> {
+    validate_project_name(name)
+        .map(|()| name.to_string())
+        .map_err(|e| e.to_string())
+}
 
-    let result = setup.run(&options).await?;
+/// Parse the `--lang` value
+fn parse_locale(tag: &str) -> std::result::Result<Locale, String> {
+    Locale::parse(tag).ok_or_else(|| format!("unsupported language '{tag}' (expected en or ja)"))
+}
 
-    if options.verbose {
-        println!("Setup result: {}", result.summary());
+/// Parse `--min-brightness` as a Hue brightness or percentage, excluding off
+fn parse_min_brightness(value: &str) -> std::result::Result<u8, String> {
+    match parse_brightness(value)? {
+        0 => Err("minimum brightness must be above 0".to_string()),
+        bri => Ok(bri),
     }
+}
 
-    Ok(())
+/// Shared `--color-name` option for showing a preset color instead of a scene
+fn color_name_arg() -> Arg {
+    Arg::new("color-name")
+        .long("color-name")
+        .value_name("NAME")
+        .value_parser(parse_color_name)
+        .help(format!(
+            "Show a preset color instead of the scene: {}",
+            ColorPresets::names().join(", ")
+        ))
 }
 
-/// Execute validate command
-async fn execute_validate_command(verbose: bool) -> Result<()> {
-    if verbose {
-        println!("🔍 Validating configuration...");
+/// Shared `--lights` option for status commands
+fn lights_arg() -> Arg {
+    Arg::new("lights")
+        .long("lights")
+        .value_name("ID,ID,...")
+        .value_delimiter(',')
+        .conflicts_with_all(["verify-change", "hold"])
+        .help("Show the status color on exactly these light IDs instead of recalling the scene")
+}
+
+/// Shared `--color-temp` option for status commands
+fn color_temp_arg() -> Arg {
+    Arg::new("color-temp")
+        .long("color-temp")
+        .value_name("KELVIN")
+        .value_parser(clap::value_parser!(u16).range(2000..=6500))
+        .conflicts_with_all(["verify-change", "color-name"])
+        .help("Show a white color temperature (2000-6500K) instead of the scene, for white lights")
+}
+
+/// Look up a `--color-name` preset, listing the valid names on error
+fn parse_color_name(name: &str) -> std::result::Result<ColorDefinition, String> {
+    ColorPresets::by_name(name).ok_or_else(|| {
+        format!(
+            "unknown color '{name}' (available: {})",
+            ColorPresets::names().join(", ")
+        )
+    })
+}
+
+/// Shared `--hold` option for momentary status indicators
+fn hold_arg() -> Arg {
+    Arg::new("hold")
+        .long("hold")
+        .value_name("DURATION")
+        .value_parser(history::parse_relative_duration)
+        .help("Show the status for DURATION (e.g. 10s, 2m), then turn the lights off")
+        .long_help("Show the status for DURATION (e.g. 10s, 2m), then turn the lights off. The command blocks until then; Ctrl-C turns the lights off early.")
+}
+
+/// Shared `--verify-change` flag for status commands
+fn verify_change_arg() -> Arg {
+    Arg::new("verify-change")
+        .long("verify-change")
+        .action(clap::ArgAction::SetTrue)
+        .conflicts_with("color-name")
+        .help("Compare light states before and after the recall")
+}
+
+/// Shared `--measure-apply` flag for status commands
+fn measure_apply_arg() -> Arg {
+    Arg::new("measure-apply")
+        .long("measure-apply")
+        .action(clap::ArgAction::SetTrue)
+        .conflicts_with_all(["color-name", "lights", "color-temp"])
+        .help("Measure how long the lights take to show the recalled scene")
+}
+
+/// Shared `--only-on-change` flag for status commands
+fn only_on_change_arg() -> Arg {
+    Arg::new("only-on-change")
+        .long("only-on-change")
+        .action(clap::ArgAction::SetTrue)
+        .conflicts_with_all([
+            "color-name",
+            "lights",
+            "color-temp",
+            "verify-change",
+            "measure-apply",
+        ])
+        .help("Skip the recall if the lights already show this status (for pollers)")
+}
+
+/// `--alert` option of the failure command
+fn alert_arg() -> Arg {
+    Arg::new("alert")
+        .long("alert")
+        .value_name("none|select|lselect")
+        .value_parser(|value: &str| value.parse::<AlertMode>())
+        .conflicts_with_all(["color-name", "lights", "color-temp"])
+        .help("Also blink (select) or breathe for ~15s (lselect) the lights after the recall")
+}
+
+/// `--strict` flag of the commands that create status scenes
+fn strict_scene_names_arg() -> Arg {
+    Arg::new("strict")
+        .long("strict")
+        .action(clap::ArgAction::SetTrue)
+        .help("Fail if a status scene name is already used by a scene huestatus didn't create")
+        .long_help("Fail if a status scene name is already used by a scene huestatus didn't create (one without the huestatus appdata marker). Without --strict such scenes are left untouched with a warning and separate status scenes are created.")
+}
+
+/// Shared `--max-verification-age` option for status commands
+fn max_verification_age_arg() -> Arg {
+    Arg::new("max-verification-age")
+        .long("max-verification-age")
+        .value_name("DURATION")
+        .value_parser(history::parse_relative_duration)
+        .help("Re-check the bridge and key first if they were last verified longer than DURATION ago (e.g. 1h)")
+        .long_help("Re-check the bridge and key first if they were last verified longer than DURATION ago (e.g. 1h, 7d). The command fails instead of showing the status when the bridge is unreachable or the key was revoked.")
+}
+
+/// Shared `--confirm` flag for status commands
+fn confirm_arg() -> Arg {
+    Arg::new("confirm")
+        .long("confirm")
+        .action(clap::ArgAction::SetTrue)
+        .help("Briefly dim the lights afterwards to confirm the status was received")
+}
+
+/// Per-command options of the status commands
+struct StatusArgs {
+    verify_change: bool,
+    confirm: bool,
+    color: Option<ColorDefinition>,
+    lights: Option<Vec<String>>,
+    color_temp: Option<u16>,
+    hold: Option<std::time::Duration>,
+    measure_apply: bool,
+    only_on_change: bool,
+    max_verification_age: Option<chrono::Duration>,
+    alert: AlertMode,
+}
+
+impl StatusArgs {
+    /// Read the status command arguments
+    fn from_matches(matches: &ArgMatches) -> Self {
+        Self {
+            verify_change: matches.get_flag("verify-change"),
+            confirm: matches.get_flag("confirm"),
+            color: matches.get_one::<ColorDefinition>("color-name").cloned(),
+            lights: matches
+                .get_many::<String>("lights")
+                .map(|ids| ids.cloned().collect()),
+            color_temp: matches.get_one::<u16>("color-temp").copied(),
+            hold: matches
+                .get_one::<chrono::Duration>("hold")
+                .and_then(|hold| hold.to_std().ok()),
+            measure_apply: matches.get_flag("measure-apply"),
+            only_on_change: matches.get_flag("only-on-change"),
+            max_verification_age: matches
+                .get_one::<chrono::Duration>("max-verification-age")
+                .copied(),
+            // Only the failure command has --alert
+            alert: matches
+                .try_get_one::<AlertMode>("alert")
+                .ok()
+                .flatten()
+                .copied()
+                .unwrap_or_default(),
+        }
     }
+}
 
-    // Load and validate configuration
-    let config = Config::load()?;
+/// Execute status command (success or failure)
+async fn execute_status_command(
+    status_type: &str,
+    options: &GlobalOptions,
+    args: StatusArgs,
+) -> Result<()> {
+    // Load configuration with environment and command-line overrides applied
+    let mut config = options.load_config()?;
+    if args.confirm {
+        config.settings.confirm_flash = true;
+    }
+    let effective_verbose = config.settings.verbose_logging;
+    let effective_quiet = config.settings.quiet_mode;
+
+    let status_app = |config| -> Result<Huestatus> {
+        Ok(Huestatus::new(config)?
+            .with_change_verification(args.verify_change)
+            .with_apply_measurement(args.measure_apply)
+            .with_only_on_change(args.only_on_change)
+            .with_failure_alert(args.alert)
+            .with_config_path(options.writable_config_path()))
+    };
+    let mut app = status_app(config)?;
+    let mut result = show_status(&app, status_type, &args).await;
+
+    // The key was revoked on the bridge: offer to press the link button again and retry
+    if matches!(result, Err(HueStatusError::AuthenticationFailed))
+        && offer_reauthentication(options, effective_quiet)
+    {
+        let mut config = app.config().clone();
+        setup::reauthenticate(&mut config).await?;
+        // Store only the new key: `config` carries env and CLI overrides
+        if let Some(path) = options.writable_config_path() {
+            let mut stored = load_config(&path)?;
+            stored.bridge.application_key = config.bridge.application_key.clone();
+            stored.bridge.last_verified = config.bridge.last_verified;
+            save_config(&stored, &path)?;
+        }
+        app = status_app(config)?;
+        result = show_status(&app, status_type, &args).await;
+    }
+    record_history(status_type, &result, effective_quiet);
+    let result = result?;
+
+    if !effective_quiet {
+        show_light_failures(&result.warnings);
+    }
+
+    if !effective_quiet && result.has_no_visible_change() {
+        if let Some(change) = &result.state_change {
+            eprintln!(
+                "⚠️ Scene recalled but no visible change detected ({})",
+                change.summary()
+            );
+        }
+    }
+
+    if options.json {
+        let output = serde_json::json!({
+            "status": status_type,
+            "scene_id": result.scene_id,
+            "scene_name": result.scene_name,
+            "success": result.success,
+            "execution_time_ms": result.execution_time_ms,
+            "apply_latency_ms": result.apply_latency_ms,
+            // Only with --verify-change, and null if the lights couldn't be re-read
+            "state_change": result.state_change.as_ref().map(|change| serde_json::json!({
+                "lights_checked": change.lights_checked,
+                "lights_changed": change.lights_changed,
+                "lights_unchanged": change.unchanged_lights.len(),
+                "unchanged_lights": change.unchanged_lights,
+            })),
+            "already_showing": result.already_showing,
+            "retry_count": result.retry_count,
+            "retried": result.had_retries(),
+            "warnings": result.warnings,
+            "color": args.color.as_ref().map(|color| color.name.as_str()),
+            "lights": args.lights,
+            "color_temp": args.color_temp,
+            "hold_seconds": args.hold.map(|hold| hold.as_secs()),
+            "alert": args.alert.as_str(),
+        });
+        options.emit(&options.json_output(&output)?)?;
+    } else if options.summary && result.already_showing {
+        options.emit(&format!("{status_type} already showing"))?;
+    } else if options.summary {
+        options.emit(&format!(
+            "{status_type} executed in {}ms",
+            result.execution_time_ms
+        ))?;
+    } else if !effective_quiet {
+        if effective_verbose {
+            println!(
+                "✅ {} status displayed successfully ({}ms, {} retries)",
+                style(status_type).bold(),
+                result.execution_time_ms,
+                result.retry_count
+            );
+        } else {
+            // Silent success for non-verbose, non-quiet mode
+        }
+        if args.measure_apply {
+            match result.apply_latency_ms {
+                Some(apply_ms) => println!(
+                    "⏱️  API {}ms, lights applied the scene {apply_ms}ms after the recall",
+                    result.execution_time_ms
+                ),
+                None => println!("⏱️  Couldn't observe the lights applying the scene"),
+            }
+        }
+    }
+
+    if let Some(hold) = args.hold {
+        hold_then_off(&app, hold, effective_quiet).await?;
+    }
+
+    Ok(())
+}
+
+/// Ask whether to re-authenticate after the bridge rejected the stored key
+///
+/// Only asked on an interactive terminal; scripts and `--json` runs get the auth error.
+fn offer_reauthentication(options: &GlobalOptions, quiet: bool) -> bool {
+    if quiet || options.json || !std::io::stdin().is_terminal() {
+        return false;
+    }
+    eprintln!("🔑 The bridge no longer accepts the stored application key.");
+    setup::confirm(
+        "Press the link button to re-authenticate now?",
+        true,
+        options.assume_yes,
+    )
+}
+
+/// Show the status the way the arguments ask: scene, color, color temperature or lights
+async fn show_status(
+    app: &Huestatus,
+    status_type: &str,
+    args: &StatusArgs,
+) -> Result<huestatus::scenes::SceneExecutionResult> {
+    if let Some(max_age) = args.max_verification_age {
+        app.ensure_verified_within(max_age).await?;
+    }
+
+    let lights = args.lights.as_deref();
+    if let Some(kelvin) = args.color_temp {
+        let brightness = status_color(status_type, app.config())?.brightness;
+        return app.show_color_temperature(kelvin, brightness, lights).await;
+    }
+
+    match (&args.color, lights) {
+        (Some(color), Some(lights)) => app.show_color_on_lights(color, lights).await,
+        (None, Some(lights)) => {
+            let color = status_color(status_type, app.config())?;
+            app.show_color_on_lights(&color, lights).await
+        }
+        (Some(color), None) => app.show_color(color).await,
+        (None, None) => app.show_status(status_type).await,
+    }
+}
+
+/// Configured color of a status, for showing it without the scene
+fn status_color(status_type: &str, config: &Config) -> Result<ColorDefinition> {
+    let color = config
+        .colors
+        .get(status_type)
+        .ok_or_else(|| HueStatusError::InvalidConfig {
+            reason: format!("no color configured for status '{status_type}'"),
+        })?;
+    Ok(ColorDefinition::new(
+        status_type.to_string(),
+        color.hue,
+        color.saturation,
+        color.brightness,
+    ))
+}
+
+/// Show a status for every JSON event on stdin, until stdin closes
+///
+/// One client is kept for the whole stream; malformed lines (including
+/// invalid UTF-8) and failed events are reported and skipped. With `--output-file`, the `--json` events
+/// are written to it as JSON lines once stdin closes.
+async fn execute_stdin_json_command(options: &GlobalOptions) -> Result<()> {
+    use std::io::BufRead;
+
+    let config = options.load_config()?;
+    let quiet = config.settings.quiet_mode;
+    let app = Huestatus::new(config)?.with_config_path(options.writable_config_path());
+    // --output-file is replaced on every write, so collect the events for one write at EOF
+    let mut json_events = Vec::new();
+
+    for (number, line) in std::io::stdin().lock().lines().enumerate() {
+        // The bytes of an invalid UTF-8 line are consumed, so the stream can go on
+        let line = match line {
+            Ok(line) => line,
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                if !quiet {
+                    eprintln!("⚠️ Skipping line {}: {e}", number + 1);
+                }
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event = match StatusEvent::parse(&line) {
+            Ok(event) => event,
+            Err(e) => {
+                if !quiet {
+                    eprintln!("⚠️ Skipping line {}: {e}", number + 1);
+                }
+                continue;
+            }
+        };
+        if !quiet {
+            match &event.reason {
+                Some(reason) => eprintln!("📨 {}: {reason}", event.status),
+                None => eprintln!("📨 {}", event.status),
+            }
+        }
+
+        let result = app.show_status(&event.status).await;
+        record_history(&event.status, &result, quiet);
+
+        if options.json {
+            let output = match &result {
+                Ok(result) => serde_json::json!({
+                    "status": event.status,
+                    "reason": event.reason,
+                    "success": result.success,
+                    "scene_id": result.scene_id,
+                    "execution_time_ms": result.execution_time_ms,
+                }),
+                Err(e) => serde_json::json!({
+                    "status": event.status,
+                    "reason": event.reason,
+                    "success": false,
+                    "error": e.error_code(),
+                }),
+            };
+            let event_line = serde_json::to_string(&output::versioned(&output))?;
+            if options.output_file.is_some() {
+                json_events.push(event_line);
+            } else {
+                options.emit(&event_line)?;
+            }
+        }
+        if !quiet {
+            match result {
+                Ok(result) => show_light_failures(&result.warnings),
+                Err(e) => eprintln!("{}", format_error(&e)),
+            }
+        }
+    }
+
+    if !json_events.is_empty() {
+        options.emit(&json_events.join("\n"))?;
+    }
+
+    Ok(())
+}
+
+/// Keep the status up for `hold`, then turn the lights off; Ctrl-C turns them off early
+async fn hold_then_off(app: &Huestatus, hold: std::time::Duration, quiet: bool) -> Result<()> {
+    if !quiet {
+        eprintln!(
+            "⏳ Holding for {}s, then turning the lights off (Ctrl-C to turn them off now)",
+            hold.as_secs()
+        );
+    }
+
+    tokio::select! {
+        _ = tokio::time::sleep(hold) => {}
+        _ = tokio::signal::ctrl_c() => {
+            if !quiet {
+                eprintln!("🛑 Interrupted, turning the lights off");
+            }
+        }
+    }
+
+    let failures = app.off().await?;
+    if !quiet {
+        show_light_failures(&failures);
+    }
+    Ok(())
+}
+
+/// Append a status command to the execution history, warning if it can't be written
+fn record_history(
+    status_type: &str,
+    result: &Result<huestatus::scenes::SceneExecutionResult>,
+    quiet: bool,
+) {
+    let entry = match result {
+        Ok(result) => HistoryEntry::from_result(status_type, result),
+        Err(e) => HistoryEntry::from_error(status_type, e),
+    };
+
+    let recorded =
+        history::history_file_path().and_then(|path| history::append_entry(&path, &entry));
+    if let Err(e) = recorded {
+        if !quiet {
+            eprintln!("⚠️ Could not record history: {e}");
+        }
+    }
+}
+
+/// Scene selected on the command line
+enum SceneTarget {
+    Id(String),
+    Name(String),
+    Color(ColorDefinition),
+}
+
+/// Execute recall command
+async fn execute_recall_command(options: &GlobalOptions, target: SceneTarget) -> Result<()> {
+    let config = options.load_config()?;
+    let effective_verbose = config.settings.verbose_logging;
+    let effective_quiet = config.settings.quiet_mode;
+
+    let app = Huestatus::new(config)?;
+    let (scene_id, result) = match target {
+        SceneTarget::Id(scene_id) => {
+            let result = app.recall_scene(&scene_id).await?;
+            (scene_id, result)
+        }
+        SceneTarget::Name(name) => {
+            let scene_id = app.client().get_scene_by_name(&name).await?.0;
+            let result = app.recall_scene(&scene_id).await?;
+            (scene_id, result)
+        }
+        SceneTarget::Color(color) => {
+            let result = app.show_color(&color).await?;
+            (format!("color {}", result.scene_name), result)
+        }
+    };
+
+    if !effective_quiet {
+        show_light_failures(&result.warnings);
+    }
+
+    if options.summary {
+        options.emit(&format!(
+            "recall {scene_id} executed in {}ms",
+            result.execution_time_ms
+        ))?;
+    } else if effective_verbose {
+        println!(
+            "✅ Scene {} ({scene_id}) recalled ({}ms)",
+            style(&result.scene_name).bold(),
+            result.execution_time_ms
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute groups command
+async fn execute_groups_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+    let groups = Huestatus::new(config)?.groups().await?;
+
+    if options.json {
+        let entries: Vec<_> = groups
+            .iter()
+            .map(|(id, group)| {
+                serde_json::json!({
+                    "id": id,
+                    "name": group.name,
+                    "type": group.group_type,
+                    "lights": group.lights,
+                    "all_on": group.state.all_on,
+                    "any_on": group.state.any_on,
+                })
+            })
+            .collect();
+        options.emit(&options.json_output(&entries)?)?;
+        return Ok(());
+    }
+
+    if options.summary {
+        options.emit(&format!("{} groups found", groups.len()))?;
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        println!("No groups found");
+        return Ok(());
+    }
+
+    let state = |group: &huestatus::bridge::Group| {
+        if group.state.all_on {
+            "all on"
+        } else if group.state.any_on {
+            "some on"
+        } else {
+            "off"
+        }
+    };
+
+    if options.format.use_table() {
+        let mut table = Table::new(&["ID", "NAME", "TYPE", "LIGHTS", "STATE"]).align_right(3);
+        for (id, group) in &groups {
+            table.add_row(vec![
+                id.clone(),
+                group.name.clone(),
+                group.group_type.clone(),
+                group.lights.len().to_string(),
+                state(group).to_string(),
+            ]);
+        }
+        print!("{}", table.render());
+        return Ok(());
+    }
+
+    println!(
+        "{:<6} {:<24} {:<14} {:>6}  STATE",
+        "ID", "NAME", "TYPE", "LIGHTS"
+    );
+    for (id, group) in &groups {
+        println!(
+            "{:<6} {:<24} {:<14} {:>6}  {}",
+            id,
+            group.name,
+            group.group_type,
+            group.lights.len(),
+            state(group)
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute config path command
+fn execute_config_path_command(options: &GlobalOptions) -> Result<()> {
+    let path = match &options.config_path {
+        Some(path) => PathBuf::from(path),
+        None => Config::get_config_file_path()?,
+    };
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// Execute config show command
+fn execute_config_show_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+
+    let mut value = serde_json::to_value(&config)?;
+    value["bridge"]["application_key"] =
+        serde_json::Value::String(mask_key(&config.bridge.application_key));
+
+    if options.json {
+        return options.emit(&options.json_output(&value)?);
+    }
+
+    println!("{}", options.to_json(&value)?);
+    Ok(())
+}
+
+/// Execute config show --diff command
+fn execute_config_diff_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+    let differences = config.differences_from_defaults()?;
+
+    if options.json {
+        return options.emit(&options.json_output(&differences)?);
+    }
+
+    if differences.is_empty() {
+        println!("All settings are at their defaults");
+        return Ok(());
+    }
+
+    let width = differences
+        .iter()
+        .map(|difference| difference.field.len())
+        .max()
+        .unwrap_or(0);
+    for difference in &differences {
+        println!(
+            "{} {} {}",
+            style(format!("{:<width$}", difference.field)).bold(),
+            style(&difference.current).green(),
+            style(format!("(default {})", difference.default)).dim()
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute config get command
+fn execute_config_get_command(options: &GlobalOptions, key: &str) -> Result<()> {
+    let config = options.load_config()?;
+    let value = config.get_value(key.trim())?;
+
+    if options.json {
+        let output = serde_json::json!({ "key": key.trim(), "value": value });
+        options.emit(&options.json_output(&output)?)?;
+        return Ok(());
+    }
+
+    match value {
+        serde_json::Value::String(text) => println!("{text}"),
+        serde_json::Value::Null => println!(),
+        other => println!("{other}"),
+    }
+
+    Ok(())
+}
+
+/// Execute config set command
+fn execute_config_set_command(options: &GlobalOptions, assignment: &str) -> Result<()> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| HueStatusError::InvalidConfig {
+            reason: format!("Expected KEY=VALUE, got '{assignment}'"),
+        })?;
+    let key = key.trim();
+
+    let config_path = options
+        .writable_config_path()
+        .ok_or(HueStatusError::ConfigNotFound)?;
+
+    // Edit the file as stored, without environment or command-line overrides
+    let mut config = load_config(&config_path)?;
+    config.set_value(key, value)?;
     config.validate()?;
+    save_config(&config, &config_path)?;
 
-    if verbose {
-        println!("✅ Configuration is valid");
+    if !options.quiet {
+        println!("✅ Set {key} = {}", value.trim());
+        if key.starts_with("colors.") || key == "settings.min_color_distance" {
+            warn_similar_colors(&config);
+        }
+    }
+
+    Ok(())
+}
+
+/// Warn when status colors are too close to tell apart
+fn warn_similar_colors(config: &Config) {
+    for warning in config
+        .colors
+        .similar_colors(config.settings.min_color_distance)
+    {
+        eprintln!("⚠️ {warning}");
+    }
+}
+
+/// Execute scenes list command
+async fn execute_scenes_list_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+    let scenes = Huestatus::new(config)?.scenes().await?;
+
+    if options.json {
+        let entries: Vec<_> = scenes
+            .iter()
+            .map(|(id, scene)| {
+                let color = scene
+                    .average_hue_sat()
+                    .map(|(hue, sat)| serde_json::json!({ "hue": hue, "sat": sat }));
+                serde_json::json!({
+                    "id": id,
+                    "name": scene.name,
+                    "lights": scene.lights,
+                    "locked": scene.locked,
+                    "color": color,
+                })
+            })
+            .collect();
+        options.emit(&options.json_output(&entries)?)?;
+        return Ok(());
+    }
+
+    if options.summary {
+        options.emit(&format!("{} scenes found", scenes.len()))?;
+        return Ok(());
+    }
+
+    if scenes.is_empty() {
+        println!("No scenes found");
+        return Ok(());
+    }
+
+    if options.format.use_table() {
+        let mut table = Table::new(&["ID", "NAME", "LIGHTS", "COLOR"]).align_right(2);
+        for (id, scene) in &scenes {
+            table.add_row(vec![
+                id.clone(),
+                scene.name.clone(),
+                scene.light_count().to_string(),
+                color_preview(scene.average_hue_sat()),
+            ]);
+        }
+        print!("{}", table.render());
+        return Ok(());
+    }
+
+    println!("{:<18} {:<32} {:>6}  COLOR", "ID", "NAME", "LIGHTS");
+    for (id, scene) in &scenes {
+        println!(
+            "{:<18} {:<32} {:>6}  {}",
+            id,
+            scene.name,
+            scene.light_count(),
+            color_preview(scene.average_hue_sat())
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute scenes refresh command
+async fn execute_scenes_refresh_command(
+    options: &GlobalOptions,
+    force: bool,
+    strict: bool,
+) -> Result<()> {
+    let config_path = options
+        .writable_config_path()
+        .ok_or(HueStatusError::ConfigNotFound)?;
+    let config = options.load_config()?;
+    let effective_quiet = config.settings.quiet_mode;
+
+    let result = Huestatus::new(config)?
+        .with_config_path(Some(config_path))
+        .with_overwrite_changes(force)
+        .with_strict_scene_names(strict)
+        .refresh_scenes()
+        .await?;
+
+    if options.json {
+        let output = serde_json::json!({
+            "success_scene_id": result.success_scene_id,
+            "failure_scene_id": result.failure_scene_id,
+            "running_scene_id": result.running_scene_id,
+            "lights": result.lights_used,
+            "scenes_created": result.scenes_created,
+            "scenes_updated": result.scenes_updated,
+            "scenes_skipped": result.scenes_skipped,
+            "warnings": result.warnings,
+        });
+        options.emit(&options.json_output(&output)?)?;
+        return Ok(());
+    }
+
+    if options.summary {
+        options.emit(&result.summary())?;
+        return Ok(());
+    }
+
+    if !effective_quiet {
+        for warning in &result.warnings {
+            eprintln!("⚠️ {warning}");
+        }
+        println!(
+            "✅ Status scenes refreshed ({} updated, {} created) using {} lights",
+            result.scenes_updated,
+            result.scenes_created,
+            result.lights_used.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute scenes export command
+async fn execute_scenes_export_command(
+    options: &GlobalOptions,
+    status_type: &str,
+    out: Option<&PathBuf>,
+) -> Result<()> {
+    let config = options.load_config()?;
+    let effective_quiet = config.settings.quiet_mode;
+    let scene_id = config
+        .get_scene(status_type)
+        .map(|scene| scene.id.clone())
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| HueStatusError::SceneNotFound {
+            scene_name: status_type.to_string(),
+        })?;
+
+    let scene = Huestatus::new(config)?
+        .client()
+        .get_scene(&scene_id)
+        .await?;
+    let json = options.to_json(&CreateSceneRequest::from(scene))?;
+
+    match out {
+        Some(path) => {
+            std::fs::write(path, json + "\n")?;
+            if !effective_quiet {
+                println!(
+                    "✅ Exported the {status_type} scene ({scene_id}) to {}",
+                    path.display()
+                );
+            }
+        }
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
+
+/// Execute scenes import command
+async fn execute_scenes_import_command(
+    options: &GlobalOptions,
+    status_type: &str,
+    file: &Path,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)?;
+    let request: CreateSceneRequest =
+        serde_json::from_str(&content).map_err(|e| HueStatusError::InvalidSceneData {
+            reason: format!("{}: {e}", file.display()),
+        })?;
+
+    let config_path = options
+        .writable_config_path()
+        .ok_or(HueStatusError::ConfigNotFound)?;
+    let config = options.load_config()?;
+    let effective_quiet = config.settings.quiet_mode;
+    let app = Huestatus::new(config)?;
+
+    // Only the scenes change on disk; command-line and environment overrides stay out
+    let mut stored = load_config(&config_path)?;
+    let result = app
+        .scene_manager()
+        .import_status_scene(&mut stored, status_type, &request)
+        .await?;
+    save_config(&stored, &config_path)?;
+    let scene_id = &result.scene_id;
+
+    if !effective_quiet {
+        for warning in &result.warnings {
+            eprintln!("⚠️ {warning}");
+        }
+    }
+
+    if options.json {
+        let output = serde_json::json!({
+            "status": status_type,
+            "scene_id": scene_id,
+            "name": request.name,
+            "lights": request.lights,
+            "replaced_scene_id": result.replaced_scene_id,
+        });
+        options.emit(&options.json_output(&output)?)?;
+    } else if options.summary || effective_quiet {
+        options.emit(scene_id)?;
+    } else {
         println!(
-            "📍 Bridge: {} ({})",
-            config.bridge.ip,
-            config.bridge.last_verified.format("%Y-%m-%d %H:%M UTC")
+            "✅ Imported '{}' as the {status_type} scene ({scene_id}, {} lights)",
+            request.name,
+            request.lights.len()
         );
+        if let Some(replaced) = &result.replaced_scene_id {
+            println!("🗑️ Deleted the replaced scene {replaced}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute scenes clone command
+async fn execute_scenes_clone_command(
+    options: &GlobalOptions,
+    source_id: &str,
+    new_name: &str,
+    brightness: Option<u8>,
+    color: Option<&ColorDefinition>,
+) -> Result<()> {
+    let config = options.load_config()?;
+    let effective_quiet = config.settings.quiet_mode;
+    let app = Huestatus::new(config)?;
+
+    let modifications = if brightness.is_some() || color.is_some() {
+        let source = app.client().get_scene(source_id).await?;
+        let states = source
+            .lightstates
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(light_id, mut state)| {
+                if let Some(color) = color {
+                    state.hue = Some(color.hue);
+                    state.sat = Some(color.saturation);
+                    state.xy = color.xy;
+                    state.ct = None;
+                    state.colormode = Some("hs".to_string());
+                }
+                if let Some(brightness) = brightness {
+                    state.bri = Some(brightness);
+                }
+                state.on = true;
+                (light_id, state)
+            })
+            .collect();
+        Some(states)
+    } else {
+        None
+    };
+
+    let scene_id = SceneCreator::new(app.client().clone())
+        .with_verbose(options.verbose)
+        .clone_scene(source_id, new_name.to_string(), modifications)
+        .await?;
+
+    if options.json {
+        let output = serde_json::json!({
+            "scene_id": scene_id,
+            "name": new_name,
+            "source_id": source_id,
+        });
+        options.emit(&options.json_output(&output)?)?;
+    } else if options.summary || effective_quiet {
+        options.emit(&scene_id)?;
+    } else {
+        println!("✅ Cloned scene {source_id} as '{new_name}' ({scene_id})");
     }
 
-    // Test bridge connection
-    let client = BridgeClient::new(config.bridge.ip.clone())?
-        .with_username(config.bridge.application_key.clone())
-        .with_verbose(verbose);
+    Ok(())
+}
+
+/// Check a `scenes clone` name against the Hue limits and reserved prefixes
+fn parse_clone_name(name: &str) -> std::result::Result<String, String> {
+    validate_scene_name(name).map_err(|e| match e {
+        HueStatusError::InvalidConfig { reason } => reason,
+        e => e.to_string(),
+    })?;
 
-    client.test_connection().await?;
+    let prefix = name.get(..HUESTATUS_SCENE_PREFIX.len());
+    if prefix.is_some_and(|prefix| prefix.eq_ignore_ascii_case(HUESTATUS_SCENE_PREFIX)) {
+        return Err(format!(
+            "scene names starting with '{HUESTATUS_SCENE_PREFIX}' are reserved for status scenes"
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Save the `--effect` before the scenes are rebuilt
+fn save_effect(
+    options: &GlobalOptions,
+    effect: &str,
+    min_brightness: Option<u8>,
+    max_brightness: Option<u8>,
+) -> Result<()> {
+    let config_path = options
+        .writable_config_path()
+        .ok_or(HueStatusError::ConfigNotFound)?;
+    let mut stored = load_config(&config_path)?;
+
+    match effect {
+        "colorloop" => stored.colors.success_effect = Some(effect.to_string()),
+        "breathing" => {
+            let effect = BreathingEffect {
+                min_brightness: min_brightness.unwrap_or_else(|| percent_to_bri(25)),
+                max_brightness: max_brightness.unwrap_or(stored.colors.failure.brightness),
+            };
+            if effect.min_brightness >= effect.max_brightness {
+                return Err(HueStatusError::InvalidConfig {
+                    reason: format!(
+                        "--effect-min ({}) must be below --effect-max ({})",
+                        effect.min_brightness, effect.max_brightness
+                    ),
+                });
+            }
+            stored.colors.failure_effect = Some(effect);
+        }
+        _ => {
+            stored.colors.failure_effect = None;
+            stored.colors.success_effect = None;
+        }
+    }
+    save_config(&stored, &config_path)
+}
+
+/// Save a gradient (or clear it when `colors` is empty) and rebuild the status scenes
+async fn execute_scenes_gradient_command(
+    options: &GlobalOptions,
+    status_type: &str,
+    colors: &[String],
+) -> Result<()> {
+    let config_path = options
+        .writable_config_path()
+        .ok_or(HueStatusError::ConfigNotFound)?;
+    let mut stored = load_config(&config_path)?;
+
+    if colors.is_empty() {
+        stored.colors.gradients.remove(status_type);
+    } else {
+        let brightness = stored
+            .colors
+            .get(status_type)
+            .map(|color| color.brightness)
+            .unwrap_or(254);
+        let gradient = colors
+            .iter()
+            .map(|name| gradient_color(name, &stored.colors, brightness))
+            .collect::<Result<Vec<_>>>()?;
+        stored
+            .colors
+            .gradients
+            .insert(status_type.to_string(), gradient);
+    }
+    save_config(&stored, &config_path)?;
+
+    execute_scenes_refresh_command(options, false, false).await
+}
+
+/// Resolve a gradient color: a status name for its configured color, or a preset
+///
+/// Presets take the brightness of the status being changed.
+fn gradient_color(name: &str, colors: &ColorsConfig, brightness: u8) -> Result<ColorConfig> {
+    if let Some(color) = colors.get(name) {
+        return Ok(color);
+    }
+
+    let preset = parse_color_name(name).map_err(|reason| HueStatusError::InvalidConfig {
+        reason: format!("{reason}, or success, failure, running"),
+    })?;
+    Ok(ColorConfig {
+        hue: preset.hue,
+        saturation: preset.saturation,
+        brightness,
+    })
+}
+
+/// Render a truecolor swatch and hue/saturation values (swatch omitted without color)
+fn color_preview(color: Option<(u16, u8)>) -> String {
+    let Some((hue, sat)) = color else {
+        return "-".to_string();
+    };
+
+    let text = format!("hue {hue} sat {sat}");
+    if !console::colors_enabled() {
+        return text;
+    }
+
+    let [r, g, b] = huestatus::bridge::hue_sat_to_rgb(hue, sat);
+    format!("\x1b[48;2;{r};{g};{b}m  \x1b[0m {text}")
+}
+
+/// Execute explain command
+fn execute_explain_command(options: &GlobalOptions, code: Option<&String>) -> Result<()> {
+    let Some(code) = code else {
+        if options.json {
+            let codes: Vec<_> = explain::ERROR_GUIDES
+                .iter()
+                .map(|guide| guide.code)
+                .collect();
+            options.emit(&options.json_output(&codes)?)?;
+            return Ok(());
+        }
+        for guide in explain::ERROR_GUIDES {
+            println!("{:<30} {}", guide.code, guide.title);
+        }
+        return Ok(());
+    };
+
+    let guide = explain::guide(code).ok_or_else(|| HueStatusError::ValidationFailed {
+        reason: format!("No guide for error code '{code}'. Run 'huestatus explain' to list codes"),
+    })?;
+
+    if options.json {
+        let output = serde_json::json!({
+            "code": guide.code,
+            "title": guide.title,
+            "causes": guide.causes,
+            "fixes": guide.fixes,
+        });
+        options.emit(&options.json_output(&output)?)?;
+        return Ok(());
+    }
+
+    print!("{}", guide.render());
+    Ok(())
+}
+
+/// Execute history command
+fn execute_history_command(options: &GlobalOptions, filter: HistoryFilter) -> Result<()> {
+    let entries = history::load_entries(&history::history_file_path()?)?;
+    let entries = filter.apply(entries, chrono::Utc::now());
+    let summary = HistorySummary::from_entries(&entries);
+
+    if options.json {
+        let output = serde_json::json!({
+            "entries": entries,
+            "summary": summary,
+        });
+        options.emit(&options.json_output(&output)?)?;
+        return Ok(());
+    }
+
+    let counts: Vec<_> = summary
+        .by_status
+        .iter()
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect();
+    let noun = if summary.total == 1 {
+        "entry"
+    } else {
+        "entries"
+    };
+    let mut line = format!("{} {noun}", summary.total);
+    if !counts.is_empty() {
+        line.push_str(&format!(": {}", counts.join(", ")));
+    }
+    if summary.errors > 0 {
+        line.push_str(&format!(" ({} failed to show)", summary.errors));
+    }
+
+    if options.summary {
+        options.emit(&line)?;
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No history recorded in this window");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let outcome = match &entry.error {
+            Some(code) => style(format!("error: {code}")).red().to_string(),
+            None if entry.success => style("ok").green().to_string(),
+            None => style("failed").red().to_string(),
+        };
+        println!(
+            "{}  {:<8} {:>6}ms  {outcome}",
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            entry.status,
+            entry.execution_time_ms
+        );
+    }
+    println!();
+    println!("{line}");
+
+    Ok(())
+}
+
+/// Execute metrics command
+async fn execute_metrics_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+    let verbose = config.settings.verbose_logging;
+
+    let bridge = match Huestatus::new(config)?.status().await {
+        Ok(status) => Some(status),
+        Err(e) => {
+            if verbose {
+                eprintln!("🔍 Bridge status unavailable: {e}");
+            }
+            None
+        }
+    };
+    let entries = history::load_entries(&history::history_file_path()?)?;
+
+    print!("{}", metrics::render_metrics(&entries, bridge.as_ref()));
+    Ok(())
+}
+
+/// Execute off command
+async fn execute_off_command(options: &GlobalOptions) -> Result<()> {
+    let config = options.load_config()?;
+    let effective_verbose = config.settings.verbose_logging;
+    let effective_quiet = config.settings.quiet_mode;
+
+    let start_time = std::time::Instant::now();
+    let failures = Huestatus::new(config)?.off().await?;
+
+    if !effective_quiet {
+        show_light_failures(&failures);
+    }
+
+    if options.summary {
+        options.emit(&format!(
+            "off executed in {}ms",
+            start_time.elapsed().as_millis()
+        ))?;
+    } else if effective_verbose {
+        println!("✅ Lights turned off");
+    }
+
+    Ok(())
+}
+
+/// Execute brightness command
+async fn execute_brightness_command(
+    options: &GlobalOptions,
+    change: BrightnessChange,
+) -> Result<()> {
+    let config = options.load_config()?;
+    let effective_quiet = config.settings.quiet_mode;
+
+    let adjustment = Huestatus::new(config)?.adjust_brightness(change).await?;
+
+    if !effective_quiet {
+        show_light_failures(&adjustment.failures);
+    }
+
+    if options.json {
+        let output = serde_json::json!({
+            "previous": adjustment.previous,
+            "brightness": adjustment.brightness,
+            "failures": adjustment.failures,
+        });
+        options.emit(&options.json_output(&output)?)?;
+    } else if options.summary {
+        options.emit(&format!(
+            "brightness {} -> {}",
+            adjustment.previous, adjustment.brightness
+        ))?;
+    } else if !effective_quiet {
+        println!(
+            "🔆 Brightness {} → {}",
+            adjustment.previous, adjustment.brightness
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute bench command
+async fn execute_bench_command(options: &GlobalOptions, iterations: usize) -> Result<()> {
+    let config = options.load_config()?;
+    let stats = Huestatus::new(config)?.bench(iterations).await;
+
+    if options.json {
+        let output = serde_json::json!({
+            "iterations": stats.attempts(),
+            "errors": stats.errors,
+            "error_rate": stats.error_rate(),
+            "min_ms": stats.min(),
+            "median_ms": stats.median(),
+            "p95_ms": stats.p95(),
+            "max_ms": stats.max(),
+        });
+        options.emit(&options.json_output(&output)?)?;
+    } else if options.summary {
+        options.emit(&format!("bench {}", stats.summary()))?;
+    } else {
+        println!("⏱️  {} requests to the bridge", stats.attempts());
+        println!("   {}", stats.summary());
+    }
+
+    Ok(())
+}
+
+/// Discovery service for setup: the existing configuration's, else `HUESTATUS_DISCOVERY_URL`
+fn setup_discovery_url(options: &GlobalOptions) -> Option<String> {
+    match options.load_config() {
+        Ok(config) => config.settings.discovery_url,
+        Err(_) => std::env::var(DISCOVERY_URL_ENV_VAR)
+            .ok()
+            .filter(|url| !url.is_empty()),
+    }
+}
+
+/// Execute setup command
+async fn execute_setup_command(options: SetupOptions, global: &GlobalOptions) -> Result<()> {
+    let mut setup = SetupProcess::new().with_options(options.verbose, options.force, None);
+
+    if options.check_only {
+        let plan = setup.plan(&options).await?;
+        if global.summary {
+            global.emit(&plan.summary())?;
+        } else {
+            println!("📋 {}", style("Setup plan (no changes made)").bold());
+            for line in plan.lines() {
+                println!("  • {line}");
+            }
+        }
+        return Ok(());
+    }
+
+    let result = setup.run(&options).await?;
+
+    if global.json {
+        let output = serde_json::json!({
+            "success": result.success,
+            "bridge_ip": result.bridge_ip,
+            "bridge_name": result.bridge_name,
+            "username": mask_key(&result.username),
+            "scenes_created": result.scenes_created,
+            "scenes_updated": result.scenes_updated,
+            "lights_configured": result.lights_configured,
+            "config_path": result.config_path,
+            "duration_ms": result.duration_ms,
+            "quality_score": result.quality_score(),
+            "warnings": result.warnings,
+        });
+        global.emit(&global.json_output(&output)?)?;
+    } else if global.summary {
+        global.emit(&result.summary())?;
+    } else if options.verbose {
+        println!("Setup result: {}", result.summary());
+    }
+
+    Ok(())
+}
+
+/// Execute validate command
+async fn execute_validate_command(options: &GlobalOptions, show_states: bool) -> Result<()> {
+    let verbose = options.verbose;
 
     if verbose {
-        println!("✅ Bridge connection successful");
+        options.print_human("🔍 Validating configuration...");
+    }
+
+    // Load and validate configuration
+    let config = options.load_config()?;
+    if !options.quiet && !options.json && !options.summary {
+        println!("{}", config.summary());
+        println!();
+    }
+    if !options.quiet {
+        warn_similar_colors(&config);
     }
 
-    // Validate scenes
-    let scene_manager = SceneManager::new(client).with_verbose(verbose);
-    let validation_results = scene_manager.validate_status_scenes(&config).await?;
+    if verbose {
+        options.print_human("✅ Configuration is valid");
+    }
 
+    // Test bridge connection and validate scenes
+    let app = Huestatus::new(config)?;
+    let validation_results = app.validate().await?;
+    if !options.quiet {
+        warn_scene_light_overlap(&app, options).await;
+    }
+
+    let scenes_checked = validation_results.len();
     let mut total_issues = 0;
+    let mut unchecked = Vec::new();
+    let mut scenes = Vec::new();
     for result in validation_results {
-        if !result.is_valid {
+        if result.inconclusive {
+            if !options.quiet {
+                eprintln!("⚠️ {}", result.summary());
+            }
+            unchecked.push(result.scene_name.clone());
+        } else if !result.is_valid {
             total_issues += result.issues.len();
             if verbose {
-                println!("❌ Scene '{}' has issues:", result.scene_name);
+                options.print_human(&format!("❌ Scene '{}' has issues:", result.scene_name));
                 for issue in &result.issues {
-                    println!("  • {issue}");
+                    options.print_human(&format!("  • {issue}"));
                 }
             }
         } else if verbose {
-            println!("✅ Scene '{}' is valid", result.scene_name);
+            options.print_human(&format!("✅ Scene '{}' is valid", result.scene_name));
         }
-    }
 
-    if total_issues == 0 {
-        if !verbose {
-            println!("✅ All validations passed");
+        if options.json {
+            let lights: Vec<_> = result
+                .lights_status
+                .iter()
+                .map(|light| {
+                    serde_json::json!({
+                        "id": light.light_id,
+                        "name": light.light_name,
+                        "reachable": light.is_reachable,
+                        "state": light.current_state.as_ref().map(|state| state.color_summary()),
+                    })
+                })
+                .collect();
+            scenes.push(serde_json::json!({
+                "scene_id": result.scene_id,
+                "scene_name": result.scene_name,
+                "valid": result.is_valid,
+                "inconclusive": result.inconclusive,
+                "issues": result.issues,
+                "lights": show_states.then_some(lights),
+            }));
+        } else if show_states && !result.inconclusive && !options.quiet {
+            let mut lines = vec![format!("💡 Light states in '{}':", result.scene_name)];
+            lines.extend(
+                result
+                    .lights_status
+                    .iter()
+                    .map(|light| format!("  • {}", light_state_line(light))),
+            );
+            // --summary keeps stdout to its one line
+            for line in lines {
+                if options.summary {
+                    eprintln!("{line}");
+                } else {
+                    println!("{line}");
+                }
+            }
         }
-    } else {
+    }
+
+    // The report is written even when validation fails; the exit code tells which
+    if options.json {
+        let output = serde_json::json!({
+            "valid": total_issues == 0 && unchecked.is_empty(),
+            "scenes_checked": scenes_checked,
+            "issues": total_issues,
+            "unchecked": unchecked,
+            "scenes": scenes,
+        });
+        options.emit(&options.json_output(&output)?)?;
+    }
+
+    if total_issues > 0 {
         return Err(HueStatusError::ValidationFailed {
             reason: format!("Found {total_issues} validation issues"),
         });
     }
 
+    // A lookup that failed for network reasons says nothing about the scene itself
+    if !unchecked.is_empty() {
+        return Err(HueStatusError::BridgeConnectionFailed {
+            reason: format!("Could not check scene(s): {}", unchecked.join(", ")),
+        });
+    }
+
+    if options.json {
+        // Already reported above
+    } else if options.summary {
+        options.emit(&format!("validate passed: {scenes_checked} scenes valid"))?;
+    } else if !verbose {
+        println!("✅ All validations passed");
+    }
+
     Ok(())
 }
 
+/// One line of `validate --show-states`: the summary plus the raw state values
+fn light_state_line(light: &LightStatus) -> String {
+    let name = format!("{} ({})", light.light_name, light.light_id);
+    let Some(state) = &light.current_state else {
+        return format!("{name}: state unknown");
+    };
+
+    let raw: Vec<_> = [
+        ("hue", state.hue),
+        ("sat", state.sat.map(u16::from)),
+        ("bri", state.bri.map(u16::from)),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|value| format!("{label} {value}")))
+    .collect();
+    let mut line = format!("{name}: {}", state.color_summary());
+    if !raw.is_empty() {
+        line.push_str(&format!(" [{}]", raw.join(" ")));
+    }
+    if !light.is_reachable {
+        line.push_str(" (unreachable)");
+    }
+    line
+}
+
+/// Report the lights shared by the success and failure scenes
+///
+/// Warns when they are identical although `settings.distinct_scene_lights` is on.
+async fn warn_scene_light_overlap(app: &Huestatus, options: &GlobalOptions) {
+    let Ok(overlap) = app
+        .scene_manager()
+        .status_scene_light_overlap(app.config())
+        .await
+    else {
+        return;
+    };
+
+    if overlap.is_identical() && app.config().settings.distinct_scene_lights {
+        eprintln!(
+            "⚠️ Success and failure scenes should use distinct lights, but {}",
+            overlap.summary()
+        );
+    } else if options.verbose {
+        options.print_human(&format!("ℹ️ Status scene lights: {}", overlap.summary()));
+    }
+}
+
 /// Execute doctor command
-async fn execute_doctor_command() -> Result<()> {
+async fn execute_doctor_command(options: &GlobalOptions, fix: bool) -> Result<()> {
     let setup = SetupProcess::new();
-    setup.run_diagnostics().await
+    let diagnostics = setup.run_diagnostics().await?;
+
+    let mut repair = None;
+    let mut diagnostics_after_repair = None;
+    if fix {
+        let config_path = options
+            .writable_config_path()
+            .ok_or(HueStatusError::ConfigNotFound)?;
+
+        options.print_human("");
+        options.print_human("🔧 Repairing...");
+        let report = ConfigRepair::new(options.verbose, options.assume_yes)
+            .run(&config_path)
+            .await?;
+
+        if report.is_empty() {
+            options.print_human("✅ Nothing to fix");
+        }
+        for change in &report.changes {
+            options.print_human(&format!("  ✅ {change}"));
+        }
+        for skipped in &report.skipped {
+            options.print_human(&format!("  ⚠️ {skipped}"));
+        }
+
+        if !report.changes.is_empty() {
+            options.print_human("");
+            diagnostics_after_repair = Some(setup.run_diagnostics().await?);
+        }
+        repair = Some(report);
+    }
+
+    if options.json {
+        let output = serde_json::json!({
+            "diagnostics": diagnostics,
+            "repair": repair,
+            "diagnostics_after_repair": diagnostics_after_repair,
+        });
+        options.emit(&options.json_output(&output)?)?;
+    }
+
+    Ok(())
+}
+
+/// Report lights that failed while the rest of a group action succeeded
+fn show_light_failures(failures: &[String]) {
+    if failures.is_empty() {
+        return;
+    }
+
+    eprintln!("⚠️ {} problem(s) while showing the status:", failures.len());
+    for failure in failures {
+        eprintln!("  • {failure}");
+    }
 }
 
 /// Format error message for display
@@ -354,9 +2755,11 @@ fn format_error(error: &HueStatusError) -> String {
         HueStatusError::ConfigNotFound => "📁",
         HueStatusError::BridgeNotFound => "🔍",
         HueStatusError::AuthenticationFailed => "🔑",
-        HueStatusError::SceneNotFound { .. } => "🎬",
+        HueStatusError::SceneNotFound { .. }
+        | HueStatusError::AmbiguousSceneName { .. }
+        | HueStatusError::EmptyScene { .. } => "🎬",
         HueStatusError::NetworkError { .. } => "🌐",
-        HueStatusError::TimeoutError { .. } => "⏰",
+        HueStatusError::TimeoutError { .. } | HueStatusError::RateLimited { .. } => "⏰",
         _ => "❌",
     };
 
@@ -364,42 +2767,64 @@ fn format_error(error: &HueStatusError) -> String {
 }
 
 /// Show helpful suggestions based on error type
+///
+/// Written to stderr with the error itself, so piped `--json` output stays clean.
 fn show_error_suggestions(error: &HueStatusError) {
-    println!();
+    eprintln!();
 
-    match error {
-        HueStatusError::ConfigNotFound => {
-            println!("💡 {}", style("Try running:").bold());
-            println!("   huestatus setup");
-        }
-        HueStatusError::BridgeNotFound => {
-            println!("💡 {}", style("Suggestions:").bold());
-            println!("   • Ensure your Hue bridge is connected and powered on");
-            println!("   • Check that your device is on the same network as the bridge");
-            println!("   • Try running: huestatus setup");
-        }
-        HueStatusError::AuthenticationFailed => {
-            println!("💡 {}", style("Try running:").bold());
-            println!("   huestatus setup --force");
-        }
-        HueStatusError::SceneNotFound { .. } => {
-            println!("💡 {}", style("Try running:").bold());
-            println!("   huestatus setup --force");
-        }
-        HueStatusError::NetworkError { .. } | HueStatusError::TimeoutError { .. } => {
-            println!("💡 {}", style("Suggestions:").bold());
-            println!("   • Check your network connection");
-            println!("   • Verify the bridge IP address is correct");
-            println!("   • Try increasing the timeout with --timeout <seconds>");
-        }
-        _ => {
-            if error.is_recoverable_with_setup() {
-                println!("💡 {}", style("Try running:").bold());
-                println!("   huestatus setup --force");
-            }
+    let code = error.error_code();
+    if let Some(guide) = explain::guide(code) {
+        eprintln!("💡 {}", style("Suggestions:").bold());
+        for fix in guide.fixes {
+            eprintln!("   • {fix}");
         }
+        eprintln!();
+        eprintln!("For details: huestatus explain {code}");
+    } else if error.is_recoverable_with_setup() {
+        eprintln!("💡 {}", style("Try running:").bold());
+        eprintln!("   huestatus setup --force");
     }
 
-    println!();
-    println!("For more help: https://github.com/mimikun/huestatus");
+    eprintln!();
+    eprintln!("For more help: https://github.com/mimikun/huestatus");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_cli() {
+        create_cli().debug_assert();
+    }
+
+    #[test]
+    fn test_light_state_line() {
+        let light = |state: Option<serde_json::Value>, is_reachable: bool| LightStatus {
+            light_id: "3".to_string(),
+            light_name: "Desk".to_string(),
+            is_reachable,
+            supports_color: true,
+            current_state: state.map(|state| serde_json::from_value(state).unwrap()),
+        };
+
+        assert_eq!(
+            light_state_line(&light(None, true)),
+            "Desk (3): state unknown"
+        );
+        assert_eq!(
+            light_state_line(&light(
+                Some(serde_json::json!({"on": true, "bri": 254, "hue": 0, "sat": 254})),
+                true
+            )),
+            "Desk (3): on, 100%, red [hue 0 sat 254 bri 254]"
+        );
+        assert_eq!(
+            light_state_line(&light(
+                Some(serde_json::json!({"on": false, "bri": 1})),
+                false
+            )),
+            "Desk (3): off [bri 1] (unreachable)"
+        );
+    }
 }
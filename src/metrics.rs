@@ -0,0 +1,221 @@
+use crate::bridge::BridgeStatus;
+use crate::history::HistoryEntry;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Per-status execution counts derived from the history
+#[derive(Debug, Clone, Copy, Default)]
+struct StatusCounters {
+    total: u64,
+    errors: u64,
+    duration_sum_ms: u64,
+    apply_sum_ms: u64,
+    apply_count: u64,
+    last_timestamp: i64,
+}
+
+/// Render a Prometheus text exposition snapshot
+///
+/// Counters come from the whole history log; the bridge gauges are omitted
+/// (and `huestatus_bridge_up` is 0) when the bridge status is unavailable.
+pub fn render_metrics(entries: &[HistoryEntry], bridge: Option<&BridgeStatus>) -> String {
+    let mut counters: BTreeMap<&str, StatusCounters> = BTreeMap::new();
+    for entry in entries {
+        let counter = counters.entry(entry.status.as_str()).or_default();
+        counter.total += 1;
+        if !entry.success {
+            counter.errors += 1;
+        }
+        counter.duration_sum_ms += entry.execution_time_ms;
+        if let Some(apply_latency_ms) = entry.apply_latency_ms {
+            counter.apply_sum_ms += apply_latency_ms;
+            counter.apply_count += 1;
+        }
+        counter.last_timestamp = counter.last_timestamp.max(entry.timestamp.timestamp());
+    }
+
+    let mut out = String::new();
+
+    write_header(
+        &mut out,
+        "huestatus_executions_total",
+        "counter",
+        "Status commands run",
+    );
+    for (status, counter) in &counters {
+        let _ = writeln!(
+            out,
+            "huestatus_executions_total{{status=\"{status}\"}} {}",
+            counter.total
+        );
+    }
+
+    write_header(
+        &mut out,
+        "huestatus_execution_errors_total",
+        "counter",
+        "Status commands that failed to show their status",
+    );
+    for (status, counter) in &counters {
+        let _ = writeln!(
+            out,
+            "huestatus_execution_errors_total{{status=\"{status}\"}} {}",
+            counter.errors
+        );
+    }
+
+    write_header(
+        &mut out,
+        "huestatus_execution_duration_ms",
+        "summary",
+        "Time taken to show a status, in milliseconds",
+    );
+    for (status, counter) in &counters {
+        let _ = writeln!(
+            out,
+            "huestatus_execution_duration_ms_sum{{status=\"{status}\"}} {}",
+            counter.duration_sum_ms
+        );
+        let _ = writeln!(
+            out,
+            "huestatus_execution_duration_ms_count{{status=\"{status}\"}} {}",
+            counter.total
+        );
+    }
+
+    write_header(
+        &mut out,
+        "huestatus_apply_latency_ms",
+        "summary",
+        "Time from the recall ACK until a light showed the scene (--measure-apply), in milliseconds",
+    );
+    for (status, counter) in counters
+        .iter()
+        .filter(|(_, counter)| counter.apply_count > 0)
+    {
+        let _ = writeln!(
+            out,
+            "huestatus_apply_latency_ms_sum{{status=\"{status}\"}} {}",
+            counter.apply_sum_ms
+        );
+        let _ = writeln!(
+            out,
+            "huestatus_apply_latency_ms_count{{status=\"{status}\"}} {}",
+            counter.apply_count
+        );
+    }
+
+    write_header(
+        &mut out,
+        "huestatus_last_execution_timestamp_seconds",
+        "gauge",
+        "Unix time of the most recent status command",
+    );
+    for (status, counter) in &counters {
+        let _ = writeln!(
+            out,
+            "huestatus_last_execution_timestamp_seconds{{status=\"{status}\"}} {}",
+            counter.last_timestamp
+        );
+    }
+
+    write_header(
+        &mut out,
+        "huestatus_bridge_up",
+        "gauge",
+        "Whether the bridge answered",
+    );
+    let _ = writeln!(out, "huestatus_bridge_up {}", u8::from(bridge.is_some()));
+
+    if let Some(bridge) = bridge {
+        let gauges = [
+            (
+                "huestatus_total_lights",
+                "Lights known to the bridge",
+                bridge.total_lights,
+            ),
+            (
+                "huestatus_reachable_lights",
+                "Lights currently reachable",
+                bridge.reachable_lights,
+            ),
+            (
+                "huestatus_suitable_lights",
+                "Reachable color lights usable for status",
+                bridge.suitable_lights,
+            ),
+            (
+                "huestatus_scenes",
+                "Scenes stored on the bridge",
+                bridge.total_scenes,
+            ),
+        ];
+        for (name, help, value) in gauges {
+            write_header(&mut out, name, "gauge", help);
+            let _ = writeln!(out, "{name} {value}");
+        }
+    }
+
+    out
+}
+
+/// Write the HELP and TYPE lines for a metric
+fn write_header(out: &mut String, name: &str, metric_type: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {metric_type}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(status: &str, success: bool, execution_time_ms: u64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            status: status.to_string(),
+            success,
+            scene_id: None,
+            execution_time_ms,
+            apply_latency_ms: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let mut entries = vec![
+            entry("success", true, 40),
+            entry("success", true, 60),
+            entry("failure", false, 0),
+        ];
+        entries[1].apply_latency_ms = Some(700);
+        let bridge = BridgeStatus {
+            bridge_name: "Hue Bridge".to_string(),
+            bridge_id: "001788fffe000000".to_string(),
+            api_version: "1.60.0".to_string(),
+            sw_version: "1960000000".to_string(),
+            total_lights: 4,
+            reachable_lights: 3,
+            suitable_lights: 2,
+            total_scenes: 12,
+            available_scenes: None,
+            max_scenes: None,
+            warnings: Vec::new(),
+        };
+
+        let text = render_metrics(&entries, Some(&bridge));
+        assert!(text.contains("huestatus_executions_total{status=\"success\"} 2\n"));
+        assert!(text.contains("huestatus_execution_errors_total{status=\"failure\"} 1\n"));
+        assert!(text.contains("huestatus_execution_duration_ms_sum{status=\"success\"} 100\n"));
+        assert!(text.contains("huestatus_apply_latency_ms_sum{status=\"success\"} 700\n"));
+        assert!(text.contains("huestatus_apply_latency_ms_count{status=\"success\"} 1\n"));
+        assert!(!text.contains("huestatus_apply_latency_ms_count{status=\"failure\"}"));
+        assert!(text.contains("huestatus_bridge_up 1\n"));
+        assert!(text.contains("huestatus_reachable_lights 3\n"));
+
+        let offline = render_metrics(&entries, None);
+        assert!(offline.contains("huestatus_bridge_up 0\n"));
+        assert!(!offline.contains("huestatus_reachable_lights"));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::error::{HueStatusError, Result};
+use console::{pad_str, style, Alignment};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Version of the `--json` output shapes
+///
+/// Incremented whenever a field is removed, renamed, or changes type in any
+/// command's JSON output. New fields may be added without a bump.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// `--json` output: a command result tagged with the schema version
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonEnvelope<'a, T: Serialize + ?Sized> {
+    /// Always `JSON_SCHEMA_VERSION`
+    pub schema_version: u32,
+    /// The command result
+    pub data: &'a T,
+}
+
+/// Wrap a command result as `{ "schema_version": N, "data": ... }`
+pub fn versioned<T: Serialize + ?Sized>(data: &T) -> JsonEnvelope<'_, T> {
+    JsonEnvelope {
+        schema_version: JSON_SCHEMA_VERSION,
+        data,
+    }
+}
+
+/// Replace a file's contents in one step, creating parent directories as needed
+///
+/// The contents go to a temporary file next to `path` that is then renamed
+/// over it, so readers never see a partially written file.
+pub fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| HueStatusError::ValidationFailed {
+            reason: format!("'{}' is not a file path", path.display()),
+        })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })?;
+    Ok(())
+}
+
+/// Presentation of list command results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Space-aligned lines, easy to grep
+    #[default]
+    Plain,
+    /// Bordered table with a header row (plain when stdout isn't a terminal)
+    Table,
+}
+
+impl OutputFormat {
+    /// Whether to draw a table; falls back to plain text when stdout isn't a terminal
+    pub fn use_table(self) -> bool {
+        self == Self::Table && console::Term::stdout().is_term()
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "table" => Ok(Self::Table),
+            _ => Err(format!("unknown format '{value}' (use plain or table)")),
+        }
+    }
+}
+
+/// Minimal bordered table renderer
+///
+/// Widths are measured without ANSI escapes, so styled cells (e.g. color
+/// swatches) line up.
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    right_aligned: Vec<bool>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create a table with the given column headers
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            right_aligned: vec![false; headers.len()],
+            rows: Vec::new(),
+        }
+    }
+
+    /// Right-align a column (e.g. counts)
+    pub fn align_right(mut self, column: usize) -> Self {
+        if let Some(right) = self.right_aligned.get_mut(column) {
+            *right = true;
+        }
+        self
+    }
+
+    /// Add a row; missing cells are left empty
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Column widths, wide enough for the header and every cell
+    fn widths(&self) -> Vec<usize> {
+        self.headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| console::measure_text_width(cell))
+                    .fold(console::measure_text_width(header), usize::max)
+            })
+            .collect()
+    }
+
+    /// Pad a row's cells to the column widths
+    fn pad_cells(&self, cells: &[String], widths: &[usize]) -> Vec<String> {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = cells.get(i).map(String::as_str).unwrap_or("");
+                let alignment = if self.right_aligned[i] {
+                    Alignment::Right
+                } else {
+                    Alignment::Left
+                };
+                pad_str(cell, *width, alignment, None).into_owned()
+            })
+            .collect()
+    }
+
+    /// Render as bordered table with a bold header
+    pub fn render(&self) -> String {
+        let widths = self.widths();
+        let border = |left: &str, middle: &str, right: &str| {
+            let segments: Vec<_> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+            format!("{left}{}{right}\n", segments.join(middle))
+        };
+        let line = |cells: Vec<String>| format!("│ {} │\n", cells.join(" │ "));
+
+        let headers = self
+            .pad_cells(&self.headers, &widths)
+            .into_iter()
+            .map(|header| style(header).bold().to_string())
+            .collect();
+
+        let mut out = border("┌", "┬", "┐");
+        out.push_str(&line(headers));
+        out.push_str(&border("├", "┼", "┤"));
+        for row in &self.rows {
+            out.push_str(&line(self.pad_cells(row, &widths)));
+        }
+        out.push_str(&border("└", "┴", "┘"));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new(&["ID", "NAME", "LIGHTS"]).align_right(2);
+        table.add_row(vec![
+            "1".to_string(),
+            "Living room".to_string(),
+            "3".to_string(),
+        ]);
+        table.add_row(vec![
+            "12".to_string(),
+            "Office".to_string(),
+            "10".to_string(),
+        ]);
+        table
+    }
+
+    #[test]
+    fn test_versioned_json() {
+        let value = serde_json::to_value(versioned(&["a", "b"])).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "schema_version": JSON_SCHEMA_VERSION, "data": ["a", "b"] })
+        );
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("results").join("status.json");
+
+        write_atomically(&path, "first\n").unwrap();
+        write_atomically(&path, "second\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_output_format_parse() {
+        assert_eq!("table".parse(), Ok(OutputFormat::Table));
+        assert_eq!("Plain".parse(), Ok(OutputFormat::Plain));
+        assert!("csv".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rendered = console::strip_ansi_codes(&sample().render()).into_owned();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "┌────┬─────────────┬────────┐");
+        assert_eq!(lines[1], "│ ID │ NAME        │ LIGHTS │");
+        assert_eq!(lines[3], "│ 1  │ Living room │      3 │");
+    }
+}
@@ -1,6 +1,6 @@
-use crate::bridge::{BridgeClient, CreateSceneRequest, Light, LightState};
+use crate::bridge::{huestatus_appdata, BridgeClient, CreateSceneRequest, Light, LightState};
 use crate::error::{HueStatusError, Result};
-use crate::scenes::ColorDefinition;
+use crate::scenes::{ColorDefinition, DEFAULT_MAX_LIGHTS_PER_SCENE};
 use std::collections::HashMap;
 
 /// Scene creation builder for customizing scene creation
@@ -9,9 +9,13 @@ pub struct SceneBuilder {
     name: String,
     lights: Vec<String>,
     color: Option<ColorDefinition>,
+    gradient: Vec<ColorDefinition>,
     brightness: Option<u8>,
+    alternating_brightness: Option<(u8, u8)>,
+    effects: HashMap<String, String>,
     recyclable: bool,
     custom_states: HashMap<String, LightState>,
+    max_lights: usize,
 }
 
 /// Scene creation options
@@ -42,9 +46,13 @@ impl SceneBuilder {
             name,
             lights: Vec::new(),
             color: None,
+            gradient: Vec::new(),
             brightness: None,
+            alternating_brightness: None,
+            effects: HashMap::new(),
             recyclable: true,
             custom_states: HashMap::new(),
+            max_lights: DEFAULT_MAX_LIGHTS_PER_SCENE,
         }
     }
 
@@ -60,12 +68,46 @@ impl SceneBuilder {
         self
     }
 
+    /// Spread colors across the lights in order, repeating them if there are more lights
+    pub fn with_gradient(mut self, colors: Vec<ColorDefinition>) -> Self {
+        self.gradient = colors;
+        self
+    }
+
     /// Set brightness for all lights
     pub fn with_brightness(mut self, brightness: u8) -> Self {
         self.brightness = Some(brightness);
         self
     }
 
+    /// Alternate lights between max and min brightness, starting with max
+    pub fn with_alternating_brightness(mut self, min_brightness: u8, max_brightness: u8) -> Self {
+        self.alternating_brightness = Some((min_brightness, max_brightness));
+        self
+    }
+
+    /// Set a light effect (e.g. `colorloop`); lights that don't support it get `none`
+    pub fn with_effect(mut self, effect: &str, lights: &[(String, Light)]) -> Self {
+        self.effects = lights
+            .iter()
+            .map(|(id, light)| {
+                let effect = if light.supports_effect(effect) {
+                    effect
+                } else {
+                    "none"
+                };
+                (id.clone(), effect.to_string())
+            })
+            .collect();
+        self
+    }
+
+    /// Limit how many lights the scene may hold (see `scene_light_limit`)
+    pub fn with_max_lights(mut self, max_lights: usize) -> Self {
+        self.max_lights = max_lights;
+        self
+    }
+
     /// Set whether scene should be recyclable
     pub fn recyclable(mut self, recyclable: bool) -> Self {
         self.recyclable = recyclable;
@@ -92,17 +134,30 @@ impl SceneBuilder {
             });
         }
 
+        if self.lights.len() > self.max_lights {
+            return Err(HueStatusError::InvalidSceneData {
+                reason: format!(
+                    "Scene has {} lights, more than the limit of {} per scene",
+                    self.lights.len(),
+                    self.max_lights
+                ),
+            });
+        }
+
         let mut lightstates = HashMap::new();
 
         // Create light states
-        for light_id in &self.lights {
-            let light_state = if let Some(custom_state) = self.custom_states.get(light_id) {
+        for (i, light_id) in self.lights.iter().enumerate() {
+            let gradient_color = self.gradient.get(i % self.gradient.len().max(1));
+            let mut light_state = if let Some(custom_state) = self.custom_states.get(light_id) {
                 // Use custom state
                 custom_state.clone()
-            } else if let Some(color) = &self.color {
+            } else if let Some(color) = gradient_color.or(self.color.as_ref()) {
                 // Use specified color
                 let mut state = color.to_light_state();
-                if let Some(brightness) = self.brightness {
+                if let Some((min, max)) = self.alternating_brightness {
+                    state.bri = Some(if i % 2 == 0 { max } else { min });
+                } else if let Some(brightness) = self.brightness {
                     state.bri = Some(brightness);
                 }
                 state
@@ -123,6 +178,10 @@ impl SceneBuilder {
                 }
             };
 
+            if let Some(effect) = self.effects.get(light_id) {
+                light_state.effect = Some(effect.clone());
+            }
+
             lightstates.insert(light_id.clone(), light_state);
         }
 
@@ -131,6 +190,7 @@ impl SceneBuilder {
             lights: self.lights,
             recycle: self.recyclable,
             lightstates,
+            appdata: Some(huestatus_appdata()),
         })
     }
 
@@ -388,20 +448,11 @@ impl SceneCreator {
             );
         }
 
-        let mut scene_builder = SceneBuilder::new(name).with_lights(lights.clone());
-
-        // Distribute colors across lights
-        for (i, light_id) in lights.iter().enumerate() {
-            let color_index = i % colors.len();
-            let color = &colors[color_index];
-
-            let mut state = color.to_light_state();
-            state.bri = Some(options.brightness);
-
-            scene_builder = scene_builder.with_light_state(light_id.clone(), state);
-        }
-
-        let scene_request = scene_builder.build_validated()?;
+        let scene_request = SceneBuilder::new(name)
+            .with_lights(lights)
+            .with_gradient(colors)
+            .with_brightness(options.brightness)
+            .build_validated()?;
         let response = self.client.create_scene(&scene_request).await?;
 
         let scene_id = response
@@ -421,6 +472,9 @@ impl SceneCreator {
     }
 
     /// Create breathing effect scene
+    ///
+    /// Hue scenes can't animate, so this is a static snapshot with every other
+    /// light dimmed; the breathing look needs repeated recalls.
     pub async fn create_breathing_scene(
         &self,
         name: String,
@@ -441,24 +495,11 @@ impl SceneCreator {
             );
         }
 
-        let mut scene_builder = SceneBuilder::new(name).with_lights(lights.clone());
-
-        // Create alternating brightness levels
-        for (i, light_id) in lights.iter().enumerate() {
-            let brightness = if i % 2 == 0 {
-                max_brightness
-            } else {
-                min_brightness
-            };
-
-            let mut state = base_color.to_light_state();
-            state.bri = Some(brightness);
-            state.effect = None; // Remove unsupported effect to prevent API errors
-
-            scene_builder = scene_builder.with_light_state(light_id.clone(), state);
-        }
-
-        let scene_request = scene_builder.build_validated()?;
+        let scene_request = SceneBuilder::new(name)
+            .with_lights(lights)
+            .with_color(base_color)
+            .with_alternating_brightness(min_brightness, max_brightness)
+            .build_validated()?;
         let response = self.client.create_scene(&scene_request).await?;
 
         let scene_id = response
@@ -570,7 +611,9 @@ impl SceneCreator {
             }
         }
 
-        let scene_request = scene_builder.build_validated()?;
+        let mut scene_request = scene_builder.build_validated()?;
+        // Clones belong to the user; without this, doctor --fix would see orphans
+        scene_request.appdata = None;
         let response = self.client.create_scene(&scene_request).await?;
 
         let scene_id = response
@@ -659,6 +702,30 @@ impl ColorPresets {
             Self::purple(),
         ]
     }
+
+    /// Find a preset by name, ignoring case, spaces, hyphens and underscores
+    pub fn by_name(name: &str) -> Option<ColorDefinition> {
+        let wanted = normalize_preset_name(name);
+        Self::all_presets()
+            .into_iter()
+            .find(|preset| normalize_preset_name(&preset.name) == wanted)
+    }
+
+    /// Preset names as accepted on the command line (e.g. `warm-white`)
+    pub fn names() -> Vec<String> {
+        Self::all_presets()
+            .iter()
+            .map(|preset| preset.name.to_lowercase().replace(' ', "-"))
+            .collect()
+    }
+}
+
+/// Reduce a preset name to lowercase letters and digits for comparison
+fn normalize_preset_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
 }
 
 #[cfg(test)]
@@ -681,6 +748,71 @@ mod tests {
         assert_eq!(request.lightstates.len(), 2);
     }
 
+    #[test]
+    fn test_scene_builder_gradient() {
+        let request = SceneBuilder::new("Gradient".to_string())
+            .with_lights(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+            .with_gradient(vec![ColorPresets::blue(), ColorPresets::orange()])
+            .with_brightness(100)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.lightstates["1"].hue, Some(43690));
+        assert_eq!(request.lightstates["2"].hue, Some(5461));
+        assert_eq!(request.lightstates["3"].hue, Some(43690));
+        assert_eq!(request.lightstates["2"].bri, Some(100));
+    }
+
+    #[test]
+    fn test_scene_builder_alternating_brightness() {
+        let request = SceneBuilder::new("Breathing".to_string())
+            .with_lights(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+            .with_color(ColorPresets::orange())
+            .with_alternating_brightness(40, 240)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.lightstates["1"].bri, Some(240));
+        assert_eq!(request.lightstates["2"].bri, Some(40));
+        assert_eq!(request.lightstates["3"].bri, Some(240));
+    }
+
+    #[test]
+    fn test_scene_builder_effect() {
+        let light = |id: &str, color: bool| {
+            let control = if color {
+                serde_json::json!({
+                    "colorgamuttype": "C",
+                    "colorgamut": [[0.69, 0.31], [0.17, 0.7], [0.15, 0.02]],
+                })
+            } else {
+                serde_json::json!({})
+            };
+            let light: Light = serde_json::from_value(serde_json::json!({
+                "name": format!("Light {id}"),
+                "type": "Extended color light",
+                "state": { "on": true },
+                "capabilities": { "certified": true, "control": control },
+            }))
+            .unwrap();
+            (id.to_string(), light)
+        };
+        let lights = vec![light("1", true), light("2", false)];
+
+        let request = SceneBuilder::new("Party".to_string())
+            .with_lights(vec!["1".to_string(), "2".to_string()])
+            .with_color(ColorPresets::blue())
+            .with_effect("colorloop", &lights)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.lightstates["1"].effect.as_deref(),
+            Some("colorloop")
+        );
+        assert_eq!(request.lightstates["2"].effect.as_deref(), Some("none"));
+    }
+
     #[test]
     fn test_light_selection_criteria() {
         let criteria = LightSelectionCriteria::for_status_scenes();
@@ -715,6 +847,24 @@ mod tests {
         assert_eq!(all_presets.len(), 5);
     }
 
+    #[test]
+    fn test_color_preset_by_name() {
+        assert_eq!(ColorPresets::by_name("blue").unwrap().hue, 43690);
+        assert_eq!(
+            ColorPresets::by_name("Warm White").unwrap().name,
+            "Warm White"
+        );
+        assert_eq!(
+            ColorPresets::by_name("cool_white").unwrap().name,
+            "Cool White"
+        );
+        assert!(ColorPresets::by_name("magenta").is_none());
+
+        for name in ColorPresets::names() {
+            assert!(ColorPresets::by_name(&name).is_some(), "{name}");
+        }
+    }
+
     #[test]
     fn test_scene_builder_validation() {
         // Empty name should fail
@@ -728,6 +878,12 @@ mod tests {
         // Valid builder should succeed
         let builder = SceneBuilder::new("Test".to_string()).with_lights(vec!["1".to_string()]);
         assert!(builder.build().is_ok());
+
+        // Too many lights should fail
+        let builder = SceneBuilder::new("Test".to_string())
+            .with_lights(vec!["1".to_string(), "2".to_string()])
+            .with_max_lights(1);
+        assert!(builder.build().is_err());
     }
 
     #[test]
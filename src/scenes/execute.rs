@@ -1,9 +1,22 @@
-use crate::bridge::BridgeClient;
+use crate::bridge::{BridgeClient, Light, LightState, Scene};
 use crate::config::Config;
-use crate::error::{HueStatusError, Result};
-use crate::scenes::{SceneExecutionResult, SceneValidationResult};
+use crate::error::{cancelled, check_cancelled, HueStatusError, Result};
+use crate::scenes::{
+    ensure_scene_has_lights, is_scene_absent_error, SceneExecutionResult, SceneValidationResult,
+};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::time::{sleep, timeout};
+use tokio_util::sync::CancellationToken;
+
+/// Time to let the bridge settle before re-reading light states after a recall
+const STATE_SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// How often a light is polled while measuring apply latency
+const APPLY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Give up measuring apply latency after this long
+const APPLY_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Scene execution manager with advanced features
 #[derive(Debug, Clone)]
@@ -12,6 +25,7 @@ pub struct SceneExecutor {
     verbose: bool,
     retry_attempts: usize,
     retry_delay: Duration,
+    cancellation: Option<CancellationToken>,
 }
 
 /// Execution options for fine-tuning scene execution
@@ -24,6 +38,7 @@ pub struct ExecutionOptions {
     pub retry_delay_ms: u64,
     pub measure_performance: bool,
     pub restore_previous_state: bool,
+    pub verify_state_change: bool,
 }
 
 /// Scene execution strategy
@@ -46,7 +61,7 @@ pub enum ExecutionStrategy {
 pub struct LightStateBackup {
     pub light_id: String,
     pub light_name: String,
-    pub previous_state: crate::bridge::LightState,
+    pub previous_state: LightState,
     pub timestamp: Instant,
 }
 
@@ -60,6 +75,14 @@ pub struct ExecutionContext {
     pub backup_states: Vec<LightStateBackup>,
 }
 
+/// Per-light change summary captured around a scene recall
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LightChangeSummary {
+    pub lights_checked: usize,
+    pub lights_changed: usize,
+    pub unchanged_lights: Vec<String>,
+}
+
 /// Execution performance metrics
 #[derive(Debug, Clone)]
 pub struct ExecutionMetrics {
@@ -80,6 +103,7 @@ impl SceneExecutor {
             verbose: false,
             retry_attempts: 3,
             retry_delay: Duration::from_secs(1),
+            cancellation: None,
         }
     }
 
@@ -96,6 +120,30 @@ impl SceneExecutor {
         self
     }
 
+    /// Stop executing when the token is cancelled
+    ///
+    /// The token is checked before each attempt and during delays, so a
+    /// recall that was already sent to the bridge is never cut off halfway.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Fail with `Cancelled` if the token has been cancelled
+    fn check_cancelled(&self) -> Result<()> {
+        check_cancelled(self.cancellation.as_ref(), "Scene execution")
+    }
+
+    /// Sleep for `delay`, failing with `Cancelled` if the token fires first
+    async fn pause(&self, delay: Duration) -> Result<()> {
+        tokio::select! {
+            _ = sleep(delay) => Ok(()),
+            _ = cancelled(self.cancellation.as_ref()) => Err(HueStatusError::Cancelled {
+                operation: "Scene execution".to_string(),
+            }),
+        }
+    }
+
     /// Execute a status scene with default options
     pub async fn execute_status_scene(
         &self,
@@ -155,11 +203,22 @@ impl SceneExecutor {
             );
             eprintln!("📋 Strategy: {:?}", context.strategy);
         }
+        self.check_cancelled()?;
+
+        // Fetch the scene and lights once for both validation and backup
+        let validate = context.options.validate_before_execution
+            || matches!(context.strategy, ExecutionStrategy::ValidatedExecution);
+        let backup = context.options.restore_previous_state || context.options.verify_state_change;
+        let snapshot = if validate || backup {
+            Some(self.fetch_scene_snapshot(&context.scene_id).await?)
+        } else {
+            None
+        };
 
         // Validation phase
-        if context.options.validate_before_execution {
+        if let (true, Some((scene, lights))) = (validate, &snapshot) {
             let validation_start = Instant::now();
-            self.validate_scene_execution(&context.scene_id).await?;
+            check_scene_execution(scene, lights)?;
             metrics.validation_time_ms = validation_start.elapsed().as_millis() as u64;
 
             if self.verbose {
@@ -171,9 +230,9 @@ impl SceneExecutor {
         }
 
         // Backup phase
-        if context.options.restore_previous_state {
+        if let (true, Some((scene, lights))) = (backup, &snapshot) {
             let backup_start = Instant::now();
-            context.backup_states = self.backup_current_states(&context.scene_id).await?;
+            context.backup_states = light_backups(scene, lights);
             metrics.backup_time_ms = backup_start.elapsed().as_millis() as u64;
 
             if self.verbose {
@@ -188,6 +247,27 @@ impl SceneExecutor {
         // Execution phase with retry logic
         let execution_result = self.execute_with_retry(&context, &mut metrics).await;
 
+        // Verification phase; the scene is already recalled, so a failed check is only a warning
+        let mut warnings = Vec::new();
+        let state_change = match &execution_result {
+            Ok(_) if context.options.verify_state_change => {
+                match self
+                    .capture_state_change(&context.scene_id, &context.backup_states)
+                    .await
+                {
+                    Ok(summary) => {
+                        metrics.lights_affected = summary.lights_changed;
+                        Some(summary)
+                    }
+                    Err(e) => {
+                        warnings.push(format!("Could not verify light changes: {e}"));
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         metrics.total_time_ms = start_time.elapsed().as_millis() as u64;
         metrics.success = execution_result.is_ok();
 
@@ -201,6 +281,11 @@ impl SceneExecutor {
                 scene_name: context.scene_name,
                 execution_time_ms: execution_time,
                 success: true,
+                state_change,
+                apply_latency_ms: None,
+                already_showing: false,
+                warnings,
+                retry_count: metrics.retry_count,
             }),
             Err(e) => {
                 if self.verbose {
@@ -227,6 +312,13 @@ impl SceneExecutor {
 
         for attempt in 0..max_attempts {
             if attempt > 0 {
+                // Requests already retried inside the client count against the same budget
+                if !self.client.retry_budget().try_consume() {
+                    if self.verbose {
+                        eprintln!("⏳ Retry budget for this command used up");
+                    }
+                    break;
+                }
                 metrics.retry_count += 1;
                 let delay = Duration::from_millis(context.options.retry_delay_ms);
 
@@ -239,14 +331,16 @@ impl SceneExecutor {
                     );
                 }
 
-                sleep(delay).await;
+                self.pause(delay).await?;
             }
+            self.check_cancelled()?;
 
             match self.execute_single_attempt(context).await {
                 Ok(execution_time) => {
                     metrics.execution_time_ms = execution_time;
                     return Ok(execution_time);
                 }
+                Err(e @ HueStatusError::Cancelled { .. }) => return Err(e),
                 Err(e) => {
                     last_error = Some(e);
 
@@ -274,7 +368,7 @@ impl SceneExecutor {
                     .await?;
             }
             ExecutionStrategy::Delayed(delay) => {
-                sleep(*delay).await;
+                self.pause(*delay).await?;
                 self.execute_immediate(&context.scene_id, context.options.timeout_ms)
                     .await?;
             }
@@ -283,7 +377,7 @@ impl SceneExecutor {
                     .await?;
             }
             ExecutionStrategy::ValidatedExecution => {
-                self.validate_scene_execution(&context.scene_id).await?;
+                // Validation already ran before the first attempt
                 self.execute_immediate(&context.scene_id, context.options.timeout_ms)
                     .await?;
             }
@@ -313,70 +407,110 @@ impl SceneExecutor {
         Ok(())
     }
 
-    /// Execute scene with fade effect (simulated)
+    /// Execute scene with fade effect using the bridge transition time
     async fn execute_with_fade(&self, scene_id: &str, duration_ms: u64) -> Result<()> {
         if self.verbose {
             eprintln!("🌅 Executing scene with fade effect ({duration_ms}ms)");
         }
 
-        // For now, just execute immediately
-        // In a full implementation, this would gradually transition the lights
-        self.execute_immediate(scene_id, duration_ms + 5000).await?;
+        // Hue transition times are expressed in deciseconds
+        let transition_time = u16::try_from(duration_ms / 100).unwrap_or(u16::MAX);
+        let client = self
+            .client
+            .clone()
+            .with_transition_time(Some(transition_time));
+        let execution_timeout = Duration::from_millis(duration_ms + 5000);
+
+        timeout(execution_timeout, client.execute_scene(scene_id))
+            .await
+            .map_err(|_| HueStatusError::TimeoutError {
+                operation: format!("Scene execution for {scene_id}"),
+            })?
+            .map_err(|e| HueStatusError::SceneExecutionFailed {
+                reason: e.to_string(),
+            })?;
 
         Ok(())
     }
 
-    /// Validate scene before execution
-    async fn validate_scene_execution(&self, scene_id: &str) -> Result<()> {
-        // Check if scene exists
+    /// Fetch a scene together with the current state of all lights
+    async fn fetch_scene_snapshot(
+        &self,
+        scene_id: &str,
+    ) -> Result<(Scene, HashMap<String, Light>)> {
         let scene = self.client.get_scene(scene_id).await?;
+        let lights = self.client.get_lights().await?;
+        Ok((scene, lights))
+    }
 
-        // Check if scene is suitable for execution
-        if !scene.is_suitable_for_status() {
-            return Err(HueStatusError::ValidationFailed {
-                reason: format!("Scene '{}' is not suitable for execution", scene.name),
-            });
-        }
+    /// Backup current light states
+    pub async fn backup_current_states(&self, scene_id: &str) -> Result<Vec<LightStateBackup>> {
+        let (scene, lights) = self.fetch_scene_snapshot(scene_id).await?;
+        Ok(light_backups(&scene, &lights))
+    }
 
-        // Check if lights are reachable
-        let lights = self.client.get_lights().await?;
-        let mut unreachable_lights = Vec::new();
+    /// Re-read light states after a recall and compare them with a backup
+    pub async fn capture_state_change(
+        &self,
+        scene_id: &str,
+        before: &[LightStateBackup],
+    ) -> Result<LightChangeSummary> {
+        sleep(STATE_SETTLE_DELAY).await;
 
-        for light_id in &scene.lights {
-            if let Some(light) = lights.get(light_id) {
-                if !light.is_reachable() {
-                    unreachable_lights.push(light.name.clone());
-                }
-            }
-        }
+        let after = self.backup_current_states(scene_id).await?;
+        let summary = diff_light_states(before, &after);
 
-        if !unreachable_lights.is_empty() {
-            return Err(HueStatusError::ValidationFailed {
-                reason: format!("Unreachable lights: {}", unreachable_lights.join(", ")),
-            });
+        if self.verbose {
+            eprintln!("🔎 Light changes: {}", summary.summary());
         }
 
-        Ok(())
+        Ok(summary)
     }
 
-    /// Backup current light states
-    async fn backup_current_states(&self, scene_id: &str) -> Result<Vec<LightStateBackup>> {
-        let scene = self.client.get_scene(scene_id).await?;
-        let lights = self.client.get_lights().await?;
-        let mut backups = Vec::new();
+    /// Measure how long after a recall ACK one of the scene's lights reports the scene state
+    ///
+    /// Polls one reachable light of the scene. Returns `None` if no light
+    /// can be checked or the state isn't reached within `APPLY_TIMEOUT`.
+    pub async fn measure_apply_latency(&self, scene_id: &str) -> Result<Option<Duration>> {
+        let acked_at = Instant::now();
+        let (scene, lights) = self.fetch_scene_snapshot(scene_id).await?;
+        let Some((light_id, expected)) = scene
+            .lightstates
+            .iter()
+            .flatten()
+            .find(|(id, _)| lights.get(*id).is_some_and(Light::is_reachable))
+        else {
+            return Ok(None);
+        };
 
-        for light_id in &scene.lights {
-            if let Some(light) = lights.get(light_id) {
-                backups.push(LightStateBackup {
-                    light_id: light_id.clone(),
-                    light_name: light.name.clone(),
-                    previous_state: light.state.clone(),
-                    timestamp: Instant::now(),
-                });
+        while acked_at.elapsed() < APPLY_TIMEOUT {
+            let light = self.client.get_light(light_id).await?;
+            if light_state_matches(expected, &light.state) {
+                let latency = acked_at.elapsed();
+                if self.verbose {
+                    eprintln!(
+                        "🔎 Light {light_id} applied the scene after {}ms",
+                        latency.as_millis()
+                    );
+                }
+                return Ok(Some(latency));
             }
+            sleep(APPLY_POLL_INTERVAL).await;
         }
 
-        Ok(backups)
+        if self.verbose {
+            eprintln!(
+                "⚠️ Light {light_id} didn't report the scene state within {}s",
+                APPLY_TIMEOUT.as_secs()
+            );
+        }
+        Ok(None)
+    }
+
+    /// Check whether the lights currently show a scene
+    pub async fn is_scene_showing(&self, scene_id: &str) -> Result<bool> {
+        let (scene, lights) = self.fetch_scene_snapshot(scene_id).await?;
+        Ok(scene_is_showing(&scene, &lights))
     }
 
     /// Restore previous light states
@@ -428,6 +562,11 @@ impl SceneExecutor {
                     scene_name: "Unknown".to_string(), // Would need to fetch scene name
                     execution_time_ms: execution_time,
                     success: true,
+                    state_change: None,
+                    apply_latency_ms: None,
+                    already_showing: false,
+                    warnings: Vec::new(),
+                    retry_count: 0,
                 })
             }
             Err(e) => {
@@ -466,13 +605,18 @@ impl SceneExecutor {
         // Check if scene exists
         let scene = match self.client.get_scene(scene_id).await {
             Ok(scene) => scene,
-            Err(_) => {
-                issues.push("Scene not found".to_string());
-                is_valid = false;
+            Err(e) => {
+                let inconclusive = !is_scene_absent_error(&e);
+                if inconclusive {
+                    issues.push(format!("Could not check scene: {e}"));
+                } else {
+                    issues.push("Scene not found".to_string());
+                }
                 return Ok(SceneValidationResult {
                     scene_id: scene_id.to_string(),
                     scene_name: "Unknown".to_string(),
-                    is_valid,
+                    is_valid: false,
+                    inconclusive,
                     issues,
                     lights_status: Vec::new(),
                 });
@@ -523,6 +667,7 @@ impl SceneExecutor {
             scene_id: scene_id.to_string(),
             scene_name: scene.name,
             is_valid,
+            inconclusive: false,
             issues,
             lights_status,
         })
@@ -557,6 +702,7 @@ impl Default for ExecutionOptions {
             retry_delay_ms: 1000,
             measure_performance: true,
             restore_previous_state: false,
+            verify_state_change: false,
         }
     }
 }
@@ -572,6 +718,7 @@ impl ExecutionOptions {
             retry_delay_ms: 500,
             measure_performance: false,
             restore_previous_state: false,
+            verify_state_change: false,
         }
     }
 
@@ -585,6 +732,7 @@ impl ExecutionOptions {
             retry_delay_ms: 2000,
             measure_performance: true,
             restore_previous_state: true,
+            verify_state_change: true,
         }
     }
 
@@ -598,6 +746,7 @@ impl ExecutionOptions {
             retry_delay_ms: 0,
             measure_performance: true,
             restore_previous_state: true,
+            verify_state_change: true,
         }
     }
 }
@@ -648,9 +797,166 @@ impl ExecutionMetrics {
     }
 }
 
+impl LightChangeSummary {
+    /// Check if at least one light visibly changed
+    pub fn has_visible_change(&self) -> bool {
+        self.lights_checked == 0 || self.lights_changed > 0
+    }
+
+    /// Get change summary
+    pub fn summary(&self) -> String {
+        if self.unchanged_lights.is_empty() {
+            format!(
+                "{}/{} lights changed",
+                self.lights_changed, self.lights_checked
+            )
+        } else {
+            format!(
+                "{}/{} lights changed (unchanged: {})",
+                self.lights_changed,
+                self.lights_checked,
+                self.unchanged_lights.join(", ")
+            )
+        }
+    }
+}
+
+/// Compare light states captured before and after a recall
+pub fn diff_light_states(
+    before: &[LightStateBackup],
+    after: &[LightStateBackup],
+) -> LightChangeSummary {
+    let mut summary = LightChangeSummary::default();
+
+    for previous in before {
+        let Some(current) = after.iter().find(|b| b.light_id == previous.light_id) else {
+            continue;
+        };
+
+        summary.lights_checked += 1;
+        if light_state_changed(&previous.previous_state, &current.previous_state) {
+            summary.lights_changed += 1;
+        } else {
+            summary.unchanged_lights.push(previous.light_name.clone());
+        }
+    }
+
+    summary
+}
+
+/// Check if any visible attribute differs between two light states
+fn light_state_changed(before: &LightState, after: &LightState) -> bool {
+    before.on != after.on
+        || before.bri != after.bri
+        || before.hue != after.hue
+        || before.sat != after.sat
+        || before.ct != after.ct
+        || before.xy != after.xy
+}
+
+/// Check if a light reports the state a scene stored for it
+///
+/// Only attributes the scene sets are compared, with tolerances for the
+/// rounding the bridge applies to reported values.
+fn light_state_matches(expected: &LightState, actual: &LightState) -> bool {
+    fn close<T: Into<f64>>(expected: Option<T>, actual: Option<T>, tolerance: f64) -> bool {
+        match (expected, actual) {
+            (None, _) => true,
+            (Some(expected), Some(actual)) => (expected.into() - actual.into()).abs() <= tolerance,
+            (Some(_), None) => false,
+        }
+    }
+
+    if expected.on != actual.on {
+        return false;
+    }
+    if !expected.on {
+        return true;
+    }
+
+    let xy_matches = match (expected.xy, actual.xy) {
+        (None, _) => true,
+        (Some([ex, ey]), Some([ax, ay])) => (ex - ax).abs() <= 0.01 && (ey - ay).abs() <= 0.01,
+        (Some(_), None) => false,
+    };
+
+    close(expected.bri, actual.bri, 2.0)
+        && xy_matches
+        && (expected.xy.is_some() || close(expected.hue, actual.hue, 300.0))
+        && (expected.xy.is_some() || close(expected.sat, actual.sat, 3.0))
+        && close(expected.ct, actual.ct, 5.0)
+}
+
+/// Check that every reachable light of a scene is in its scene state
+///
+/// Unreachable lights are ignored; a scene without a reachable light (or
+/// without stored light states) is never considered showing.
+fn scene_is_showing(scene: &Scene, lights: &HashMap<String, Light>) -> bool {
+    let mut reachable = scene
+        .lightstates
+        .iter()
+        .flatten()
+        .filter_map(|(id, expected)| {
+            lights
+                .get(id)
+                .filter(|light| light.is_reachable())
+                .map(|light| (expected, &light.state))
+        })
+        .peekable();
+
+    reachable.peek().is_some()
+        && reachable.all(|(expected, actual)| light_state_matches(expected, actual))
+}
+
+/// Check that a scene can be executed with the given light states
+fn check_scene_execution(scene: &Scene, lights: &HashMap<String, Light>) -> Result<()> {
+    ensure_scene_has_lights(scene)?;
+
+    // Check if scene is suitable for execution
+    if !scene.is_suitable_for_status() {
+        return Err(HueStatusError::ValidationFailed {
+            reason: format!("Scene '{}' is not suitable for execution", scene.name),
+        });
+    }
+
+    // Check if lights are reachable
+    let unreachable_lights: Vec<&str> = scene
+        .lights
+        .iter()
+        .filter_map(|light_id| lights.get(light_id))
+        .filter(|light| !light.is_reachable())
+        .map(|light| light.name.as_str())
+        .collect();
+
+    if !unreachable_lights.is_empty() {
+        return Err(HueStatusError::ValidationFailed {
+            reason: format!("Unreachable lights: {}", unreachable_lights.join(", ")),
+        });
+    }
+
+    Ok(())
+}
+
+/// Capture the current state of the lights in a scene
+fn light_backups(scene: &Scene, lights: &HashMap<String, Light>) -> Vec<LightStateBackup> {
+    scene
+        .lights
+        .iter()
+        .filter_map(|light_id| {
+            lights.get(light_id).map(|light| LightStateBackup {
+                light_id: light_id.clone(),
+                light_name: light.name.clone(),
+                previous_state: light.state.clone(),
+                timestamp: Instant::now(),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scenes::test_scene;
 
     #[test]
     fn test_execution_options() {
@@ -708,7 +1014,7 @@ mod tests {
         let backup = LightStateBackup {
             light_id: "1".to_string(),
             light_name: "Test Light".to_string(),
-            previous_state: crate::bridge::LightState {
+            previous_state: LightState {
                 on: true,
                 bri: Some(200),
                 hue: Some(1000),
@@ -743,4 +1049,138 @@ mod tests {
         assert_eq!(context.strategy, ExecutionStrategy::Immediate);
         assert_eq!(context.options.timeout_ms, 2000);
     }
+
+    fn backup(light_id: &str, on: bool, hue: u16) -> LightStateBackup {
+        let mut previous_state = LightState::new_custom_state(hue, 254, 254);
+        previous_state.on = on;
+
+        LightStateBackup {
+            light_id: light_id.to_string(),
+            light_name: format!("Light {light_id}"),
+            previous_state,
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_check_scene_execution_rejects_empty_scene() {
+        let scene = test_scene("huestatus-success", &[], false);
+
+        assert!(!scene.is_suitable_for_status());
+        assert!(matches!(
+            check_scene_execution(&scene, &HashMap::new()),
+            Err(HueStatusError::EmptyScene { scene_name }) if scene_name == "huestatus-success"
+        ));
+    }
+
+    #[test]
+    fn test_scene_is_showing() {
+        let light = |hue: u16, reachable: bool| {
+            let mut state = LightState::new_custom_state(hue, 254, 254);
+            state.reachable = Some(reachable);
+            Light {
+                name: "Desk".to_string(),
+                state,
+                light_type: "Extended color light".to_string(),
+                modelid: String::new(),
+                manufacturername: String::new(),
+                productname: None,
+                capabilities: None,
+                config: None,
+                swversion: None,
+                swconfigid: None,
+                productid: None,
+            }
+        };
+        let green = LightState::new_custom_state(25500, 254, 254);
+        let scene = Scene {
+            lightstates: Some(HashMap::from([
+                ("1".to_string(), green.clone()),
+                ("2".to_string(), green),
+            ])),
+            ..test_scene("huestatus-success", &["1", "2"], false)
+        };
+
+        let mut lights = HashMap::from([
+            ("1".to_string(), light(25500, true)),
+            ("2".to_string(), light(0, false)),
+        ]);
+        assert!(scene_is_showing(&scene, &lights));
+
+        lights.insert("2".to_string(), light(0, true));
+        assert!(!scene_is_showing(&scene, &lights));
+
+        lights.clear();
+        assert!(!scene_is_showing(&scene, &lights));
+    }
+
+    #[test]
+    fn test_light_state_matches() {
+        let expected = LightState {
+            on: true,
+            bri: Some(254),
+            hue: None,
+            sat: None,
+            effect: None,
+            xy: Some([0.6915, 0.3083]),
+            ct: None,
+            alert: None,
+            colormode: None,
+            mode: None,
+            reachable: None,
+        };
+        let mut actual = expected.clone();
+        actual.bri = Some(253);
+        actual.xy = Some([0.6914, 0.3077]);
+        actual.ct = Some(153);
+        assert!(light_state_matches(&expected, &actual));
+
+        actual.xy = Some([0.4, 0.4]);
+        assert!(!light_state_matches(&expected, &actual));
+
+        let off = LightState {
+            on: false,
+            ..expected.clone()
+        };
+        assert!(!light_state_matches(&off, &expected));
+        assert!(light_state_matches(
+            &off,
+            &LightState {
+                bri: Some(1),
+                ..off.clone()
+            }
+        ));
+    }
+
+    #[test]
+    fn test_diff_light_states() {
+        let before = vec![
+            backup("1", true, 0),
+            backup("2", true, 0),
+            backup("3", false, 0),
+        ];
+        let after = vec![
+            backup("1", true, 21845),
+            backup("2", true, 0),
+            backup("3", true, 0),
+        ];
+
+        let summary = diff_light_states(&before, &after);
+        assert_eq!(summary.lights_checked, 3);
+        assert_eq!(summary.lights_changed, 2);
+        assert_eq!(summary.unchanged_lights, vec!["Light 2".to_string()]);
+        assert!(summary.has_visible_change());
+    }
+
+    #[test]
+    fn test_diff_light_states_no_visible_change() {
+        let before = vec![backup("1", true, 0)];
+        let after = vec![backup("1", true, 0), backup("9", true, 0)];
+
+        let summary = diff_light_states(&before, &after);
+        assert_eq!(summary.lights_checked, 1);
+        assert_eq!(summary.lights_changed, 0);
+        assert!(!summary.has_visible_change());
+        assert!(summary.summary().contains("unchanged: Light 1"));
+    }
 }
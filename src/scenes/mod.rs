@@ -1,6 +1,10 @@
-use crate::bridge::{BridgeClient, CreateSceneRequest, Light, LightState, Scene};
-use crate::config::Config;
+use crate::bridge::{
+    action_errors, ActionResponse, BridgeClient, CreateSceneRequest, Light, LightState,
+    LightStateUpdate, Scene, UpdateSceneRequest,
+};
+use crate::config::{ColorConfig, ColorsConfig, Config};
 use crate::error::{HueStatusError, Result};
+use std::collections::HashMap;
 
 pub mod create;
 pub mod execute;
@@ -8,11 +12,19 @@ pub mod execute;
 pub use create::*;
 pub use execute::*;
 
+/// Lights per status scene when the configuration doesn't set a limit
+pub const DEFAULT_MAX_LIGHTS_PER_SCENE: usize = 50;
+
 /// Scene manager for creating and executing status scenes
 #[derive(Debug, Clone)]
 pub struct SceneManager {
     client: BridgeClient,
     verbose: bool,
+    verify_changes: bool,
+    measure_apply: bool,
+    only_on_change: bool,
+    overwrite_changes: bool,
+    strict_scene_names: bool,
 }
 
 /// Scene creation result
@@ -20,8 +32,42 @@ pub struct SceneManager {
 pub struct SceneCreationResult {
     pub success_scene_id: String,
     pub failure_scene_id: String,
+    pub running_scene_id: String,
     pub lights_used: Vec<String>,
     pub scenes_created: usize,
+    /// Existing huestatus scenes that were updated in place
+    pub scenes_updated: usize,
+    /// Scenes left alone because they were changed outside huestatus
+    pub scenes_skipped: usize,
+    /// Adjustments made while building the scenes (e.g. raised brightness)
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of deleting the status scenes
+#[derive(Debug, Clone, Default)]
+pub struct SceneDeletionResult {
+    /// IDs of the scenes that were deleted (or were already gone)
+    pub deleted: Vec<String>,
+    /// Scenes that could not be deleted, with the reason, for manual cleanup
+    pub failed: Vec<(String, String)>,
+}
+
+impl SceneDeletionResult {
+    /// Check if every scene was removed
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Result of importing a status scene
+#[derive(Debug, Clone)]
+pub struct SceneImportResult {
+    /// ID of the imported scene
+    pub scene_id: String,
+    /// Previous status scene that was deleted from the bridge
+    pub replaced_scene_id: Option<String>,
+    /// Problems that didn't stop the import
+    pub warnings: Vec<String>,
 }
 
 /// Scene execution result
@@ -31,6 +77,16 @@ pub struct SceneExecutionResult {
     pub scene_name: String,
     pub execution_time_ms: u64,
     pub success: bool,
+    pub state_change: Option<LightChangeSummary>,
+    /// Time from the recall ACK until a light reported the scene state (`--measure-apply`)
+    pub apply_latency_ms: Option<u64>,
+    /// The lights already showed the scene, so it wasn't recalled (`--only-on-change`)
+    pub already_showing: bool,
+    /// Per-light failures reported by the bridge, and other problems that
+    /// didn't stop the status from showing
+    pub warnings: Vec<String>,
+    /// Number of retried requests while executing
+    pub retry_count: usize,
 }
 
 /// Scene validation result
@@ -39,6 +95,8 @@ pub struct SceneValidationResult {
     pub scene_id: String,
     pub scene_name: String,
     pub is_valid: bool,
+    /// The scene couldn't be checked (e.g. a timeout), so `is_valid` says nothing about it
+    pub inconclusive: bool,
     pub issues: Vec<String>,
     pub lights_status: Vec<LightStatus>,
 }
@@ -60,6 +118,17 @@ pub struct StatusColors {
     pub failure: ColorDefinition,
 }
 
+/// How the success and failure scenes' light sets overlap
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneLightOverlap {
+    /// Lights in both scenes
+    pub shared: Vec<String>,
+    /// Lights only in the success scene
+    pub success_only: Vec<String>,
+    /// Lights only in the failure scene
+    pub failure_only: Vec<String>,
+}
+
 /// Color definition with multiple formats
 #[derive(Debug, Clone)]
 pub struct ColorDefinition {
@@ -76,6 +145,11 @@ impl SceneManager {
         Self {
             client,
             verbose: false,
+            verify_changes: false,
+            measure_apply: false,
+            only_on_change: false,
+            overwrite_changes: false,
+            strict_scene_names: false,
         }
     }
 
@@ -85,19 +159,117 @@ impl SceneManager {
         self
     }
 
-    /// Create status scenes (success and failure)
+    /// Compare light states before and after each recall
+    pub fn with_change_verification(mut self, verify_changes: bool) -> Self {
+        self.verify_changes = verify_changes;
+        self
+    }
+
+    /// Skip the recall when the lights already show the status scene
+    pub fn with_only_on_change(mut self, only_on_change: bool) -> Self {
+        self.only_on_change = only_on_change;
+        self
+    }
+
+    /// Measure how long the lights take to apply each recalled scene
+    pub fn with_apply_measurement(mut self, measure_apply: bool) -> Self {
+        self.measure_apply = measure_apply;
+        self
+    }
+
+    /// Overwrite status scenes even if they were changed outside huestatus
+    pub fn with_overwrite_changes(mut self, overwrite_changes: bool) -> Self {
+        self.overwrite_changes = overwrite_changes;
+        self
+    }
+
+    /// Fail instead of warning when a status scene name is taken by a scene huestatus didn't create
+    pub fn with_strict_scene_names(mut self, strict_scene_names: bool) -> Self {
+        self.strict_scene_names = strict_scene_names;
+        self
+    }
+
+    /// Create status scenes (success, failure and running) in their configured colors
     pub async fn create_status_scenes(&self, config: &mut Config) -> Result<SceneCreationResult> {
         if self.verbose {
             eprintln!("🎨 Creating status scenes...");
         }
 
-        // Get suitable lights for status indication
-        let suitable_lights = self.client.get_suitable_lights().await?;
+        // Get suitable lights for status indication, limited to the recall group
+        let suitable_lights = self
+            .client
+            .get_suitable_lights_in_group(&config.settings.recall_group_id)
+            .await?;
 
         if suitable_lights.is_empty() {
             return Err(HueStatusError::NoLightsFound);
         }
 
+        // Existing huestatus scenes are updated in place so re-running setup
+        // doesn't leave stale copies behind on the bridge
+        let existing_scenes = self.client.get_scenes().await?;
+        let mut warnings = Vec::new();
+
+        // Never update a hand-made scene that happens to use a status scene name
+        let collisions = foreign_scene_name_collisions(&existing_scenes, config);
+        if !collisions.is_empty() {
+            let names: Vec<String> = collisions
+                .iter()
+                .map(|id| format!("'{}' ({id})", existing_scenes[id].name))
+                .collect();
+            if self.strict_scene_names {
+                return Err(HueStatusError::ValidationFailed {
+                    reason: format!(
+                        "Scene name(s) already used by scenes huestatus didn't create: {}",
+                        names.join(", ")
+                    ),
+                });
+            }
+            warnings.push(format!(
+                "Scene name(s) already used by scenes huestatus didn't create: {}; left them untouched and created separate status scenes",
+                names.join(", ")
+            ));
+        }
+        let reusable_scenes: HashMap<String, Scene> = existing_scenes
+            .iter()
+            .filter(|(id, _)| !collisions.contains(id))
+            .map(|(id, scene)| (id.clone(), scene.clone()))
+            .collect();
+
+        // Stay within what the bridge can store; rewritten scenes free their light states
+        let free_lightstates = self
+            .client
+            .get_capabilities()
+            .await
+            .ok()
+            .and_then(|capabilities| capabilities.scenes.lightstates)
+            .map(|lightstates| {
+                let reused: usize = ["success", "failure", "running"]
+                    .iter()
+                    .filter_map(|scene_type| config.get_scene(scene_type))
+                    .filter_map(|scene| existing_scenes.get(&scene.id))
+                    .map(Scene::light_count)
+                    .sum();
+                lightstates.available + reused
+            });
+        let max_lights = scene_light_limit(
+            config.advanced.max_lights_per_scene,
+            free_lightstates,
+            STATUS_SCENE_COUNT,
+        );
+        if max_lights == 0 {
+            return Err(HueStatusError::InvalidSceneData {
+                reason: "The bridge has no free light states left for new scenes; delete unused scenes first".to_string(),
+            });
+        }
+        let (suitable_lights, skipped) = select_scene_lights(suitable_lights, max_lights);
+        if skipped > 0 {
+            warnings.push(format!(
+                "Using {max_lights} of {} lights (the per-scene limit); {skipped} left out",
+                max_lights + skipped
+            ));
+        }
+
         let light_ids: Vec<String> = suitable_lights.iter().map(|(id, _)| id.clone()).collect();
 
         if self.verbose {
@@ -107,34 +279,242 @@ impl SceneManager {
             }
         }
 
-        // Create success scene (green)
-        let success_scene_name = "huestatus-success".to_string();
-        let success_scene_request =
-            CreateSceneRequest::new_success_scene(success_scene_name.clone(), light_ids.clone());
+        let min_brightness = config.settings.min_brightness;
+        let mut scenes_created = 0;
+        let mut scenes_updated = 0;
+        let mut scenes_skipped = 0;
+        let mut written = Vec::new();
+        let colors = config.colors.clone();
+        let status_scenes = [
+            ("success", STATUS_SCENE_NAMES[0], colors.success),
+            ("failure", STATUS_SCENE_NAMES[1], colors.failure),
+            ("running", STATUS_SCENE_NAMES[2], colors.running),
+        ];
+
+        for (scene_type, scene_name, color) in status_scenes {
+            let mut request = self.status_scene_request(
+                scene_type,
+                scene_name,
+                &suitable_lights,
+                &colors,
+                color,
+                max_lights,
+            )?;
+            warnings.extend(request.apply_brightness_floor(min_brightness));
+
+            let configured_id = config
+                .get_scene(scene_type)
+                .map(|scene| scene.id.clone())
+                .unwrap_or_default();
 
-        let success_response = self.client.create_scene(&success_scene_request).await?;
-        let success_scene_id = success_response
-            .first()
-            .ok_or_else(|| HueStatusError::SceneExecutionFailed {
-                reason: "No response from scene creation".to_string(),
-            })?
-            .success
-            .id
-            .clone();
+            // Imported scenes aren't huestatus's to regenerate
+            let imported = config
+                .get_scene(scene_type)
+                .is_some_and(|scene| scene.imported && existing_scenes.contains_key(&scene.id));
+            if imported && !self.overwrite_changes {
+                warnings.push(format!(
+                    "The {scene_type} scene was imported; kept it, use --force to replace it"
+                ));
+                scenes_skipped += 1;
+                continue;
+            }
+
+            // Don't silently overwrite edits made in the Hue app
+            let recorded = config
+                .get_scene(scene_type)
+                .and_then(|scene| scene.last_updated.as_deref());
+            if let Some(changed) = changed_externally(&existing_scenes, &configured_id, recorded) {
+                if !self.overwrite_changes {
+                    warnings.push(format!(
+                        "Scene '{scene_name}' was changed outside huestatus (at {changed}); kept it, use --force to overwrite"
+                    ));
+                    scenes_skipped += 1;
+                    continue;
+                }
+            }
+
+            let (scene_id, created) = self
+                .upsert_status_scene(&reusable_scenes, &configured_id, request)
+                .await?;
+            if created {
+                scenes_created += 1;
+            } else {
+                scenes_updated += 1;
+            }
+            written.push(scene_type);
+
+            // Update configuration with the scene ID, adding the running scene if missing
+            match config.get_scene_mut(scene_type) {
+                Some(scene) => {
+                    scene.id = scene_id;
+                    scene.name = scene_name.to_string();
+                    scene.auto_created = true;
+                    scene.imported = false;
+                }
+                None => {
+                    config.scenes.running = Some(Config::create_scene_config(
+                        scene_id,
+                        scene_name.to_string(),
+                        true,
+                    ));
+                }
+            }
+        }
+
+        self.record_last_updated(config, &written).await;
+
+        let result = SceneCreationResult {
+            success_scene_id: config.scenes.success.id.clone(),
+            failure_scene_id: config.scenes.failure.id.clone(),
+            running_scene_id: config
+                .get_scene("running")
+                .map(|scene| scene.id.clone())
+                .unwrap_or_default(),
+            lights_used: light_ids,
+            scenes_created,
+            scenes_updated,
+            scenes_skipped,
+            warnings,
+        };
 
         if self.verbose {
-            eprintln!(
-                "✅ Created success scene: {success_scene_name} ({success_scene_id})"
-            );
+            eprintln!("🎉 Scene creation completed successfully!");
+        }
+
+        Ok(result)
+    }
+
+    /// Build a status scene in its solid color, gradient or effect
+    ///
+    /// Gradients fall back to the solid color when there is only one light, and
+    /// lights that can't show the success effect get `none`.
+    fn status_scene_request(
+        &self,
+        scene_type: &str,
+        scene_name: &str,
+        lights: &[(String, Light)],
+        colors: &ColorsConfig,
+        color: ColorConfig,
+        max_lights: usize,
+    ) -> Result<CreateSceneRequest> {
+        let light_ids: Vec<String> = lights.iter().map(|(id, _)| id.clone()).collect();
+        let configured_gradient = colors.gradient(scene_type);
+        let gradient = configured_gradient.filter(|_| light_ids.len() > 1);
+        if configured_gradient.is_some() && gradient.is_none() && self.verbose {
+            eprintln!("💡 Only one light, using a solid color for {scene_name}");
+        }
+        let breathing = colors.failure_effect.filter(|_| scene_type == "failure");
+        let effect = colors
+            .success_effect
+            .as_deref()
+            .filter(|_| scene_type == "success");
+
+        if gradient.is_none() && breathing.is_none() && effect.is_none() {
+            return Ok(CreateSceneRequest::new_custom_scene(
+                scene_name.to_string(),
+                light_ids,
+                color.hue,
+                color.saturation,
+                color.brightness,
+            ));
+        }
+
+        let to_definition = |stop: &ColorConfig| {
+            ColorDefinition::new(
+                scene_name.to_string(),
+                stop.hue,
+                stop.saturation,
+                stop.brightness,
+            )
+        };
+        let mut builder = SceneBuilder::new(scene_name.to_string())
+            .with_max_lights(max_lights)
+            .with_lights(light_ids)
+            .with_color(to_definition(&color));
+        if let Some(gradient) = gradient {
+            builder = builder.with_gradient(gradient.iter().map(to_definition).collect());
+        }
+        if let Some(breathing) = breathing {
+            builder = builder
+                .with_alternating_brightness(breathing.min_brightness, breathing.max_brightness);
+        }
+        if let Some(effect) = effect {
+            if self.verbose {
+                for (id, light) in lights
+                    .iter()
+                    .filter(|(_, light)| !light.supports_effect(effect))
+                {
+                    eprintln!(
+                        "💡 {} ({id}) doesn't support {effect}, using none",
+                        light.name
+                    );
+                }
+            }
+            builder = builder.with_effect(effect, lights);
         }
+        builder.build_validated()
+    }
 
-        // Create failure scene (red)
-        let failure_scene_name = "huestatus-failure".to_string();
-        let failure_scene_request =
-            CreateSceneRequest::new_failure_scene(failure_scene_name.clone(), light_ids.clone());
+    /// Store the bridge's `lastupdated` for the scenes just written
+    async fn record_last_updated(&self, config: &mut Config, scene_types: &[&str]) {
+        let scenes = match self.client.get_scenes().await {
+            Ok(scenes) => scenes,
+            Err(e) => {
+                if self.verbose {
+                    eprintln!("⚠️ Could not read back scene versions: {e}");
+                }
+                return;
+            }
+        };
 
-        let failure_response = self.client.create_scene(&failure_scene_request).await?;
-        let failure_scene_id = failure_response
+        for scene_type in scene_types {
+            if let Some(scene_config) = config.get_scene_mut(scene_type) {
+                scene_config.last_updated = scenes
+                    .get(&scene_config.id)
+                    .map(|scene| scene.lastupdated.clone());
+            }
+        }
+    }
+
+    /// Update the huestatus scene with the request's name, or create it if there is none
+    ///
+    /// Returns the scene ID and whether a new scene was created.
+    async fn upsert_status_scene(
+        &self,
+        existing_scenes: &HashMap<String, Scene>,
+        configured_id: &str,
+        request: CreateSceneRequest,
+    ) -> Result<(String, bool)> {
+        if let Some(scene_id) = find_reusable_scene(
+            existing_scenes,
+            &request.name,
+            configured_id,
+            &request.lights,
+        ) {
+            let scene_id = scene_id.to_string();
+            let name = request.name.clone();
+            let response = self
+                .client
+                .update_scene(&scene_id, &UpdateSceneRequest::from(request))
+                .await?;
+
+            if response.iter().any(ActionResponse::is_success) {
+                if self.verbose {
+                    eprintln!("♻️ Updated existing scene: {name} ({scene_id})");
+                }
+                return Ok((scene_id, false));
+            }
+
+            return Err(HueStatusError::SceneExecutionFailed {
+                reason: format!(
+                    "Failed to update scene '{name}' ({scene_id}): {}",
+                    action_errors(&response).join(", ")
+                ),
+            });
+        }
+
+        let response = self.client.create_scene(&request).await?;
+        let scene_id = response
             .first()
             .ok_or_else(|| HueStatusError::SceneExecutionFailed {
                 reason: "No response from scene creation".to_string(),
@@ -144,31 +524,10 @@ impl SceneManager {
             .clone();
 
         if self.verbose {
-            eprintln!(
-                "✅ Created failure scene: {failure_scene_name} ({failure_scene_id})"
-            );
-        }
-
-        // Update configuration with new scene IDs
-        config.scenes.success.id = success_scene_id.clone();
-        config.scenes.success.name = success_scene_name;
-        config.scenes.success.auto_created = true;
-        config.scenes.failure.id = failure_scene_id.clone();
-        config.scenes.failure.name = failure_scene_name;
-        config.scenes.failure.auto_created = true;
-
-        let result = SceneCreationResult {
-            success_scene_id,
-            failure_scene_id,
-            lights_used: light_ids,
-            scenes_created: 2,
-        };
-
-        if self.verbose {
-            eprintln!("🎉 Scene creation completed successfully!");
+            eprintln!("✅ Created scene: {} ({scene_id})", request.name);
         }
 
-        Ok(result)
+        Ok((scene_id, true))
     }
 
     /// Execute a status scene
@@ -191,15 +550,67 @@ impl SceneManager {
             );
         }
 
-        let start_time = std::time::Instant::now();
+        let executor = SceneExecutor::new(self.client.clone()).with_config(
+            1,
+            std::time::Duration::ZERO,
+            self.verbose,
+        );
+        let mut scene_id = scene_config.id.clone();
+        if self.verify_changes || config.settings.validate_scenes_on_startup {
+            scene_id = self
+                .resolve_scene_id(&scene_config.id, &scene_config.name)
+                .await?;
+        }
+        // A failed check (e.g. a stale scene ID) falls through to the normal recall
+        if self.only_on_change && matches!(executor.is_scene_showing(&scene_id).await, Ok(true)) {
+            if self.verbose {
+                eprintln!("⏭️ already showing {scene_type}");
+            }
+            return Ok(SceneExecutionResult {
+                scene_id,
+                scene_name: scene_config.name.clone(),
+                execution_time_ms: 0,
+                success: true,
+                state_change: None,
+                apply_latency_ms: None,
+                already_showing: true,
+                warnings: Vec::new(),
+                retry_count: 0,
+            });
+        }
 
-        // Execute the scene
-        let response = self.client.execute_scene(&scene_config.id).await?;
+        let states_before = if self.verify_changes {
+            Some(executor.backup_current_states(&scene_id).await?)
+        } else {
+            None
+        };
+
+        let start_time = std::time::Instant::now();
+        let retries_before = self.client.retry_count();
+
+        // Execute the scene, falling back to a name lookup if the stored ID is stale
+        let group_id = &config.settings.recall_group_id;
+        let response = match self
+            .client
+            .execute_scene_on_group(group_id, &scene_id)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if !self.verify_changes && is_scene_absent_error(&e) => {
+                scene_id = self.resolve_scene_id(&scene_id, &scene_config.name).await?;
+                self.client
+                    .execute_scene_on_group(group_id, &scene_id)
+                    .await?
+            }
+            Err(e) => return Err(e),
+        };
 
         let execution_time = start_time.elapsed().as_millis() as u64;
+        let retry_count = self.client.retry_count() - retries_before;
 
-        // Check if execution was successful
-        let success = !response.is_empty();
+        // Check if execution was successful; lights can fail individually
+        let success = response.iter().any(ActionResponse::is_success);
+        let mut warnings = action_errors(&response);
 
         if self.verbose {
             if success {
@@ -207,38 +618,109 @@ impl SceneManager {
             } else {
                 eprintln!("❌ Scene execution failed");
             }
+            if retry_count > 0 {
+                eprintln!("⏳ Needed {retry_count} retries");
+            }
         }
 
+        // The scene is showing; a failed measurement mustn't fail the command
+        let apply_latency_ms = if self.measure_apply && success {
+            match executor.measure_apply_latency(&scene_id).await {
+                Ok(latency) => latency.map(|latency| latency.as_millis() as u64),
+                Err(e) => {
+                    warnings.push(format!("Could not measure the apply latency: {e}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Same for the optional check of what changed
+        let state_change = match states_before {
+            Some(before) if success => {
+                match executor.capture_state_change(&scene_id, &before).await {
+                    Ok(summary) => Some(summary),
+                    Err(e) => {
+                        warnings.push(format!("Could not verify light changes: {e}"));
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         Ok(SceneExecutionResult {
-            scene_id: scene_config.id.clone(),
+            scene_id,
             scene_name: scene_config.name.clone(),
             execution_time_ms: execution_time,
             success,
+            state_change,
+            apply_latency_ms,
+            already_showing: false,
+            warnings,
+            retry_count,
         })
     }
 
+    /// Resolve a configured scene, looking it up by name if the stored ID no longer exists
+    ///
+    /// Scenes that no longer contain any lights are rejected with `EmptyScene`.
+    pub async fn resolve_scene_id(&self, scene_id: &str, scene_name: &str) -> Result<String> {
+        match self.client.get_scene(scene_id).await {
+            Ok(scene) => {
+                ensure_scene_has_lights(&scene)?;
+                Ok(scene_id.to_string())
+            }
+            Err(e) if is_scene_absent_error(&e) => {
+                let lookup = self.client.get_scene_by_name(scene_name).await;
+                let (resolved_id, scene) = lookup.map_err(|lookup_error| match lookup_error {
+                    HueStatusError::SceneNotFound { .. } => e,
+                    other => other,
+                })?;
+                ensure_scene_has_lights(&scene)?;
+
+                if self.verbose {
+                    eprintln!(
+                        "🔍 Scene ID {scene_id} is stale, using '{scene_name}' ({resolved_id})"
+                    );
+                }
+
+                Ok(resolved_id)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Validate status scenes
     pub async fn validate_status_scenes(
         &self,
         config: &Config,
     ) -> Result<Vec<SceneValidationResult>> {
-        let mut results = Vec::new();
-
-        // Validate success scene
-        if let Some(success_scene) = config.get_scene("success") {
-            let result = self
-                .validate_scene(&success_scene.id, &success_scene.name)
-                .await?;
-            results.push(result);
-        }
+        // Fetch lights once and share them between the scene checks
+        let all_lights = &self.client.get_lights().await?;
+
+        // The running scene is absent in configs from before it existed
+        let validate = |scene_type: &'static str| async move {
+            match config.get_scene(scene_type) {
+                Some(scene) => self
+                    .validate_scene(&scene.id, &scene.name, all_lights)
+                    .await
+                    .map(Some),
+                None => Ok(None),
+            }
+        };
 
-        // Validate failure scene
-        if let Some(failure_scene) = config.get_scene("failure") {
-            let result = self
-                .validate_scene(&failure_scene.id, &failure_scene.name)
-                .await?;
-            results.push(result);
-        }
+        // Check the scenes concurrently, keeping results in success/failure/running order
+        let (success, failure, running) = tokio::join!(
+            validate("success"),
+            validate("failure"),
+            validate("running")
+        );
+        let results = [success?, failure?, running?]
+            .into_iter()
+            .flatten()
+            .collect();
 
         Ok(results)
     }
@@ -248,6 +730,7 @@ impl SceneManager {
         &self,
         scene_id: &str,
         scene_name: &str,
+        all_lights: &HashMap<String, Light>,
     ) -> Result<SceneValidationResult> {
         if self.verbose {
             eprintln!("🔍 Validating scene: {scene_name} ({scene_id})");
@@ -257,16 +740,21 @@ impl SceneManager {
         let mut lights_status = Vec::new();
         let mut is_valid = true;
 
-        // Check if scene exists
+        // Check if scene exists; only a definite "not available" answer means it's gone
         let scene = match self.client.get_scene(scene_id).await {
             Ok(scene) => scene,
-            Err(_) => {
-                issues.push(format!("Scene '{scene_name}' not found"));
-                is_valid = false;
+            Err(e) => {
+                let inconclusive = !is_scene_absent_error(&e);
+                if inconclusive {
+                    issues.push(format!("Could not check scene '{scene_name}': {e}"));
+                } else {
+                    issues.push(format!("Scene '{scene_name}' not found"));
+                }
                 return Ok(SceneValidationResult {
                     scene_id: scene_id.to_string(),
                     scene_name: scene_name.to_string(),
-                    is_valid,
+                    is_valid: false,
+                    inconclusive,
                     issues,
                     lights_status,
                 });
@@ -274,14 +762,17 @@ impl SceneManager {
         };
 
         // Check if scene is suitable for status indication
-        if !scene.is_suitable_for_status() {
+        if scene.lights.is_empty() {
+            issues.push(
+                "Scene has no lights; run 'huestatus setup --force' to refresh it".to_string(),
+            );
+            is_valid = false;
+        } else if !scene.is_suitable_for_status() {
             issues.push("Scene is not suitable for status indication".to_string());
             is_valid = false;
         }
 
         // Validate lights in scene
-        let all_lights = self.client.get_lights().await?;
-
         for light_id in &scene.lights {
             if let Some(light) = all_lights.get(light_id) {
                 let light_status = LightStatus {
@@ -323,6 +814,7 @@ impl SceneManager {
             scene_id: scene_id.to_string(),
             scene_name: scene_name.to_string(),
             is_valid,
+            inconclusive: false,
             issues,
             lights_status,
         })
@@ -356,61 +848,98 @@ impl SceneManager {
         self.client.get_scene(scene_id).await
     }
 
-    /// Delete status scenes
-    pub async fn delete_status_scenes(&self, config: &Config) -> Result<()> {
+    /// Delete the status scenes huestatus created, reporting each outcome
+    ///
+    /// Scenes already gone from the bridge count as deleted; scenes the user
+    /// chose themselves (not auto-created) are left alone.
+    pub async fn delete_status_scenes(&self, config: &Config) -> Result<SceneDeletionResult> {
         if self.verbose {
             eprintln!("🗑️ Deleting status scenes...");
         }
 
-        // Delete success scene
-        if let Some(success_scene) = config.get_scene("success") {
-            if success_scene.auto_created {
-                match self.client.delete_scene(&success_scene.id).await {
-                    Ok(_) => {
-                        if self.verbose {
-                            eprintln!("✅ Deleted success scene: {}", success_scene.name);
-                        }
+        let mut result = SceneDeletionResult::default();
+        for scene_type in ["success", "failure", "running"] {
+            let Some(scene) = config.get_scene(scene_type) else {
+                continue;
+            };
+            if !scene.auto_created {
+                continue;
+            }
+
+            match self.client.delete_scene(&scene.id).await {
+                Ok(_) => {
+                    if self.verbose {
+                        eprintln!("✅ Deleted {scene_type} scene: {}", scene.name);
                     }
-                    Err(e) => {
-                        if self.verbose {
-                            eprintln!("⚠️ Failed to delete success scene: {e}");
-                        }
+                    result.deleted.push(scene.id.clone());
+                }
+                Err(e) if is_scene_absent_error(&e) => {
+                    result.deleted.push(scene.id.clone());
+                }
+                Err(e) => {
+                    if self.verbose {
+                        eprintln!("⚠️ Failed to delete {scene_type} scene: {e}");
                     }
+                    result.failed.push((scene.id.clone(), e.to_string()));
                 }
             }
         }
 
-        // Delete failure scene
-        if let Some(failure_scene) = config.get_scene("failure") {
-            if failure_scene.auto_created {
-                match self.client.delete_scene(&failure_scene.id).await {
-                    Ok(_) => {
-                        if self.verbose {
-                            eprintln!("✅ Deleted failure scene: {}", failure_scene.name);
-                        }
+        Ok(result)
+    }
+
+    /// Find huestatus scenes on the bridge that no configuration references
+    ///
+    /// These are typically left behind by a setup that crashed before saving.
+    /// `in_use` holds the scene IDs of other configurations sharing the bridge
+    /// (see [`Config::scene_ids_in_use`]).
+    pub async fn find_orphaned_huestatus_scenes(
+        &self,
+        config: &Config,
+        in_use: &[String],
+    ) -> Result<Vec<(String, Scene)>> {
+        let scenes = self.client.get_scenes().await?;
+        Ok(find_orphaned_scenes(scenes, config, in_use))
+    }
+
+    /// Delete the given orphaned scenes, returning the names of those deleted
+    ///
+    /// Pass the scenes the user confirmed (see `find_orphaned_huestatus_scenes`),
+    /// so nothing that appeared on the bridge since is deleted.
+    pub async fn delete_orphaned_huestatus_scenes(
+        &self,
+        orphans: &[(String, Scene)],
+    ) -> Vec<String> {
+        let mut deleted = Vec::new();
+
+        for (id, scene) in orphans {
+            match self.client.delete_scene(id).await {
+                Ok(_) => {
+                    if self.verbose {
+                        eprintln!("✅ Deleted orphaned scene: {} ({id})", scene.name);
                     }
-                    Err(e) => {
-                        if self.verbose {
-                            eprintln!("⚠️ Failed to delete failure scene: {e}");
-                        }
+                    deleted.push(scene.name.clone());
+                }
+                Err(e) => {
+                    if self.verbose {
+                        eprintln!("⚠️ Failed to delete orphaned scene {}: {e}", scene.name);
                     }
                 }
             }
         }
 
-        Ok(())
+        deleted
     }
 
-    /// Refresh status scenes (delete and recreate)
+    /// Refresh status scenes with the current suitable lights
+    ///
+    /// Scenes that still exist are updated in place so their IDs stay the same;
+    /// missing ones are recreated.
     pub async fn refresh_status_scenes(&self, config: &mut Config) -> Result<SceneCreationResult> {
         if self.verbose {
             eprintln!("🔄 Refreshing status scenes...");
         }
 
-        // Delete existing scenes
-        self.delete_status_scenes(config).await?;
-
-        // Create new scenes
         self.create_status_scenes(config).await
     }
 
@@ -470,6 +999,86 @@ impl SceneManager {
         Ok(scene_id)
     }
 
+    /// Create a scene from a user-supplied request and use it as a status scene
+    ///
+    /// The scene is recorded as imported, so `scenes refresh` keeps it unless
+    /// forced. The scene it replaces is deleted if huestatus created it and no
+    /// other status uses it.
+    pub async fn import_status_scene(
+        &self,
+        config: &mut Config,
+        scene_type: &str,
+        request: &CreateSceneRequest,
+    ) -> Result<SceneImportResult> {
+        request.validate()?;
+
+        let response = self.client.create_scene(request).await?;
+        let scene_id = response
+            .first()
+            .ok_or_else(|| HueStatusError::SceneExecutionFailed {
+                reason: "No response from scene creation".to_string(),
+            })?
+            .success
+            .id
+            .clone();
+
+        let replaced = config
+            .get_scene(scene_type)
+            .filter(|scene| scene.auto_created || scene.imported)
+            .map(|scene| scene.id.clone())
+            .filter(|id| !id.is_empty() && *id != scene_id);
+
+        let mut scene_config =
+            Config::create_scene_config(scene_id.clone(), request.name.clone(), false);
+        scene_config.imported = true;
+        match scene_type {
+            "success" => config.scenes.success = scene_config,
+            "failure" => config.scenes.failure = scene_config,
+            _ => config.scenes.running = Some(scene_config),
+        }
+
+        if self.verbose {
+            eprintln!(
+                "✅ Imported {scene_type} scene: {} ({scene_id})",
+                request.name
+            );
+        }
+
+        let mut result = SceneImportResult {
+            scene_id,
+            replaced_scene_id: None,
+            warnings: Vec::new(),
+        };
+
+        // Keep the old scene if another status still points at it
+        let still_used = |id: &str| {
+            ["success", "failure", "running"]
+                .iter()
+                .any(|other| config.get_scene(other).is_some_and(|scene| scene.id == id))
+        };
+        if let Some(old_id) = replaced.filter(|id| !still_used(id)) {
+            match self.client.delete_scene(&old_id).await {
+                Ok(_) => result.replaced_scene_id = Some(old_id),
+                Err(e) if is_scene_absent_error(&e) => {}
+                Err(e) => result.warnings.push(format!(
+                    "Could not delete the replaced {scene_type} scene {old_id}: {e}"
+                )),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Compare the light sets of the success and failure scenes on the bridge
+    pub async fn status_scene_light_overlap(&self, config: &Config) -> Result<SceneLightOverlap> {
+        let success = self.client.get_scene(&config.scenes.success.id).await?;
+        let failure = self.client.get_scene(&config.scenes.failure.id).await?;
+        Ok(SceneLightOverlap::from_light_sets(
+            &success.lights,
+            &failure.lights,
+        ))
+    }
+
     /// Get all available lights suitable for status scenes
     pub async fn get_available_lights(&self) -> Result<Vec<(String, Light)>> {
         self.client.get_suitable_lights().await
@@ -482,27 +1091,194 @@ impl SceneManager {
     }
 }
 
+/// Number of status scenes `create_status_scenes` writes
+const STATUS_SCENE_COUNT: usize = 3;
+
+/// Names of the success, failure and running scenes
+const STATUS_SCENE_NAMES: [&str; STATUS_SCENE_COUNT] = [
+    "huestatus-success",
+    "huestatus-failure",
+    "huestatus-running",
+];
+
+/// Most lights each status scene can hold
+///
+/// The configured limit applies, lowered so that all `scene_count` scenes fit
+/// in the bridge's free light states when it reports them.
+fn scene_light_limit(
+    configured: usize,
+    free_lightstates: Option<usize>,
+    scene_count: usize,
+) -> usize {
+    match free_lightstates {
+        Some(free) => configured.min(free / scene_count.max(1)),
+        None => configured,
+    }
+}
+
+/// Order the lights by ID and keep the most suitable ones when there are more than `max_lights`
+///
+/// The bridge's light map has no stable order, so lights are sorted by numeric
+/// ID first; that keeps the selection and gradient order the same between runs.
+/// Full-color lights are kept before color-temperature-only ones. Returns the
+/// kept lights and how many were left out.
+fn select_scene_lights(
+    mut lights: Vec<(String, Light)>,
+    max_lights: usize,
+) -> (Vec<(String, Light)>, usize) {
+    lights.sort_by(|(a, _), (b, _)| {
+        let numeric = |id: &str| id.parse::<u32>().unwrap_or(u32::MAX);
+        numeric(a).cmp(&numeric(b)).then_with(|| a.cmp(b))
+    });
+    if lights.len() <= max_lights {
+        return (lights, 0);
+    }
+    lights.sort_by_key(|(_, light)| !light.supports_color());
+    let skipped = lights.len() - max_lights;
+    lights.truncate(max_lights);
+    (lights, skipped)
+}
+
+/// Pick the existing, unlocked scene to update instead of creating a new one
+///
+/// The configured scene is used while it still exists, even if it was renamed;
+/// otherwise the lowest-ID scene named `name` that huestatus created (appdata
+/// marker) for exactly `lights`. Same-named scenes of other projects or tools
+/// are never taken over.
+fn find_reusable_scene<'a>(
+    scenes: &'a HashMap<String, Scene>,
+    name: &str,
+    configured_id: &str,
+    lights: &[String],
+) -> Option<&'a str> {
+    let same_lights = |scene: &Scene| {
+        let mut scene_lights: Vec<&String> = scene.lights.iter().collect();
+        let mut wanted: Vec<&String> = lights.iter().collect();
+        scene_lights.sort();
+        wanted.sort();
+        scene_lights == wanted
+    };
+
+    scenes
+        .iter()
+        .filter(|(id, scene)| {
+            let ours = *id == configured_id
+                || (scene.name.eq_ignore_ascii_case(name)
+                    && scene.has_huestatus_appdata()
+                    && same_lights(scene));
+            ours && !scene.is_locked()
+        })
+        .map(|(id, _)| id.as_str())
+        .min_by_key(|id| {
+            (
+                *id != configured_id,
+                id.parse::<u32>().unwrap_or(u32::MAX),
+                *id,
+            )
+        })
+}
+
+/// Find scenes named like a status scene that huestatus neither created nor was configured with
+///
+/// Ownership is decided by the appdata marker alone, since a hand-made scene
+/// can carry the status scene name. Configured scenes are excluded because
+/// scenes from older versions may predate the marker. Returns IDs in order.
+fn foreign_scene_name_collisions(scenes: &HashMap<String, Scene>, config: &Config) -> Vec<String> {
+    let configured: Vec<&str> = ["success", "failure", "running"]
+        .into_iter()
+        .filter_map(|scene_type| config.get_scene(scene_type))
+        .map(|scene| scene.id.as_str())
+        .collect();
+
+    let mut collisions: Vec<String> = scenes
+        .iter()
+        .filter(|(id, scene)| {
+            STATUS_SCENE_NAMES
+                .iter()
+                .any(|name| scene.name.eq_ignore_ascii_case(name))
+                && !scene.has_huestatus_appdata()
+                && !configured.contains(&id.as_str())
+        })
+        .map(|(id, _)| id.clone())
+        .collect();
+    collisions.sort_by_key(|id| (id.parse::<u32>().unwrap_or(u32::MAX), id.clone()));
+    collisions
+}
+
+/// Select unlocked huestatus scenes referenced by neither `config` nor `in_use`, ordered by ID
+///
+/// Only the appdata marker counts: other tools' and hand-made scenes may use
+/// a `huestatus-` name.
+fn find_orphaned_scenes(
+    scenes: HashMap<String, Scene>,
+    config: &Config,
+    in_use: &[String],
+) -> Vec<(String, Scene)> {
+    let referenced: Vec<&str> = ["success", "failure", "running"]
+        .into_iter()
+        .filter_map(|scene_type| config.get_scene(scene_type))
+        .map(|scene| scene.id.as_str())
+        .chain(in_use.iter().map(String::as_str))
+        .collect();
+
+    let mut orphans: Vec<_> = scenes
+        .into_iter()
+        .filter(|(id, scene)| {
+            scene.has_huestatus_appdata()
+                && !scene.is_locked()
+                && !referenced.contains(&id.as_str())
+        })
+        .collect();
+    orphans.sort_by_key(|(id, _)| id.parse::<u32>().unwrap_or(u32::MAX));
+    orphans
+}
+
+/// Get the bridge `lastupdated` of the configured scene if it differs from the recorded one
+///
+/// Scenes without a recorded value (e.g. from older configs) are never reported.
+fn changed_externally<'a>(
+    scenes: &'a HashMap<String, Scene>,
+    configured_id: &str,
+    recorded: Option<&str>,
+) -> Option<&'a str> {
+    let recorded = recorded?;
+    scenes
+        .get(configured_id)
+        .map(|scene| scene.lastupdated.as_str())
+        .filter(|lastupdated| *lastupdated != recorded)
+}
+
 impl SceneCreationResult {
     /// Get summary of creation result
     pub fn summary(&self) -> String {
         format!(
-            "Created {} scenes using {} lights (Success: {}, Failure: {})",
+            "Created {} scenes, updated {} using {} lights (Success: {}, Failure: {}, Running: {})",
             self.scenes_created,
+            self.scenes_updated,
             self.lights_used.len(),
             self.success_scene_id,
-            self.failure_scene_id
+            self.failure_scene_id,
+            self.running_scene_id
         )
     }
 
     /// Check if creation was successful
     pub fn is_successful(&self) -> bool {
-        self.scenes_created == 2
+        self.scenes_created + self.scenes_updated + self.scenes_skipped == 3
             && !self.success_scene_id.is_empty()
             && !self.failure_scene_id.is_empty()
+            && !self.running_scene_id.is_empty()
     }
 }
 
 impl SceneExecutionResult {
+    /// Check if the recall was accepted but no light visibly changed
+    pub fn has_no_visible_change(&self) -> bool {
+        self.state_change
+            .as_ref()
+            .is_some_and(|change| !change.has_visible_change())
+    }
+
     /// Check if execution was fast (under 500ms)
     pub fn is_fast(&self) -> bool {
         self.execution_time_ms < 500
@@ -528,6 +1304,11 @@ impl SceneExecutionResult {
         }
     }
 
+    /// Check if any request had to be retried
+    pub fn had_retries(&self) -> bool {
+        self.retry_count > 0
+    }
+
     /// Get summary of execution result
     pub fn summary(&self) -> String {
         format!(
@@ -539,11 +1320,58 @@ impl SceneExecutionResult {
     }
 }
 
+impl SceneLightOverlap {
+    /// Compare two scenes' light ID lists
+    pub fn from_light_sets(success: &[String], failure: &[String]) -> Self {
+        let mut overlap = Self::default();
+        for id in success {
+            if failure.contains(id) {
+                overlap.shared.push(id.clone());
+            } else {
+                overlap.success_only.push(id.clone());
+            }
+        }
+        overlap.failure_only = failure
+            .iter()
+            .filter(|id| !success.contains(id))
+            .cloned()
+            .collect();
+        overlap
+    }
+
+    /// Check if both scenes use exactly the same lights
+    pub fn is_identical(&self) -> bool {
+        !self.shared.is_empty() && self.success_only.is_empty() && self.failure_only.is_empty()
+    }
+
+    /// Get overlap summary
+    pub fn summary(&self) -> String {
+        if self.is_identical() {
+            return format!(
+                "success and failure use the same {} light(s)",
+                self.shared.len()
+            );
+        }
+        format!(
+            "{} shared light(s), {} success-only, {} failure-only",
+            self.shared.len(),
+            self.success_only.len(),
+            self.failure_only.len()
+        )
+    }
+}
+
 impl SceneValidationResult {
     /// Get validation summary
     pub fn summary(&self) -> String {
         if self.is_valid {
             format!("Scene '{}' is valid", self.scene_name)
+        } else if self.inconclusive {
+            format!(
+                "Scene '{}' could not be checked: {}",
+                self.scene_name,
+                self.issues.join(", ")
+            )
         } else {
             format!(
                 "Scene '{}' has {} issue(s): {}",
@@ -625,6 +1453,18 @@ impl ColorDefinition {
         }
     }
 
+    /// Convert to a group action that shows the color directly, without a scene
+    pub fn to_light_state_update(&self) -> LightStateUpdate {
+        LightStateUpdate {
+            on: Some(true),
+            bri: Some(self.brightness),
+            hue: Some(self.hue),
+            sat: Some(self.saturation),
+            xy: self.xy,
+            ..LightStateUpdate::default()
+        }
+    }
+
     /// Get color summary
     pub fn summary(&self) -> String {
         format!(
@@ -634,6 +1474,48 @@ impl ColorDefinition {
     }
 }
 
+/// Reject scenes whose lights were all removed, since recalling them is a silent no-op
+pub(crate) fn ensure_scene_has_lights(scene: &Scene) -> Result<()> {
+    if scene.lights.is_empty() {
+        return Err(HueStatusError::EmptyScene {
+            scene_name: scene.name.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Check whether an error proves the scene is absent, as opposed to a failed lookup
+///
+/// Scene lookups answer with Hue error type 3 ("resource, /scenes/<id>, not
+/// available"). Recalling an unknown scene through a group action answers with
+/// type 7 instead ("invalid value, <id>, for parameter, scene"). Other errors,
+/// including a missing light or group, say nothing about the scene.
+pub(crate) fn is_scene_absent_error(error: &HueStatusError) -> bool {
+    match error {
+        HueStatusError::ResourceNotAvailable { resource } => resource.contains("/scenes"),
+        HueStatusError::InvalidConfig { reason } => reason.contains("for parameter, scene"),
+        _ => false,
+    }
+}
+
+/// Bridge scene for tests, unlocked and last updated 2024-01-01; `owned` adds the huestatus marker
+#[cfg(test)]
+pub(crate) fn test_scene(name: &str, lights: &[&str], owned: bool) -> Scene {
+    Scene {
+        name: name.to_string(),
+        lights: lights.iter().map(|id| id.to_string()).collect(),
+        owner: "owner".to_string(),
+        recycle: false,
+        locked: false,
+        appdata: owned.then(crate::bridge::huestatus_appdata),
+        picture: None,
+        image: None,
+        lastupdated: "2024-01-01T00:00:00".to_string(),
+        version: 2,
+        lightstates: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -670,6 +1552,11 @@ mod tests {
             scene_name: "Test Scene".to_string(),
             execution_time_ms: 200,
             success: true,
+            state_change: None,
+            apply_latency_ms: None,
+            already_showing: false,
+            warnings: Vec::new(),
+            retry_count: 0,
         };
 
         assert!(fast_result.is_fast());
@@ -681,11 +1568,24 @@ mod tests {
             scene_name: "Test Scene".to_string(),
             execution_time_ms: 3000,
             success: true,
+            state_change: Some(LightChangeSummary {
+                lights_checked: 2,
+                lights_changed: 0,
+                unchanged_lights: vec!["Desk".to_string(), "Shelf".to_string()],
+            }),
+            apply_latency_ms: Some(800),
+            already_showing: false,
+            warnings: Vec::new(),
+            retry_count: 2,
         };
 
         assert!(!slow_result.is_fast());
         assert!(slow_result.is_slow());
         assert_eq!(slow_result.performance_rating(), "Poor");
+        assert!(!fast_result.has_no_visible_change());
+        assert!(slow_result.has_no_visible_change());
+        assert!(!fast_result.had_retries());
+        assert!(slow_result.had_retries());
     }
 
     #[test]
@@ -713,17 +1613,554 @@ mod tests {
         assert!(unsuitable_light.summary().contains("Unreachable"));
     }
 
+    #[test]
+    fn test_inconclusive_validation_summary() {
+        let timeout = HueStatusError::TimeoutError {
+            operation: "HTTP request".to_string(),
+        };
+        let missing = HueStatusError::ResourceNotAvailable {
+            resource: "/scenes/abc".to_string(),
+        };
+        assert!(!is_scene_absent_error(&timeout));
+        assert!(is_scene_absent_error(&missing));
+        assert!(!is_scene_absent_error(
+            &HueStatusError::ResourceNotAvailable {
+                resource: "resource, /lights/5, not available".to_string(),
+            }
+        ));
+        assert!(!is_scene_absent_error(&HueStatusError::ApiError {
+            message: "HTTP 404 Not Found".to_string(),
+        }));
+        assert!(is_scene_absent_error(&HueStatusError::InvalidConfig {
+            reason: "Invalid value: invalid value, abc, for parameter, scene".to_string(),
+        }));
+        assert!(!is_scene_absent_error(&HueStatusError::InvalidConfig {
+            reason: "Invalid value: invalid value, 500, for parameter, bri".to_string(),
+        }));
+
+        let result = SceneValidationResult {
+            scene_id: "abc".to_string(),
+            scene_name: "huestatus-success".to_string(),
+            is_valid: false,
+            inconclusive: true,
+            issues: vec![format!(
+                "Could not check scene 'huestatus-success': {timeout}"
+            )],
+            lights_status: Vec::new(),
+        };
+        assert!(result.summary().contains("could not be checked"));
+    }
+
     #[test]
     fn test_scene_creation_result() {
         let result = SceneCreationResult {
             success_scene_id: "success-123".to_string(),
             failure_scene_id: "failure-456".to_string(),
+            running_scene_id: "running-789".to_string(),
             lights_used: vec!["1".to_string(), "2".to_string()],
             scenes_created: 2,
+            scenes_updated: 1,
+            scenes_skipped: 0,
+            warnings: Vec::new(),
         };
 
         assert!(result.is_successful());
-        assert!(result.summary().contains("2 scenes"));
+        assert!(result.summary().contains("2 scenes, updated 1"));
         assert!(result.summary().contains("2 lights"));
     }
+
+    #[test]
+    fn test_scene_light_limit() {
+        assert_eq!(scene_light_limit(50, None, 3), 50);
+        assert_eq!(scene_light_limit(50, Some(60), 3), 20);
+        assert_eq!(scene_light_limit(10, Some(600), 3), 10);
+        assert_eq!(scene_light_limit(50, Some(2), 3), 0);
+    }
+
+    #[test]
+    fn test_select_scene_lights() {
+        let light = |id: &str, color: bool| {
+            let control = if color {
+                serde_json::json!({ "colorgamut": [[0.6915, 0.3083], [0.17, 0.7], [0.1532, 0.0475]] })
+            } else {
+                serde_json::json!({ "ct": { "min": 153, "max": 454 } })
+            };
+            let light: Light = serde_json::from_value(serde_json::json!({
+                "name": id,
+                "type": "Extended color light",
+                "state": { "on": true, "reachable": true },
+                "capabilities": { "certified": true, "control": control },
+            }))
+            .unwrap();
+            (id.to_string(), light)
+        };
+        let ids = |kept: &[(String, Light)]| -> Vec<String> {
+            kept.iter().map(|(id, _)| id.clone()).collect()
+        };
+        let lights = vec![
+            light("10", true),
+            light("1", false),
+            light("3", true),
+            light("2", true),
+        ];
+
+        let (kept, skipped) = select_scene_lights(lights.clone(), 4);
+        assert_eq!(ids(&kept), ["1", "2", "3", "10"]);
+        assert_eq!(skipped, 0);
+
+        // The same lights are picked whatever order the bridge listed them in
+        let mut reversed = lights.clone();
+        reversed.reverse();
+        for input in [lights, reversed] {
+            let (kept, skipped) = select_scene_lights(input, 2);
+            assert_eq!(ids(&kept), ["2", "3"]);
+            assert_eq!(skipped, 2);
+        }
+    }
+
+    #[test]
+    fn test_scene_light_overlap() {
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        let same = SceneLightOverlap::from_light_sets(&ids(&["1", "2"]), &ids(&["2", "1"]));
+        assert!(same.is_identical());
+        assert_eq!(
+            same.summary(),
+            "success and failure use the same 2 light(s)"
+        );
+
+        let partial = SceneLightOverlap::from_light_sets(&ids(&["1", "2"]), &ids(&["2", "3"]));
+        assert!(!partial.is_identical());
+        assert_eq!(partial.shared, ids(&["2"]));
+        assert_eq!(partial.success_only, ids(&["1"]));
+        assert_eq!(partial.failure_only, ids(&["3"]));
+    }
+
+    #[test]
+    fn test_find_reusable_scene() {
+        let locked = Scene {
+            locked: true,
+            ..test_scene("huestatus-failure", &["1", "2"], true)
+        };
+        let scenes = HashMap::from([
+            (
+                "12".to_string(),
+                test_scene("huestatus-success", &["2", "1"], true),
+            ),
+            (
+                "3".to_string(),
+                test_scene("huestatus-success", &["1", "2"], true),
+            ),
+            (
+                "2".to_string(),
+                test_scene("huestatus-success", &["1", "2"], false),
+            ),
+            (
+                "1".to_string(),
+                test_scene("huestatus-success", &["1", "5"], true),
+            ),
+            ("7".to_string(), locked),
+            ("8".to_string(), test_scene("Relax", &["1"], false)),
+            ("9".to_string(), test_scene("Renamed", &["4"], false)),
+        ]);
+        let lights = vec!["1".to_string(), "2".to_string()];
+
+        // "2" has no marker and "1" another project's lights
+        assert_eq!(
+            find_reusable_scene(&scenes, "huestatus-success", "", &lights),
+            Some("3")
+        );
+        assert_eq!(
+            find_reusable_scene(&scenes, "huestatus-success", "12", &lights),
+            Some("12")
+        );
+        assert_eq!(
+            find_reusable_scene(&scenes, "huestatus-failure", "7", &lights),
+            None
+        );
+        assert_eq!(find_reusable_scene(&scenes, "missing", "", &lights), None);
+        assert_eq!(find_reusable_scene(&scenes, "Relax", "", &lights), None);
+        assert_eq!(
+            find_reusable_scene(&scenes, "huestatus-failure", "9", &lights),
+            Some("9")
+        );
+    }
+
+    #[test]
+    fn test_find_orphaned_scenes() {
+        let scene = |name: &str, owned: bool| test_scene(name, &["1"], owned);
+        let locked = Scene {
+            locked: true,
+            ..scene("huestatus-failure", false)
+        };
+        let scenes = HashMap::from([
+            ("12".to_string(), scene("huestatus-success", false)),
+            ("3".to_string(), scene("huestatus-success", false)),
+            ("4".to_string(), scene("Renamed", true)),
+            ("5".to_string(), locked),
+            ("6".to_string(), scene("huestatus-success", true)),
+            ("8".to_string(), scene("Relax", false)),
+            ("9".to_string(), scene("huestatus-failure", true)),
+        ]);
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("12".to_string(), "huestatus-success".to_string(), true),
+            Config::create_scene_config("7".to_string(), "huestatus-failure".to_string(), true),
+        );
+
+        // "6" belongs to another project's configuration
+        let in_use = vec!["6".to_string()];
+        let orphans: Vec<_> = find_orphaned_scenes(scenes, &config, &in_use)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(orphans, vec!["4", "9"]);
+    }
+
+    #[test]
+    fn test_foreign_scene_name_collisions() {
+        let scene = |name: &str, owned: bool| test_scene(name, &["1"], owned);
+        let scenes = HashMap::from([
+            ("12".to_string(), scene("huestatus-success", false)),
+            ("3".to_string(), scene("Huestatus-Failure", false)),
+            ("4".to_string(), scene("huestatus-running", true)),
+            ("5".to_string(), scene("huestatus-failure", false)),
+            ("8".to_string(), scene("Relax", false)),
+        ]);
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("12".to_string(), "huestatus-success".to_string(), true),
+            Config::create_scene_config("7".to_string(), "huestatus-failure".to_string(), true),
+        );
+
+        assert_eq!(
+            foreign_scene_name_collisions(&scenes, &config),
+            vec!["3", "5"]
+        );
+    }
+
+    #[test]
+    fn test_collisions_and_orphans_agree_on_ownership() {
+        let scene = |name: &str, owned: bool| test_scene(name, &["1"], owned);
+        let scenes = HashMap::from([
+            ("3".to_string(), scene("huestatus-failure", false)),
+            ("4".to_string(), scene("huestatus-failure", true)),
+            ("5".to_string(), scene("huestatus-mine", false)),
+        ]);
+        let config = Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("1".to_string(), "huestatus-success".to_string(), true),
+            Config::create_scene_config("2".to_string(), "huestatus-failure".to_string(), true),
+        );
+
+        // The hand-made scene is protected from both updating and cleanup
+        let collisions = foreign_scene_name_collisions(&scenes, &config);
+        let orphans: Vec<_> = find_orphaned_scenes(scenes, &config, &[])
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(collisions, vec!["3"]);
+        assert_eq!(orphans, vec!["4"]);
+    }
+
+    #[test]
+    fn test_changed_externally() {
+        let scene = Scene {
+            lastupdated: "2024-03-01T10:00:00".to_string(),
+            ..test_scene("huestatus-success", &["1"], false)
+        };
+        let scenes = HashMap::from([("12".to_string(), scene)]);
+
+        assert_eq!(
+            changed_externally(&scenes, "12", Some("2024-01-01T00:00:00")),
+            Some("2024-03-01T10:00:00")
+        );
+        assert_eq!(
+            changed_externally(&scenes, "12", Some("2024-03-01T10:00:00")),
+            None
+        );
+        assert_eq!(changed_externally(&scenes, "12", None), None);
+        assert_eq!(
+            changed_externally(&scenes, "7", Some("2024-01-01T00:00:00")),
+            None
+        );
+    }
+
+    /// Scene manager for a mock bridge, without retries so error cases finish immediately
+    fn mock_scene_manager(server: &mockito::Server) -> SceneManager {
+        let client = BridgeClient::with_config("192.168.1.100".to_string(), 5, 1, 0, false)
+            .unwrap()
+            .with_base_url(&server.url())
+            .unwrap()
+            .with_username("test-user".to_string());
+        SceneManager::new(client)
+    }
+
+    /// One reachable color light, as a mock bridge's `GET /lights` body
+    const MOCK_LIGHTS: &str = r#"{"1": {"name": "Desk", "type": "Extended color light",
+        "state": {"on": true, "bri": 200, "hue": 0, "sat": 254, "reachable": true},
+        "capabilities": {"certified": true, "control": {"colorgamuttype": "C",
+        "colorgamut": [[0.6915, 0.3083], [0.17, 0.7], [0.1532, 0.0475]]}}}}"#;
+
+    fn import_config() -> Config {
+        Config::new(
+            "192.168.1.100".to_string(),
+            "test-application-key-with-proper-length".to_string(),
+            Config::create_scene_config("12".to_string(), "huestatus-success".to_string(), true),
+            Config::create_scene_config("7".to_string(), "Relax".to_string(), false),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_import_status_scene_deletes_replaced_scene() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/test-user/scenes")
+            .with_body(r#"[{"success": {"id": "Imported1"}}]"#)
+            .create_async()
+            .await;
+        let delete = server
+            .mock("DELETE", "/api/test-user/scenes/12")
+            .with_body(r#"[{"success": "/scenes/12 deleted"}]"#)
+            .create_async()
+            .await;
+
+        let mut config = import_config();
+        let request =
+            CreateSceneRequest::new_success_scene("Party".to_string(), vec!["1".to_string()]);
+        let result = mock_scene_manager(&server)
+            .import_status_scene(&mut config, "success", &request)
+            .await
+            .unwrap();
+
+        delete.assert_async().await;
+        assert_eq!(result.scene_id, "Imported1");
+        assert_eq!(result.replaced_scene_id.as_deref(), Some("12"));
+        assert!(result.warnings.is_empty());
+        assert_eq!(config.scenes.success.id, "Imported1");
+        assert!(config.scenes.success.imported);
+        assert!(!config.scenes.success.auto_created);
+    }
+
+    #[tokio::test]
+    async fn test_import_status_scene_keeps_hand_picked_scene() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/test-user/scenes")
+            .with_body(r#"[{"success": {"id": "Imported2"}}]"#)
+            .create_async()
+            .await;
+        let delete = server
+            .mock("DELETE", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let mut config = import_config();
+        let request =
+            CreateSceneRequest::new_failure_scene("Alarm".to_string(), vec!["1".to_string()]);
+        let result = mock_scene_manager(&server)
+            .import_status_scene(&mut config, "failure", &request)
+            .await
+            .unwrap();
+
+        delete.assert_async().await;
+        assert_eq!(result.replaced_scene_id, None);
+        assert_eq!(config.scenes.failure.id, "Imported2");
+    }
+
+    #[tokio::test]
+    async fn test_delete_status_scenes() {
+        let mut server = mockito::Server::new_async().await;
+        let deleted = server
+            .mock("DELETE", "/api/test-user/scenes/12")
+            .with_body(r#"[{"success": "/scenes/12 deleted"}]"#)
+            .create_async()
+            .await;
+        let absent = server
+            .mock("DELETE", "/api/test-user/scenes/7")
+            .with_body(
+                r#"[{"error": {"type": 3, "address": "/scenes/7", "description": "resource, /scenes/7, not available"}}]"#,
+            )
+            .create_async()
+            .await;
+        let failed = server
+            .mock("DELETE", "/api/test-user/scenes/8")
+            .with_body(
+                r#"[{"error": {"type": 7, "address": "/scenes/8", "description": "invalid value, 8, for parameter, id"}}]"#,
+            )
+            .create_async()
+            .await;
+
+        let mut config = import_config();
+        config.scenes.failure.auto_created = true;
+        config.scenes.running = Some(Config::create_scene_config(
+            "8".to_string(),
+            "huestatus-running".to_string(),
+            true,
+        ));
+        let result = mock_scene_manager(&server)
+            .delete_status_scenes(&config)
+            .await
+            .unwrap();
+
+        deleted.assert_async().await;
+        absent.assert_async().await;
+        failed.assert_async().await;
+        // An already-absent scene counts as deleted
+        assert_eq!(result.deleted, vec!["12", "7"]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, "8");
+        assert!(!result.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_keeps_only_imported_scenes() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/test-user/lights")
+            .with_body(MOCK_LIGHTS)
+            .create_async()
+            .await;
+        let scene = |name: &str| {
+            serde_json::json!({
+                "name": name, "lights": ["1"], "owner": "owner", "recycle": false,
+                "locked": false, "lastupdated": "2024-01-01T00:00:00", "version": 2,
+            })
+        };
+        let scenes = serde_json::json!({ "20": scene("Party"), "7": scene("Relax") });
+        server
+            .mock("GET", "/api/test-user/scenes")
+            .with_body(scenes.to_string())
+            .create_async()
+            .await;
+        let imported_update = server
+            .mock("PUT", "/api/test-user/scenes/20")
+            .expect(0)
+            .create_async()
+            .await;
+        // Picked by hand but not imported: refreshed as before
+        let hand_picked_update = server
+            .mock("PUT", "/api/test-user/scenes/7")
+            .with_body(r#"[{"success": {"/scenes/7/lights": ["1"]}}]"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/test-user/scenes")
+            .with_body(r#"[{"success": {"id": "Running1"}}]"#)
+            .create_async()
+            .await;
+
+        let mut config = import_config();
+        config.scenes.success =
+            Config::create_scene_config("20".to_string(), "Party".to_string(), false);
+        config.scenes.success.imported = true;
+        let result = mock_scene_manager(&server)
+            .create_status_scenes(&mut config)
+            .await
+            .unwrap();
+
+        imported_update.assert_async().await;
+        hand_picked_update.assert_async().await;
+        assert_eq!(result.scenes_skipped, 1);
+        assert_eq!(result.scenes_updated, 1);
+        assert!(config.scenes.success.imported);
+        assert!(config.scenes.failure.auto_created);
+    }
+
+    #[tokio::test]
+    async fn test_failed_change_verification_keeps_the_recall() {
+        let mut server = mockito::Server::new_async().await;
+        let scene = serde_json::json!({
+            "name": "huestatus-success", "lights": ["1"], "owner": "owner", "recycle": false,
+            "locked": false, "lastupdated": "2024-01-01T00:00:00", "version": 2,
+        });
+        server
+            .mock("GET", "/api/test-user/scenes/12")
+            .with_body(scene.to_string())
+            .create_async()
+            .await;
+        // The backup before the recall reads the lights; the check after it fails
+        server
+            .mock("GET", "/api/test-user/lights")
+            .with_body(MOCK_LIGHTS)
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/test-user/lights")
+            .with_status(503)
+            .create_async()
+            .await;
+        let recall = server
+            .mock("PUT", "/api/test-user/groups/0/action")
+            .with_body(r#"[{"success": {"/groups/0/action/scene": "12"}}]"#)
+            .create_async()
+            .await;
+
+        let result = mock_scene_manager(&server)
+            .with_change_verification(true)
+            .execute_status_scene("success", &import_config())
+            .await
+            .unwrap();
+
+        recall.assert_async().await;
+        assert!(result.success);
+        assert_eq!(result.state_change, None);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].starts_with("Could not verify light changes: "));
+    }
+
+    #[tokio::test]
+    async fn test_recall_of_stale_scene_id_resolves_by_name() {
+        let mut server = mockito::Server::new_async().await;
+        // What the bridge answers when a group action recalls an unknown scene
+        let stale_recall = server
+            .mock("PUT", "/api/test-user/groups/0/action")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({ "scene": "12" }),
+            ))
+            .with_body(
+                r#"[{"error": {"type": 7, "address": "/groups/0/action/scene", "description": "invalid value, 12, for parameter, scene"}}]"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/test-user/scenes/12")
+            .with_body(
+                r#"[{"error": {"type": 3, "address": "/scenes/12", "description": "resource, /scenes/12, not available"}}]"#,
+            )
+            .create_async()
+            .await;
+        let scene = serde_json::json!({
+            "name": "huestatus-success", "lights": ["1"], "owner": "owner", "recycle": false,
+            "locked": false, "lastupdated": "2024-01-01T00:00:00", "version": 2,
+        });
+        server
+            .mock("GET", "/api/test-user/scenes")
+            .with_body(serde_json::json!({ "34": scene }).to_string())
+            .create_async()
+            .await;
+        let recall = server
+            .mock("PUT", "/api/test-user/groups/0/action")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({ "scene": "34" }),
+            ))
+            .with_body(r#"[{"success": {"/groups/0/action/scene": "34"}}]"#)
+            .create_async()
+            .await;
+
+        let result = mock_scene_manager(&server)
+            .execute_status_scene("success", &import_config())
+            .await
+            .unwrap();
+
+        stale_recall.assert_async().await;
+        recall.assert_async().await;
+        assert!(result.success);
+        assert_eq!(result.scene_id, "34");
+    }
 }
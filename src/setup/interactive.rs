@@ -1,7 +1,9 @@
 use crate::bridge::DiscoveredBridge;
 use crate::error::{HueStatusError, Result};
+use crate::i18n::message;
+use crate::setup::{flush_progress, progress_to_stderr};
 use console::{style, Term};
-use std::io::{self, Write};
+use std::io::{self, IsTerminal};
 
 /// Interactive user interface for setup
 pub struct InteractiveSetup {
@@ -18,15 +20,15 @@ impl InteractiveSetup {
 
     /// Confirm bridge selection with user
     pub fn confirm_bridge(&self, bridge: &DiscoveredBridge) -> Result<bool> {
-        println!("Found Hue bridge:");
-        println!("  • Name: {}", bridge.name.as_deref().unwrap_or("Unknown"));
-        println!("  • IP: {}", bridge.ip);
+        progress!("Found Hue bridge:");
+        progress!("  • Name: {}", bridge.name.as_deref().unwrap_or("Unknown"));
+        progress!("  • IP: {}", bridge.ip);
         if let Some(model) = &bridge.model {
-            println!("  • Model: {model}");
+            progress!("  • Model: {model}");
         }
-        println!();
+        progress!();
 
-        self.ask_yes_no("Use this bridge?", true)
+        self.ask_yes_no(&message("setup.use_bridge", &[]), true)
     }
 
     /// Ask yes/no question
@@ -34,10 +36,8 @@ impl InteractiveSetup {
         let default_text = if default { "(Y/n)" } else { "(y/N)" };
 
         loop {
-            print!("{} {}: ", question, style(default_text).dim());
-            io::stdout()
-                .flush()
-                .map_err(|e| HueStatusError::IoError { source: e })?;
+            progress_prompt!("{} {}: ", question, style(default_text).dim());
+            flush_progress().map_err(|e| HueStatusError::IoError { source: e })?;
 
             let mut input = String::new();
             io::stdin()
@@ -48,7 +48,7 @@ impl InteractiveSetup {
                 "y" | "yes" => return Ok(true),
                 "n" | "no" => return Ok(false),
                 "" => return Ok(default),
-                _ => println!("Please enter 'y' or 'n'"),
+                _ => progress!("{}", message("setup.answer_yes_no", &[])),
             }
         }
     }
@@ -56,10 +56,8 @@ impl InteractiveSetup {
     /// Get manual IP from user
     pub fn get_manual_ip(&self) -> Result<String> {
         loop {
-            print!("Enter bridge IP address: ");
-            io::stdout()
-                .flush()
-                .map_err(|e| HueStatusError::IoError { source: e })?;
+            progress_prompt!("{}", message("setup.enter_bridge_ip", &[]));
+            flush_progress().map_err(|e| HueStatusError::IoError { source: e })?;
 
             let mut input = String::new();
             io::stdin()
@@ -71,29 +69,48 @@ impl InteractiveSetup {
                 return Ok(ip.to_string());
             }
 
-            println!("Please enter a valid IP address.");
+            progress!("{}", message("setup.invalid_ip", &[]));
         }
     }
 
     /// Show authentication instructions
     pub fn show_auth_instructions(&self, bridge_ip: &str) {
-        println!();
-        println!("{}", style("Authentication Required").bold().cyan());
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!();
-        println!("To connect to your Hue bridge at {bridge_ip}:");
-        println!("1. Press the large round button on top of your bridge");
-        println!("2. The button will start blinking");
-        println!("3. Press Enter within 30 seconds");
-        println!();
-        print!("Press the bridge button now, then press Enter...");
-        io::stdout().flush().ok();
+        progress!();
+        progress!(
+            "{}",
+            style(message("setup.auth_required", &[])).bold().cyan()
+        );
+        progress!("━━━━━━━━━━━━━━━━━━━━━━━━━");
+        progress!();
+        progress!("{}", message("setup.auth_connect", &[("ip", bridge_ip)]));
+        progress!("{}", message("setup.auth_step_press", &[]));
+        progress!("{}", message("setup.auth_step_blink", &[]));
+        progress!("{}", message("setup.auth_step_enter", &[]));
+        progress!();
+        progress_prompt!("{}", message("setup.auth_press_enter", &[]));
+        flush_progress().ok();
 
         let mut input = String::new();
         io::stdin().read_line(&mut input).ok();
     }
 }
 
+/// Ask for confirmation, the one place every prompt goes through
+///
+/// `--yes` confirms without asking; without an interactive terminal the
+/// default is used, since nobody can answer.
+pub fn confirm(question: &str, default: bool, assume_yes: bool) -> bool {
+    if assume_yes {
+        return true;
+    }
+    if !io::stdin().is_terminal() {
+        return default;
+    }
+    InteractiveSetup::new()
+        .ask_yes_no(question, default)
+        .unwrap_or(default)
+}
+
 impl Default for InteractiveSetup {
     fn default() -> Self {
         Self::new()
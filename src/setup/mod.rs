@@ -1,17 +1,73 @@
-use crate::bridge::{BridgeAuth, BridgeClient, BridgeDiscovery, DiscoveredBridge};
+use crate::bridge::{BridgeAuth, BridgeClient, BridgeDiscovery, DiscoveredBridge, Light};
 use crate::config::{file::init_config_directory, Config};
 use crate::error::{HueStatusError, Result};
+use crate::i18n::message;
 use crate::scenes::SceneManager;
 use console::{style, Term};
-use std::io::{self, Write};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Write};
+use std::sync::OnceLock;
 use std::time::Duration;
 
+/// Whether setup progress goes to stderr, keeping stdout for `--json` output
+static PROGRESS_TO_STDERR: OnceLock<bool> = OnceLock::new();
+
+/// Send setup progress and prompts to stderr (only the first call has an effect)
+pub fn init_progress_to_stderr(enabled: bool) {
+    let _ = PROGRESS_TO_STDERR.set(enabled);
+}
+
+fn progress_to_stderr() -> bool {
+    PROGRESS_TO_STDERR.get().copied().unwrap_or(false)
+}
+
+/// Flush the stream setup progress goes to, e.g. after a prompt
+fn flush_progress() -> io::Result<()> {
+    if progress_to_stderr() {
+        io::stderr().flush()
+    } else {
+        io::stdout().flush()
+    }
+}
+
+/// Print a line of setup progress (stdout, or stderr under `--json`)
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if progress_to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Print setup output without a newline, e.g. a prompt
+macro_rules! progress_prompt {
+    ($($arg:tt)*) => {
+        if progress_to_stderr() {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
 pub mod interactive;
+pub mod repair;
 pub mod validation;
 
 pub use interactive::*;
+pub use repair::*;
 pub use validation::*;
 
+/// Names of the scenes created by setup
+const STATUS_SCENE_NAMES: [&str; 3] = [
+    "huestatus-success",
+    "huestatus-failure",
+    "huestatus-running",
+];
+
 /// Setup process orchestrator
 #[derive(Debug)]
 pub struct SetupProcess {
@@ -21,6 +77,29 @@ pub struct SetupProcess {
     term: Term,
 }
 
+/// Error for when discovery failed and the bridge IP can't be asked for
+fn no_manual_ip_error() -> HueStatusError {
+    HueStatusError::SetupFailed {
+        reason: "bridge discovery failed and stdin is not an interactive terminal to enter \
+                 the bridge IP; run setup from a terminal"
+            .to_string(),
+    }
+}
+
+/// Replace a revoked application key by pressing the link button again
+///
+/// Used when the bridge's whitelist entry was deleted: the bridge and scenes
+/// stay as configured, only the key and its verification time change.
+pub async fn reauthenticate(config: &mut Config) -> Result<()> {
+    let auth =
+        BridgeAuth::new(config.bridge.ip.clone())?.with_verbose(config.settings.verbose_logging);
+    let result = auth.authenticate_interactive("huestatus", "cli").await?;
+
+    config.bridge.application_key = result.username;
+    config.update_last_verified();
+    Ok(())
+}
+
 /// Setup configuration options
 #[derive(Debug, Clone)]
 pub struct SetupOptions {
@@ -32,6 +111,28 @@ pub struct SetupOptions {
     pub skip_validation: bool,
     pub backup_existing: bool,
     pub test_scenes: bool,
+    /// Skip TLS certificate verification for self-signed bridge certificates
+    pub insecure: bool,
+    /// Stop the network scan once the first bridge is confirmed
+    pub first_match: bool,
+    /// Scan every host on the local subnet when the discovery service and mDNS find nothing
+    pub network_scan: bool,
+    /// Override the lowest brightness used in the status scenes
+    pub min_brightness: Option<u8>,
+    /// Override the brightness of all status scenes (1-254)
+    pub brightness: Option<u8>,
+    /// Only report what setup would do, without changing the bridge or filesystem
+    pub check_only: bool,
+    /// Group to recall the status on; only its lights are used for the status scenes
+    pub group_id: Option<String>,
+    /// Answer yes to every confirmation prompt, except the network scan (see `network_scan`)
+    pub assume_yes: bool,
+    /// Bridge discovery service to use instead of the Philips one
+    pub discovery_url: Option<String>,
+    /// Hue remote API access token; enables remote discovery when local discovery fails
+    pub remote_token: Option<String>,
+    /// Fail instead of warning when a status scene name is taken by a scene huestatus didn't create
+    pub strict_scene_names: bool,
 }
 
 /// Setup result with detailed information
@@ -42,12 +143,52 @@ pub struct SetupResult {
     pub bridge_name: String,
     pub username: String,
     pub scenes_created: usize,
+    pub scenes_updated: usize,
     pub lights_configured: usize,
     pub config_path: String,
     pub duration_ms: u64,
     pub warnings: Vec<String>,
 }
 
+/// What setup would do, gathered without changing the bridge or filesystem
+#[derive(Debug, Clone)]
+pub struct SetupPlan {
+    pub config_path: String,
+    pub config_exists: bool,
+    pub force: bool,
+    pub bridge: Option<DiscoveredBridge>,
+    /// Whether the existing application key works (`None` if there is none for this bridge)
+    pub key_valid: Option<bool>,
+    /// Suitable light count, known only with a working application key
+    pub suitable_lights: Option<usize>,
+    /// Status scenes to write, with the scene already using that name (`None` if unknown)
+    pub scenes: Vec<(String, Option<ExistingScene>)>,
+    pub notes: Vec<String>,
+}
+
+/// What `doctor` diagnostics found
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiagnosticsReport {
+    pub config_found: bool,
+    pub config_loaded: bool,
+    pub bridge_connected: bool,
+    /// Checks that failed
+    pub problems: Vec<String>,
+    /// Findings that don't stop huestatus from working
+    pub warnings: Vec<String>,
+}
+
+/// Scene already on the bridge under a status scene name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExistingScene {
+    /// No scene uses the name
+    Absent,
+    /// Created by huestatus (appdata marker); updated if it uses the same lights
+    Huestatus,
+    /// Made by hand or another tool; left as is
+    Foreign,
+}
+
 /// Setup step for progress tracking
 #[derive(Debug, Clone, PartialEq)]
 pub enum SetupStep {
@@ -84,7 +225,11 @@ impl SetupProcess {
             verbose: false,
             force: false,
             config_path: None,
-            term: Term::stdout(),
+            term: if progress_to_stderr() {
+                Term::stderr()
+            } else {
+                Term::stdout()
+            },
         }
     }
 
@@ -134,7 +279,8 @@ impl SetupProcess {
         status.message = format!("Authenticating with bridge at {}...", bridge.ip);
         self.update_progress(&status);
 
-        let auth_result = self.authenticate_bridge(&bridge, options).await?;
+        let auth = self.bridge_auth(&bridge, options)?;
+        let auth_result = self.authenticate_bridge(&auth, options).await?;
         status.completed_steps += 1;
 
         // Step 4: Discover lights
@@ -142,11 +288,11 @@ impl SetupProcess {
         status.message = "Discovering lights...".to_string();
         self.update_progress(&status);
 
-        let client = BridgeClient::new(bridge.ip.clone())?
-            .with_username(auth_result.username.clone())
-            .with_verbose(self.verbose);
+        // One client (and connection pool) for the rest of the run
+        let client = auth.create_authenticated_client(auth_result.username.clone())?;
 
-        let suitable_lights = client.get_suitable_lights().await?;
+        let group_id = options.group_id.as_deref().unwrap_or("0");
+        let suitable_lights = client.get_suitable_lights_in_group(group_id).await?;
 
         if suitable_lights.is_empty() {
             return Err(HueStatusError::NoLightsFound);
@@ -166,9 +312,29 @@ impl SetupProcess {
             Config::create_scene_config("".to_string(), "huestatus-success".to_string(), true),
             Config::create_scene_config("".to_string(), "huestatus-failure".to_string(), true),
         );
+        config.settings.insecure = options.insecure;
+        if let Some(min_brightness) = options.min_brightness {
+            config.settings.min_brightness = min_brightness;
+        }
+        if let Some(brightness) = options.brightness {
+            config.colors.set_brightness(brightness);
+        }
+        if let Some(group_id) = &options.group_id {
+            config.settings.recall_group_id = group_id.clone();
+        }
 
-        let scene_manager = SceneManager::new(client.clone()).with_verbose(self.verbose);
+        let scene_manager = SceneManager::new(client.clone())
+            .with_verbose(self.verbose)
+            .with_strict_scene_names(options.strict_scene_names);
         let scene_result = scene_manager.create_status_scenes(&mut config).await?;
+        warnings.extend(scene_result.warnings.iter().cloned());
+
+        if let Some(group_id) = self
+            .select_recall_group(&client, &scene_result.lights_used, options)
+            .await
+        {
+            config.settings.recall_group_id = group_id;
+        }
         status.completed_steps += 1;
 
         // Step 6: Validate setup
@@ -215,6 +381,7 @@ impl SetupProcess {
             bridge_name: bridge.name.unwrap_or_else(|| "Unknown Bridge".to_string()),
             username: auth_result.username,
             scenes_created: scene_result.scenes_created,
+            scenes_updated: scene_result.scenes_updated,
             lights_configured: suitable_lights.len(),
             config_path: config_path_str,
             duration_ms: duration,
@@ -228,11 +395,11 @@ impl SetupProcess {
     /// Show setup header
     fn show_header(&self) {
         self.term.clear_screen().ok();
-        println!();
-        println!("{}", style("🏗️  Huestatus Setup").bold().cyan());
-        println!("{}", style("━".repeat(50)).dim());
-        println!("Welcome to huestatus! Let's configure your Philips Hue lights.");
-        println!();
+        progress!();
+        progress!("{}", style("🏗️  Huestatus Setup").bold().cyan());
+        progress!("{}", style("━".repeat(50)).dim());
+        progress!("{}", message("setup.welcome", &[]));
+        progress!();
     }
 
     /// Update progress display
@@ -243,7 +410,7 @@ impl SetupProcess {
         let progress_bar = "█".repeat(progress_bar_len);
         let empty_bar = "░".repeat(20 - progress_bar_len);
 
-        println!(
+        progress!(
             "{} Step {}/{}: {}",
             self.get_step_emoji(&status.current_step),
             status.completed_steps + 1,
@@ -252,10 +419,10 @@ impl SetupProcess {
         );
 
         if self.verbose {
-            println!("   [{progress_bar}{empty_bar}] {progress:.0}%");
+            progress!("   [{progress_bar}{empty_bar}] {progress:.0}%");
         }
 
-        println!();
+        progress!();
     }
 
     /// Get emoji for setup step
@@ -275,13 +442,13 @@ impl SetupProcess {
     /// Initialize setup process
     async fn initialize_setup(&self) -> Result<()> {
         if self.verbose {
-            println!("  • Initializing configuration directory...");
+            progress!("  • Initializing configuration directory...");
         }
 
         init_config_directory()?;
 
         if self.verbose {
-            println!("  • Configuration directory ready");
+            progress!("  • Configuration directory ready");
         }
 
         Ok(())
@@ -292,12 +459,10 @@ impl SetupProcess {
         &self,
         options: &SetupOptions,
     ) -> Result<DiscoveredBridge> {
-        let discovery = BridgeDiscovery::new()?
-            .with_timeout(Duration::from_secs(options.timeout_seconds))
-            .with_verbose(self.verbose);
+        let discovery = self.bridge_discovery(options)?;
 
         if self.verbose {
-            println!("  • Trying Philips discovery service...");
+            progress!("  • Trying Philips discovery service...");
         }
 
         // Try all discovery methods
@@ -305,14 +470,64 @@ impl SetupProcess {
             Ok(result) => {
                 if let Some(bridge) = result.first_bridge() {
                     if self.verbose {
-                        println!("  • Found bridge: {}", bridge.display_name());
+                        progress!("  • Found bridge: {}", bridge.display_name());
                     }
                     return Ok(bridge.clone());
                 }
             }
             Err(e) => {
                 if self.verbose {
-                    println!("  • Automatic discovery failed: {e}");
+                    progress!("  • Automatic discovery failed: {e}");
+                }
+            }
+        }
+
+        // The scan probes every host on the subnet, so ask before running it;
+        // --yes doesn't count as consent, only --network-scan does
+        if !options.network_scan
+            && options.interactive
+            && !options.assume_yes
+            && confirm(&message("setup.confirm_network_scan", &[]), false, false)
+        {
+            if self.verbose {
+                progress!("  • Scanning the local network...");
+            }
+            match discovery.discover_via_network_scan().await {
+                Ok(result) => {
+                    if let Some(bridge) = result.first_bridge() {
+                        if self.verbose {
+                            progress!("  • Found bridge: {}", bridge.display_name());
+                        }
+                        return Ok(bridge.clone());
+                    }
+                }
+                Err(e) => {
+                    if self.verbose {
+                        progress!("  • Network scan failed: {e}");
+                    }
+                }
+            }
+        }
+
+        // With an access token, ask the Hue cloud before falling back to a manual IP
+        if let Some(token) = &options.remote_token {
+            if self.verbose {
+                progress!("  • Trying the Hue remote API...");
+            }
+            let remote = discovery.clone().with_remote_access_token(token.clone());
+            match remote.discover_via_remote().await {
+                Ok(result) => {
+                    if let Some(bridge) = result.first_bridge() {
+                        if self.verbose {
+                            progress!("  • Found bridge: {}", bridge.display_name());
+                        }
+                        return Ok(bridge.clone());
+                    }
+                }
+                Err(e) => {
+                    if self.verbose {
+                        progress!("  • Remote discovery failed: {e}");
+                    }
                 }
             }
         }
@@ -321,39 +536,173 @@ impl SetupProcess {
         self.request_manual_bridge_ip(&discovery).await
     }
 
+    /// Create a bridge discovery client for the setup options
+    fn bridge_discovery(&self, options: &SetupOptions) -> Result<BridgeDiscovery> {
+        let mut discovery = BridgeDiscovery::new()?
+            .with_timeout(Duration::from_secs(options.timeout_seconds))
+            .with_verbose(self.verbose)
+            .with_first_match(options.first_match)
+            .with_network_scan(options.network_scan);
+        if let Some(url) = &options.discovery_url {
+            discovery = discovery.with_discovery_url(url.clone());
+        }
+        Ok(discovery)
+    }
+
+    /// Create an authentication manager for the setup options
+    fn bridge_auth(&self, bridge: &DiscoveredBridge, options: &SetupOptions) -> Result<BridgeAuth> {
+        Ok(BridgeAuth::new(bridge.ip.clone())?
+            .with_timeout(Duration::from_secs(options.timeout_seconds))
+            .with_verbose(self.verbose))
+    }
+
+    /// Work out what setup would do, without pressing-button polling or any changes
+    pub async fn plan(&self, options: &SetupOptions) -> Result<SetupPlan> {
+        let config_path =
+            crate::config::safe_path_to_string_fallback(Config::get_config_file_path());
+        let config_exists = Config::exists();
+        let existing = if config_exists {
+            Config::load().ok()
+        } else {
+            None
+        };
+        let mut notes = Vec::new();
+
+        let bridge = match self.bridge_discovery(options)?.discover_all().await {
+            Ok(result) => result.first_bridge().cloned(),
+            Err(e) => {
+                notes.push(format!(
+                    "Automatic discovery failed ({e}); setup would ask for the bridge IP"
+                ));
+                if !options.network_scan {
+                    notes.push(
+                        "The network scan was skipped; pass --network-scan to include it"
+                            .to_string(),
+                    );
+                }
+                None
+            }
+        };
+
+        // Only an existing key for the same bridge can be checked without the link button
+        let mut key_valid = None;
+        let mut client = None;
+        if let Some(bridge) = &bridge {
+            let same_bridge = existing
+                .as_ref()
+                .filter(|config| config.bridge.ip == bridge.ip);
+            match same_bridge {
+                Some(config) => {
+                    let auth = self.bridge_auth(bridge, options)?;
+                    let key = &config.bridge.application_key;
+                    let valid = auth.test_authentication(key).await.is_ok();
+                    if valid {
+                        client = Some(auth.create_authenticated_client(key.clone())?);
+                    } else {
+                        notes.push("The existing application key was rejected; setup would wait for the link button".to_string());
+                    }
+                    key_valid = Some(valid);
+                }
+                None => notes.push(
+                    "No application key for this bridge; setup would wait for the link button"
+                        .to_string(),
+                ),
+            }
+        }
+
+        let suitable_lights = match &client {
+            Some(client) => match client
+                .get_suitable_lights_in_group(options.group_id.as_deref().unwrap_or("0"))
+                .await
+            {
+                Ok(lights) => Some(lights.len()),
+                Err(HueStatusError::NoLightsFound) => Some(0),
+                Err(e) => {
+                    notes.push(format!("Could not list lights: {e}"));
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let bridge_scenes = match &client {
+            Some(client) => client.get_scenes().await.ok(),
+            None => None,
+        };
+        let scenes = STATUS_SCENE_NAMES
+            .iter()
+            .map(|name| {
+                let existing = bridge_scenes.as_ref().map(|scenes| {
+                    let named: Vec<_> = scenes
+                        .values()
+                        .filter(|scene| scene.name == *name)
+                        .collect();
+                    if named.is_empty() {
+                        ExistingScene::Absent
+                    } else if named.iter().any(|scene| scene.has_huestatus_appdata()) {
+                        ExistingScene::Huestatus
+                    } else {
+                        ExistingScene::Foreign
+                    }
+                });
+                (name.to_string(), existing)
+            })
+            .collect();
+
+        Ok(SetupPlan {
+            config_path,
+            config_exists,
+            force: options.force,
+            bridge,
+            key_valid,
+            suitable_lights,
+            scenes,
+            notes,
+        })
+    }
+
     /// Request manual bridge IP from user
     async fn request_manual_bridge_ip(
         &self,
         discovery: &BridgeDiscovery,
     ) -> Result<DiscoveredBridge> {
-        println!("⚠️ Automatic bridge discovery failed.");
-        println!("Please enter your Hue bridge IP address manually.");
-        println!();
+        progress!("{}", message("setup.discovery_failed", &[]));
+
+        // Without a terminal (e.g. in CI) nobody can answer the prompt
+        if !io::stdin().is_terminal() {
+            return Err(no_manual_ip_error());
+        }
+
+        progress!("{}", message("setup.enter_ip_manually", &[]));
+        progress!();
 
         loop {
-            print!("Bridge IP address: ");
-            io::stdout().flush().unwrap();
+            progress_prompt!("{}", message("setup.bridge_ip_prompt", &[]));
+            flush_progress().unwrap();
 
             let mut input = String::new();
-            io::stdin()
+            let read = io::stdin()
                 .read_line(&mut input)
                 .map_err(|e| HueStatusError::IoError { source: e })?;
+            if read == 0 {
+                return Err(no_manual_ip_error());
+            }
 
             let ip = input.trim();
             if ip.is_empty() {
-                println!("Please enter a valid IP address.");
+                progress!("{}", message("setup.invalid_ip", &[]));
                 continue;
             }
 
             match discovery.discover_manual(ip).await {
                 Ok(result) => {
                     if let Some(bridge) = result.first_bridge() {
-                        println!("✅Bridge found at {ip}");
+                        progress!("{}", message("setup.bridge_found", &[("ip", ip)]));
                         return Ok(bridge.clone());
                     }
                 }
                 Err(_) => {
-                    println!("❌No Hue bridge found at {ip}. Please try again.");
+                    progress!("{}", message("setup.bridge_not_found_at", &[("ip", ip)]));
                     continue;
                 }
             }
@@ -363,24 +712,21 @@ impl SetupProcess {
     /// Authenticate with bridge
     async fn authenticate_bridge(
         &self,
-        bridge: &DiscoveredBridge,
+        auth: &BridgeAuth,
         options: &SetupOptions,
     ) -> Result<crate::bridge::AuthResult> {
-        let auth = BridgeAuth::new(bridge.ip.clone())?
-            .with_timeout(Duration::from_secs(options.timeout_seconds))
-            .with_verbose(self.verbose);
-
         if options.interactive {
             auth.authenticate_interactive("huestatus", "cli").await
         } else {
             // Show instructions and wait for user input
-            println!("🔑Press the link button on your Hue bridge now.");
-            println!("The button is the large round button on top of the bridge.");
-            println!(
-                "You have {} seconds to press it...",
-                options.timeout_seconds
+            let seconds = options.timeout_seconds.to_string();
+            progress!("{}", message("setup.press_link_button", &[]));
+            progress!("{}", message("setup.link_button_location", &[]));
+            progress!(
+                "{}",
+                message("setup.link_button_deadline", &[("seconds", &seconds)])
             );
-            println!();
+            progress!();
 
             auth.authenticate("huestatus", "cli").await
         }
@@ -388,7 +734,7 @@ impl SetupProcess {
 
     /// Show discovered lights
     fn show_discovered_lights(&self, lights: &[(String, crate::bridge::Light)]) {
-        println!("💡Found {} suitable light(s):", lights.len());
+        progress!("💡Found {} suitable light(s):", lights.len());
 
         for (id, light) in lights {
             let status = if light.is_reachable() {
@@ -405,9 +751,50 @@ impl SetupProcess {
                 style("Basic").dim()
             };
 
-            println!("  {} {} ({}) - {}", status, light.name, id, color_support);
+            if self.verbose {
+                progress!(
+                    "  {} {} ({}) - {} - {}",
+                    status,
+                    light.name,
+                    id,
+                    color_support,
+                    light.effective_color_summary()
+                );
+            } else {
+                progress!("  {} {} ({}) - {}", status, light.name, id, color_support);
+            }
+        }
+        progress!();
+    }
+
+    /// Offer to confine the status to the room containing the selected lights
+    async fn select_recall_group(
+        &self,
+        client: &BridgeClient,
+        light_ids: &[String],
+        options: &SetupOptions,
+    ) -> Option<String> {
+        if !options.interactive || options.group_id.is_some() || light_ids.is_empty() {
+            return None;
         }
-        println!();
+
+        let groups = match client.get_groups().await {
+            Ok(groups) => groups,
+            Err(e) => {
+                if self.verbose {
+                    progress!("  • Could not list rooms: {e}");
+                }
+                return None;
+            }
+        };
+
+        // A light belongs to at most one room, so there is at most one match
+        let (group_id, room) = groups
+            .into_iter()
+            .find(|(_, group)| group.is_room() && group.contains_lights(light_ids))?;
+
+        let question = message("setup.confine_to_room", &[("room", &room.name)]);
+        confirm(&question, true, options.assume_yes).then_some(group_id)
     }
 
     /// Validate setup
@@ -415,14 +802,14 @@ impl SetupProcess {
         let mut warnings = Vec::new();
 
         if self.verbose {
-            println!("  • Validating bridge connection...");
+            progress!("  • Validating bridge connection...");
         }
 
         // Test bridge connection
         client.test_connection().await?;
 
         if self.verbose {
-            println!("  • Validating scenes...");
+            progress!("  • Validating scenes...");
         }
 
         // Validate scenes
@@ -436,7 +823,7 @@ impl SetupProcess {
         }
 
         if self.verbose {
-            println!("  • Validation completed with {} warnings", warnings.len());
+            progress!("  • Validation completed with {} warnings", warnings.len());
         }
 
         Ok(warnings)
@@ -444,41 +831,49 @@ impl SetupProcess {
 
     /// Test scenes
     async fn test_scenes(&self, config: &Config, scene_manager: &SceneManager) -> Result<()> {
-        println!("🎨Testing scene execution...");
+        progress!("🎨Testing scene execution...");
 
         // Test success scene
         if let Some(success_scene) = config.get_scene("success") {
             match scene_manager.test_scene_execution(&success_scene.id).await {
-                Ok(_) => println!("  ✅Success scene test passed"),
-                Err(e) => println!("  ⚠️Success scene test failed: {e}"),
+                Ok(_) => progress!("  ✅Success scene test passed"),
+                Err(e) => progress!("  ⚠️Success scene test failed: {e}"),
             }
         }
 
         // Test failure scene
         if let Some(failure_scene) = config.get_scene("failure") {
             match scene_manager.test_scene_execution(&failure_scene.id).await {
-                Ok(_) => println!("  ✅Failure scene test passed"),
-                Err(e) => println!("  ⚠️Failure scene test failed: {e}"),
+                Ok(_) => progress!("  ✅Failure scene test passed"),
+                Err(e) => progress!("  ⚠️Failure scene test failed: {e}"),
             }
         }
 
-        println!();
+        // Test running scene
+        if let Some(running_scene) = config.get_scene("running") {
+            match scene_manager.test_scene_execution(&running_scene.id).await {
+                Ok(_) => progress!("  ✅Running scene test passed"),
+                Err(e) => progress!("  ⚠️Running scene test failed: {e}"),
+            }
+        }
+
+        progress!();
         Ok(())
     }
 
     /// Handle existing configuration
     async fn handle_existing_config(&self) -> Result<SetupResult> {
-        println!("⚠️Configuration already exists!");
+        progress!("{}", message("setup.config_exists", &[]));
 
         if self.force {
-            println!("Force flag detected, overwriting existing configuration...");
+            progress!("Force flag detected, overwriting existing configuration...");
             return Err(HueStatusError::SetupFailed {
                 reason: "Force setup not yet implemented".to_string(),
             });
         }
 
-        println!("Use --force to overwrite the existing configuration.");
-        println!("Or use 'huestatus --validate' to check your current setup.");
+        progress!("{}", message("setup.use_force", &[]));
+        progress!("{}", message("setup.use_validate", &[]));
 
         Err(HueStatusError::SetupFailed {
             reason: "Configuration already exists".to_string(),
@@ -487,53 +882,70 @@ impl SetupProcess {
 
     /// Show success message
     fn show_success(&self, result: &SetupResult) {
-        println!("{}", style("━".repeat(50)).dim());
-        println!("✨Setup completed successfully!");
-        println!();
-        println!("Configuration Summary:");
-        println!("  • Bridge: {} ({})", result.bridge_name, result.bridge_ip);
-        println!("  • Scenes created: {}", result.scenes_created);
-        println!("  • Lights configured: {}", result.lights_configured);
-        println!("  • Setup time: {:.1}s", result.duration_ms as f64 / 1000.0);
-        println!("  • Config saved to: {}", style(&result.config_path).cyan());
+        progress!("{}", style("━".repeat(50)).dim());
+        progress!("✨Setup completed successfully!");
+        progress!();
+        progress!("Configuration Summary:");
+        progress!("  • Bridge: {} ({})", result.bridge_name, result.bridge_ip);
+        progress!("  • Scenes created: {}", result.scenes_created);
+        if result.scenes_updated > 0 {
+            progress!("  • Scenes updated: {}", result.scenes_updated);
+        }
+        progress!("  • Lights configured: {}", result.lights_configured);
+        progress!("  • Setup time: {:.1}s", result.duration_ms as f64 / 1000.0);
+        progress!("  • Config saved to: {}", style(&result.config_path).cyan());
 
         if !result.warnings.is_empty() {
-            println!();
-            println!("⚠️Warnings:");
+            progress!();
+            progress!("⚠️Warnings:");
             for warning in &result.warnings {
-                println!("  • {warning}");
+                progress!("  • {warning}");
             }
         }
 
-        println!();
-        println!("{}You can now use:", style("Next steps:").bold());
-        println!(
+        progress!();
+        progress!("{}You can now use:", style("Next steps:").bold());
+        progress!(
             "  • {} - Show successful status",
             style("huestatus success").green()
         );
-        println!(
+        progress!(
             "  • {} - Show failure status",
             style("huestatus failure").red()
         );
-        println!(
+        progress!(
             "  • {} - Validate your setup",
             style("huestatus --validate").cyan()
         );
-        println!();
+        progress!();
     }
 
     /// Run setup diagnostics
-    pub async fn run_diagnostics(&self) -> Result<()> {
-        println!("⚙️Running setup diagnostics...");
-        println!();
+    pub async fn run_diagnostics(&self) -> Result<DiagnosticsReport> {
+        progress!("⚙️Running setup diagnostics...");
+        progress!();
+
+        let mut report = DiagnosticsReport::default();
+        let problem = |report: &mut DiagnosticsReport, text: String| {
+            progress!("❌{text}");
+            report.problems.push(text);
+        };
 
         // Check if config exists
         if Config::exists() {
-            println!("✅Configuration file found");
+            progress!("✅Configuration file found");
+            report.config_found = true;
 
-            match Config::load() {
+            match Config::load().and_then(|mut config| {
+                config.apply_env_overrides()?;
+                Ok(config)
+            }) {
                 Ok(config) => {
-                    println!("✅Configuration loaded successfully");
+                    progress!("✅Configuration loaded successfully");
+                    report.config_loaded = true;
+                    for line in config.summary().lines() {
+                        progress!("   {line}");
+                    }
 
                     // Test bridge connection
                     match BridgeClient::new(config.bridge.ip.clone()) {
@@ -542,23 +954,105 @@ impl SetupProcess {
                                 client.with_username(config.bridge.application_key.clone());
 
                             match client.test_connection().await {
-                                Ok(_) => println!("✅Bridge connection successful"),
-                                Err(e) => println!("❌Bridge connection failed: {e}"),
+                                Ok(_) => {
+                                    progress!("✅Bridge connection successful");
+                                    report.bridge_connected = true;
+                                    check_light_gamuts(&client, &config, &mut report).await;
+                                    check_scene_light_overlap(&client, &config, &mut report).await;
+                                }
+                                Err(e) => {
+                                    problem(&mut report, format!("Bridge connection failed: {e}"))
+                                }
                             }
                         }
-                        Err(e) => println!("❌Failed to create bridge client: {e}"),
+                        Err(e) => {
+                            problem(&mut report, format!("Failed to create bridge client: {e}"))
+                        }
                     }
                 }
-                Err(e) => println!("❌Failed to load configuration: {e}"),
+                Err(e) => problem(&mut report, format!("Failed to load configuration: {e}")),
             }
         } else {
-            println!("❌No configuration found. Run 'huestatus --setup' to configure.");
+            problem(
+                &mut report,
+                "No configuration found. Run 'huestatus --setup' to configure.".to_string(),
+            );
         }
 
-        Ok(())
+        Ok(report)
     }
 }
 
+/// Warn when the status lights mix color gamuts (informational only)
+async fn check_light_gamuts(
+    client: &BridgeClient,
+    config: &Config,
+    report: &mut DiagnosticsReport,
+) {
+    let Ok(lights) = client
+        .get_suitable_lights_in_group(&config.settings.recall_group_id)
+        .await
+    else {
+        return;
+    };
+
+    if let Some(gamuts) = gamut_mismatch(&lights) {
+        let warning = format!("Status lights use different color gamuts ({gamuts})");
+        progress!("⚠️{warning}");
+        report.warnings.push(warning);
+        progress!(
+            "   The same hue/saturation looks different on each; xy colors render more consistently"
+        );
+    }
+}
+
+/// Report which lights the success and failure scenes share (informational only)
+async fn check_scene_light_overlap(
+    client: &BridgeClient,
+    config: &Config,
+    report: &mut DiagnosticsReport,
+) {
+    let Ok(overlap) = SceneManager::new(client.clone())
+        .status_scene_light_overlap(config)
+        .await
+    else {
+        return;
+    };
+
+    if overlap.is_identical() && config.settings.distinct_scene_lights {
+        let warning = format!(
+            "Status scene lights: {} (settings.distinct_scene_lights is on)",
+            overlap.summary()
+        );
+        progress!("⚠️{warning}");
+        report.warnings.push(warning);
+    } else {
+        progress!("ℹ️Status scene lights: {}", overlap.summary());
+    }
+}
+
+/// Describe the gamut of each status light if they don't all match
+///
+/// Lights that don't report a gamut are left out.
+fn gamut_mismatch(lights: &[(String, Light)]) -> Option<String> {
+    let mut gamuts: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (_, light) in lights {
+        if let Some(gamut) = light.color_gamut_type() {
+            gamuts.entry(gamut).or_default().push(&light.name);
+        }
+    }
+
+    if gamuts.len() < 2 {
+        return None;
+    }
+
+    let groups: Vec<_> = gamuts
+        .iter()
+        .map(|(gamut, names)| format!("gamut {gamut}: {}", names.join(", ")))
+        .collect();
+    Some(groups.join("; "))
+}
+
 impl Default for SetupOptions {
     fn default() -> Self {
         Self {
@@ -570,6 +1064,17 @@ impl Default for SetupOptions {
             skip_validation: false,
             backup_existing: true,
             test_scenes: false,
+            insecure: false,
+            first_match: false,
+            network_scan: false,
+            min_brightness: None,
+            brightness: None,
+            check_only: false,
+            group_id: None,
+            assume_yes: false,
+            discovery_url: None,
+            remote_token: None,
+            strict_scene_names: false,
         }
     }
 }
@@ -606,6 +1111,71 @@ impl SetupOptions {
     }
 }
 
+impl SetupPlan {
+    /// Get plan summary
+    pub fn summary(&self) -> String {
+        let bridge = self
+            .bridge
+            .as_ref()
+            .map_or_else(|| "none found".to_string(), |bridge| bridge.ip.clone());
+        let lights = self
+            .suitable_lights
+            .map_or_else(|| "?".to_string(), |count| count.to_string());
+
+        format!(
+            "Setup plan: bridge {bridge}, {lights} suitable lights, {} scenes to create",
+            self.scenes.len()
+        )
+    }
+
+    /// Describe each step setup would take
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        lines.push(match (self.config_exists, self.force) {
+            (false, _) => format!("Config: would be written to {}", self.config_path),
+            (true, true) => format!(
+                "Config: {} exists and would be overwritten",
+                self.config_path
+            ),
+            (true, false) => format!(
+                "Config: {} exists; setup would stop (use --force to overwrite)",
+                self.config_path
+            ),
+        });
+
+        lines.push(match &self.bridge {
+            Some(bridge) => format!("Bridge: {}", bridge.display_name()),
+            None => "Bridge: none found automatically".to_string(),
+        });
+
+        lines.push(match self.key_valid {
+            Some(true) => "Authentication: existing application key is valid".to_string(),
+            _ => "Authentication: link button press required".to_string(),
+        });
+
+        lines.push(match self.suitable_lights {
+            Some(count) => format!("Lights: {count} suitable for status"),
+            None => "Lights: unknown until authenticated".to_string(),
+        });
+
+        for (name, existing) in &self.scenes {
+            lines.push(match existing {
+                Some(ExistingScene::Huestatus) => format!(
+                    "Scene: would update '{name}' if it still uses the same lights, otherwise create a new one"
+                ),
+                Some(ExistingScene::Foreign) => format!(
+                    "Scene: would create '{name}' (the scene with this name wasn't created by huestatus and is left as is)"
+                ),
+                _ => format!("Scene: would create '{name}'"),
+            });
+        }
+
+        lines.extend(self.notes.iter().map(|note| format!("Note: {note}")));
+        lines
+    }
+}
+
 impl SetupResult {
     /// Get setup summary
     pub fn summary(&self) -> String {
@@ -665,6 +1235,40 @@ mod tests {
         assert!(thorough_options.test_scenes);
     }
 
+    fn light(name: &str, gamut: Option<&str>) -> (String, Light) {
+        let light = serde_json::from_value(serde_json::json!({
+            "name": name,
+            "type": "Extended color light",
+            "state": { "on": true, "bri": 254, "reachable": true },
+            "capabilities": {
+                "certified": true,
+                "control": { "colorgamuttype": gamut },
+            },
+        }))
+        .unwrap();
+        (name.to_string(), light)
+    }
+
+    #[test]
+    fn test_gamut_mismatch() {
+        assert_eq!(
+            gamut_mismatch(&[light("Desk", Some("C")), light("Strip", Some("C"))]),
+            None
+        );
+        assert_eq!(
+            gamut_mismatch(&[light("Desk", Some("C")), light("Lamp", None)]),
+            None
+        );
+        assert_eq!(
+            gamut_mismatch(&[
+                light("Desk", Some("C")),
+                light("Bloom", Some("A")),
+                light("Strip", Some("C"))
+            ]),
+            Some("gamut A: Bloom; gamut C: Desk, Strip".to_string())
+        );
+    }
+
     #[test]
     fn test_setup_steps() {
         assert_eq!(SetupStep::Initialize, SetupStep::Initialize);
@@ -679,6 +1283,7 @@ mod tests {
             bridge_name: "Test Bridge".to_string(),
             username: "test-user".to_string(),
             scenes_created: 2,
+            scenes_updated: 0,
             lights_configured: 5,
             config_path: "/test/config.json".to_string(),
             duration_ms: 15000,
@@ -691,6 +1296,42 @@ mod tests {
         assert!(result.summary().contains("15.0s"));
     }
 
+    #[test]
+    fn test_setup_plan_lines() {
+        let plan = SetupPlan {
+            config_path: "/tmp/huestatus/config.json".to_string(),
+            config_exists: true,
+            force: false,
+            bridge: None,
+            key_valid: None,
+            suitable_lights: None,
+            scenes: vec![
+                ("huestatus-success".to_string(), None),
+                (
+                    "huestatus-failure".to_string(),
+                    Some(ExistingScene::Huestatus),
+                ),
+                (
+                    "huestatus-running".to_string(),
+                    Some(ExistingScene::Foreign),
+                ),
+            ],
+            notes: vec!["Automatic discovery failed".to_string()],
+        };
+
+        let lines = plan.lines();
+        assert!(lines[0].contains("use --force"));
+        assert_eq!(lines[1], "Bridge: none found automatically");
+        assert_eq!(lines[4], "Scene: would create 'huestatus-success'");
+        assert!(lines[5].starts_with("Scene: would update 'huestatus-failure'"));
+        assert!(lines[6].contains("wasn't created by huestatus and is left as is"));
+        assert_eq!(lines.last().unwrap(), "Note: Automatic discovery failed");
+        assert_eq!(
+            plan.summary(),
+            "Setup plan: bridge none found, ? suitable lights, 3 scenes to create"
+        );
+    }
+
     #[test]
     fn test_setup_status() {
         let status = SetupStatus {
@@ -0,0 +1,221 @@
+use crate::app::Huestatus;
+use crate::bridge::{BridgeClient, BridgeDiscovery};
+use crate::config::{load_config, save_config, Config};
+use crate::error::Result;
+use crate::scenes::SceneValidationResult;
+use crate::setup::confirm;
+use serde::Serialize;
+use std::path::Path;
+
+/// Repairs for problems found by `doctor`
+#[derive(Debug, Clone)]
+pub struct ConfigRepair {
+    verbose: bool,
+    allow_destructive: bool,
+}
+
+/// What a repair run changed, and what it left for the user to confirm
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RepairReport {
+    /// Changes that were made
+    pub changes: Vec<String>,
+    /// Problems that were found but not fixed
+    pub skipped: Vec<String>,
+}
+
+impl RepairReport {
+    /// Check if nothing needed repairing
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.skipped.is_empty()
+    }
+}
+
+impl ConfigRepair {
+    /// Create a repair run; destructive fixes (deleting scenes) are confirmed first unless allowed
+    pub fn new(verbose: bool, allow_destructive: bool) -> Self {
+        Self {
+            verbose,
+            allow_destructive,
+        }
+    }
+
+    /// Fix what can be fixed in the configuration file at `config_path` and on the bridge
+    pub async fn run(&self, config_path: &Path) -> Result<RepairReport> {
+        let mut config = load_config(config_path)?;
+        let mut report = RepairReport::default();
+
+        let mut app = Huestatus::new(config.clone())?;
+        if app.client().test_connection().await.is_err() {
+            match self.find_moved_bridge(&config).await {
+                Some(ip) => {
+                    report.changes.push(format!(
+                        "Updated bridge IP from {} to {ip}",
+                        config.bridge.ip
+                    ));
+                    config.bridge.ip = ip;
+                    app = Huestatus::new(config.clone())?;
+                }
+                None => {
+                    report.skipped.push(
+                        "Bridge is unreachable and wasn't found elsewhere on the network"
+                            .to_string(),
+                    );
+                    save_if_changed(&config, config_path, &report)?;
+                    return Ok(report);
+                }
+            }
+        }
+
+        if config.is_bridge_verification_stale() || !report.changes.is_empty() {
+            config.update_last_verified();
+            report
+                .changes
+                .push("Re-verified the bridge connection".to_string());
+        }
+
+        if config.is_capabilities_cache_stale() {
+            match app.client().get_capabilities().await {
+                Ok(capabilities) => {
+                    config.update_capabilities_cache(
+                        capabilities.scenes.total,
+                        capabilities.lights.total,
+                    );
+                    report
+                        .changes
+                        .push("Refreshed the bridge capabilities cache".to_string());
+                }
+                Err(e) => report
+                    .skipped
+                    .push(format!("Could not refresh bridge capabilities: {e}")),
+            }
+        }
+
+        let validation = app.scene_manager().validate_status_scenes(&config).await?;
+        if scenes_need_rebuild(&validation) {
+            let creation = app
+                .scene_manager()
+                .refresh_status_scenes(&mut config)
+                .await?;
+            report.changes.push(format!(
+                "Rebuilt the status scenes ({} created, {} updated)",
+                creation.scenes_created, creation.scenes_updated
+            ));
+            report.skipped.extend(creation.warnings);
+        }
+
+        // Other projects' scenes on the same bridge aren't orphans
+        let in_use = Config::get_config_dir()
+            .map(|dir| Config::scene_ids_in_use(&dir))
+            .unwrap_or_default();
+        let orphans = app
+            .scene_manager()
+            .find_orphaned_huestatus_scenes(&config, &in_use)
+            .await?;
+        if !orphans.is_empty() {
+            let names: Vec<_> = orphans
+                .iter()
+                .map(|(_, scene)| scene.name.as_str())
+                .collect();
+            let question = format!(
+                "Delete {} orphaned huestatus scene(s) ({})?",
+                orphans.len(),
+                names.join(", ")
+            );
+            if confirm(&question, false, self.allow_destructive) {
+                let deleted = app
+                    .scene_manager()
+                    .delete_orphaned_huestatus_scenes(&orphans)
+                    .await;
+                report.changes.push(format!(
+                    "Deleted {} orphaned huestatus scene(s)",
+                    deleted.len()
+                ));
+            } else {
+                report.skipped.push(format!(
+                    "Found {} orphaned huestatus scene(s) ({}); run with --yes to delete them",
+                    orphans.len(),
+                    names.join(", ")
+                ));
+            }
+        }
+
+        save_if_changed(&config, config_path, &report)?;
+        Ok(report)
+    }
+
+    /// Look for the configured bridge at a new address, identified by the stored key
+    async fn find_moved_bridge(&self, config: &Config) -> Option<String> {
+        if self.verbose {
+            eprintln!(
+                "🔍 Bridge unreachable at {}, searching the network...",
+                config.bridge.ip
+            );
+        }
+
+        let mut discovery = BridgeDiscovery::new().ok()?.with_verbose(self.verbose);
+        if let Some(url) = &config.settings.discovery_url {
+            discovery = discovery.with_discovery_url(url.clone());
+        }
+        let result = discovery.discover_all().await.ok()?;
+
+        for bridge in result.bridges {
+            if bridge.ip == config.bridge.ip {
+                continue;
+            }
+            let Ok(client) = BridgeClient::new(bridge.ip.clone()) else {
+                continue;
+            };
+            // Only the bridge that issued the key accepts it
+            let client = client.with_username(config.bridge.application_key.clone());
+            if client.get_lights().await.is_ok() {
+                return Some(bridge.ip);
+            }
+        }
+
+        None
+    }
+}
+
+/// Write the configuration back if any change was made
+fn save_if_changed(config: &Config, config_path: &Path, report: &RepairReport) -> Result<()> {
+    if report.changes.is_empty() {
+        return Ok(());
+    }
+    save_config(config, config_path)
+}
+
+/// Check if a status scene is definitely broken, rather than just uncheckable
+fn scenes_need_rebuild(results: &[SceneValidationResult]) -> bool {
+    results
+        .iter()
+        .any(|result| !result.is_valid && !result.inconclusive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(is_valid: bool, inconclusive: bool) -> SceneValidationResult {
+        SceneValidationResult {
+            scene_id: "1".to_string(),
+            scene_name: "huestatus-success".to_string(),
+            is_valid,
+            inconclusive,
+            issues: Vec::new(),
+            lights_status: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scenes_need_rebuild() {
+        assert!(!scenes_need_rebuild(&[result(true, false)]));
+        assert!(!scenes_need_rebuild(&[
+            result(true, false),
+            result(false, true)
+        ]));
+        assert!(scenes_need_rebuild(&[
+            result(true, false),
+            result(false, false)
+        ]));
+    }
+}
@@ -41,7 +41,7 @@ impl SetupValidator {
         }
 
         // Validate scenes
-        for scene_type in &["success", "failure"] {
+        for scene_type in &["success", "failure", "running"] {
             if let Some(scene_config) = config.get_scene(scene_type) {
                 if let Err(e) = client.get_scene(&scene_config.id).await {
                     warnings.push(format!("Scene '{}' not found: {}", scene_config.name, e));